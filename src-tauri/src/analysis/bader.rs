@@ -0,0 +1,463 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::analysis::symmetry::periodic_distance;
+use crate::parsers::atom_config::{CrystalStructure, VolumetricData};
+use crate::utils::geometry::cell_volume;
+use crate::utils::linalg::{inverse, mat_vec, vec_mat};
+
+/// 玻尔半径（Å），cube 文件的电荷密度单位为 e/Bohr³
+const BOHR: f64 = 0.529177210903;
+
+/// Bader 分析参数
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct BaderSettings {
+    pub data_index: usize,
+    pub vacuum_threshold: Option<f64>, // 密度低于该值的格点归入真空，不参与划分
+    pub refine: bool,                  // 是否对盆边界上的格点重新追踪（消除格点偏差）
+}
+
+impl Default for BaderSettings {
+    fn default() -> Self {
+        BaderSettings {
+            data_index: 0,
+            vacuum_threshold: None,
+            refine: true,
+        }
+    }
+}
+
+/// 单个原子的 Bader 电荷与体积
+#[derive(Serialize, Clone, Debug)]
+pub struct BaderAtom {
+    pub index: usize,
+    pub label: String,
+    pub element: String,
+    pub charge: f64,   // 原子盆内的电子数
+    pub volume: f64,   // 原子盆体积（Å³）
+    pub maxima: usize, // 归属该原子的密度极大值个数
+}
+
+/// 密度极大值（Bader 盆的吸引子）
+#[derive(Serialize, Clone, Debug)]
+pub struct BaderMaximum {
+    pub frac: [f64; 3],
+    pub value: f64,
+    pub atom: usize,    // 最近的原子
+    pub distance: f64,  // 到最近原子的距离（Å）
+}
+
+/// Bader 分析结果
+#[derive(Serialize, Clone, Debug)]
+pub struct BaderResult {
+    pub atoms: Vec<BaderAtom>,
+    pub maxima: Vec<BaderMaximum>,
+    pub vacuum_charge: f64,
+    pub vacuum_volume: f64,
+    pub total_charge: f64,
+    pub basin_data_index: Option<usize>, // 原子盆格点数据在结构中的序号，由命令层填写
+}
+
+/// 基于格点的 Bader 划分：near-grid 方法（Tang、Sanville、Henkelman 2009）
+/// 沿梯度方向上升，按累积的偏离量修正格点步长以消除格点方向偏差；
+/// 第一遍遇到已划分的格点即停止，随后对盆边界上的格点重新完整追踪
+/// 返回结果与逐格点的原子盆数据（值为原子序号，真空为 -1）
+pub fn bader_analysis(
+    structure: &CrystalStructure,
+    data: &VolumetricData,
+    settings: &BaderSettings,
+) -> Result<(BaderResult, VolumetricData), String> {
+    if structure.sites.is_empty() {
+        return Err("Structure has no atoms".to_string());
+    }
+    // 只有电荷密度能按盆积分；cube 文件不区分内容，由用户确认
+    if data.kind != "density" && data.kind != "cube" {
+        return Err(format!("Bader analysis needs charge density, not {} data", data.kind));
+    }
+    let dims = data.dims;
+    let total = dims[0] * dims[1] * dims[2];
+    let mut grid = Grid::new(data);
+
+    const VACUUM: i32 = -2;
+    const UNASSIGNED: i32 = -1;
+    let mut assignment = vec![UNASSIGNED; total];
+    if let Some(threshold) = settings.vacuum_threshold {
+        for (index, value) in data.values.iter().enumerate() {
+            if *value < threshold {
+                assignment[index] = VACUUM;
+            }
+        }
+    }
+
+    for start in 0..total {
+        if assignment[start] == UNASSIGNED {
+            let (basin, path) = grid.trace(start, |index| assignment[index] >= 0, &assignment);
+            for index in path {
+                assignment[index] = basin;
+            }
+        }
+    }
+
+    // 盆边界上的格点重新追踪，直到不再变化
+    if settings.refine {
+        for _ in 0..8 {
+            let edges: Vec<bool> = (0..total).map(|index| grid.is_edge(index, &assignment)).collect();
+            let starts: Vec<usize> = (0..total).filter(|&index| edges[index] && assignment[index] >= 0).collect();
+            let mut changed = 0;
+            for start in starts {
+                let (basin, _) = grid.trace(start, |_| false, &assignment);
+                if basin != assignment[start] {
+                    assignment[start] = basin;
+                    changed += 1;
+                }
+            }
+            if changed == 0 {
+                break;
+            }
+        }
+    }
+
+    // 极大值归属最近的原子
+    let lattice = &structure.lattice;
+    let maxima: Vec<BaderMaximum> = grid
+        .maxima
+        .iter()
+        .map(|&index| {
+            let frac = grid.frac(index);
+            let (atom, distance) = structure
+                .sites
+                .iter()
+                .map(|site| periodic_distance(lattice, frac, site.frac))
+                .enumerate()
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .unwrap();
+            BaderMaximum { frac, value: data.values[index], atom, distance }
+        })
+        .collect();
+
+    // cube 文件的密度单位为 e/Bohr³，体积元换算为 Bohr³
+    let voxel = cell_volume(&data.lattice) / total as f64;
+    let charge_voxel = if data.kind == "cube" { voxel / BOHR.powi(3) } else { voxel };
+    let mut atoms: Vec<BaderAtom> = structure
+        .sites
+        .iter()
+        .enumerate()
+        .map(|(index, site)| BaderAtom {
+            index,
+            label: site.label.clone(),
+            element: site.element.clone(),
+            charge: 0.0,
+            volume: 0.0,
+            maxima: maxima.iter().filter(|m| m.atom == index).count(),
+        })
+        .collect();
+    let (mut vacuum_charge, mut vacuum_volume) = (0.0, 0.0);
+    let mut basin_values = vec![-1.0; total];
+    for (index, &basin) in assignment.iter().enumerate() {
+        let value = data.values[index];
+        if basin >= 0 {
+            let atom = maxima[basin as usize].atom;
+            atoms[atom].charge += value * charge_voxel;
+            atoms[atom].volume += voxel;
+            basin_values[index] = atom as f64;
+        } else {
+            vacuum_charge += value * charge_voxel;
+            vacuum_volume += voxel;
+        }
+    }
+    let total_charge = atoms.iter().map(|a| a.charge).sum::<f64>() + vacuum_charge;
+
+    let basins = VolumetricData::new(
+        format!("{} Bader basins", data.name),
+        "basins".to_string(),
+        dims,
+        data.lattice,
+        data.origin,
+        basin_values,
+    );
+    Ok((
+        BaderResult { atoms, maxima, vacuum_charge, vacuum_volume, total_charge, basin_data_index: None },
+        basins,
+    ))
+}
+
+/// 原子盆的指示函数：属于 atoms 中任一原子的格点为 1，其余为 0，可在 0.5 处提取等值面
+pub fn basin_indicator(basins: &VolumetricData, atoms: &[usize]) -> VolumetricData {
+    let values = basins
+        .values
+        .iter()
+        .map(|&v| if v >= 0.0 && atoms.contains(&(v as usize)) { 1.0 } else { 0.0 })
+        .collect();
+    let name = basins.name.clone();
+    VolumetricData::new(name, "basins".to_string(), basins.dims, basins.lattice, basins.origin, values)
+}
+
+/// 梯度上升所需的格点信息
+struct Grid<'a> {
+    data: &'a VolumetricData,
+    inverse_lattice: [[f64; 3]; 3],
+    maxima: Vec<usize>,                 // 极大值格点序号，下标即盆编号
+    maximum_basin: HashMap<usize, i32>, // 极大值格点 -> 盆编号
+    stamp: Vec<u32>,                    // 记录格点在第几次追踪中经过，用于发现回路
+    trace_count: u32,
+}
+
+impl<'a> Grid<'a> {
+    fn new(data: &'a VolumetricData) -> Self {
+        let total = data.dims[0] * data.dims[1] * data.dims[2];
+        Grid {
+            data,
+            inverse_lattice: inverse(&data.lattice).unwrap_or([[0.0; 3]; 3]),
+            maxima: Vec::new(),
+            maximum_basin: HashMap::new(),
+            stamp: vec![0; total],
+            trace_count: 0,
+        }
+    }
+
+    fn coords(&self, index: usize) -> [i64; 3] {
+        let [na, nb, _] = self.data.dims;
+        [(index % na) as i64, ((index / na) % nb) as i64, (index / (na * nb)) as i64]
+    }
+
+    fn index(&self, p: [i64; 3]) -> usize {
+        let [na, nb, nc] = self.data.dims.map(|n| n as i64);
+        (p[0].rem_euclid(na) + na * (p[1].rem_euclid(nb) + nb * p[2].rem_euclid(nc))) as usize
+    }
+
+    fn frac(&self, index: usize) -> [f64; 3] {
+        let p = self.coords(index);
+        [0, 1, 2].map(|k| p[k] as f64 / self.data.dims[k] as f64)
+    }
+
+    fn value(&self, p: [i64; 3]) -> f64 {
+        self.data.value(p[0], p[1], p[2])
+    }
+
+    /// 相邻 6 个格点中存在属于其他盆的格点
+    fn is_edge(&self, index: usize, assignment: &[i32]) -> bool {
+        let p = self.coords(index);
+        [[1, 0, 0], [-1, 0, 0], [0, 1, 0], [0, -1, 0], [0, 0, 1], [0, 0, -1]]
+            .iter()
+            .any(|d| assignment[self.index([p[0] + d[0], p[1] + d[1], p[2] + d[2]])] != assignment[index])
+    }
+
+    /// 26 个相邻格点中按单位距离上升最快的方向；是局部极大值时返回 None
+    fn steepest_neighbor(&self, p: [i64; 3]) -> Option<[i64; 3]> {
+        let center = self.value(p);
+        let lattice = &self.data.lattice;
+        let mut best = None;
+        let mut best_slope = 0.0;
+        for dz in -1..=1 {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    if (dx, dy, dz) == (0, 0, 0) {
+                        continue;
+                    }
+                    let step = [dx, dy, dz];
+                    let frac = [0, 1, 2].map(|k| step[k] as f64 / self.data.dims[k] as f64);
+                    let length = vec_mat(frac, lattice).iter().map(|v| v * v).sum::<f64>().sqrt();
+                    let slope = (self.value([p[0] + dx, p[1] + dy, p[2] + dz]) - center) / length;
+                    if slope > best_slope {
+                        best_slope = slope;
+                        best = Some(step);
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    /// near-grid 步长：沿梯度方向的格点位移取整，取整误差累积到 drift 中，超过半个格点时修正
+    fn near_grid_step(&self, p: [i64; 3], drift: &mut [f64; 3]) -> [i64; 3] {
+        let dims = self.data.dims;
+        let du = [0, 1, 2].map(|k| {
+            let mut forward = p;
+            let mut backward = p;
+            forward[k] += 1;
+            backward[k] -= 1;
+            (self.value(forward) - self.value(backward)) / 2.0 * dims[k] as f64
+        });
+        // 笛卡尔梯度方向换算为格点位移
+        let gradient = mat_vec(&self.inverse_lattice, du);
+        let du = vec_mat(gradient, &self.inverse_lattice);
+        let grid_step = [0, 1, 2].map(|k| du[k] * dims[k] as f64);
+        let largest = grid_step.iter().map(|v| v.abs()).fold(0.0, f64::max);
+        if largest < 1e-300 {
+            return [0; 3];
+        }
+        let grid_step = grid_step.map(|v| v / largest);
+        let mut step = grid_step.map(|v| v.round() as i64);
+        for k in 0..3 {
+            drift[k] += grid_step[k] - step[k] as f64;
+            if drift[k].abs() > 0.5 {
+                let correction = drift[k].signum() as i64;
+                step[k] += correction;
+                drift[k] -= correction as f64;
+            }
+        }
+        step
+    }
+
+    /// 从 start 出发沿梯度上升，直到到达极大值或满足 stop 的格点；返回盆编号与经过的格点
+    fn trace(&mut self, start: usize, stop: impl Fn(usize) -> bool, assignment: &[i32]) -> (i32, Vec<usize>) {
+        self.trace_count += 1;
+        let mut path = Vec::new();
+        let mut p = self.coords(start);
+        let mut drift = [0.0; 3];
+        loop {
+            let index = self.index(p);
+            if index != start && stop(index) {
+                return (assignment[index], path);
+            }
+            if self.steepest_neighbor(p).is_none() {
+                path.push(index);
+                return (self.maximum(index), path);
+            }
+            if self.stamp[index] == self.trace_count {
+                // 在平坦区域形成回路，视为极大值
+                return (self.maximum(index), path);
+            }
+            self.stamp[index] = self.trace_count;
+            path.push(index);
+
+            let mut step = self.near_grid_step(p, &mut drift);
+            let next = [p[0] + step[0], p[1] + step[1], p[2] + step[2]];
+            // 修正后的步长不上升时退回到相邻格点中的最陡方向
+            if step == [0; 3] || self.value(next) <= self.value(p) {
+                step = self.steepest_neighbor(p).unwrap();
+                drift = [0.0; 3];
+            }
+            p = [p[0] + step[0], p[1] + step[1], p[2] + step[2]];
+        }
+    }
+
+    /// 极大值格点对应的盆编号，首次遇到时新建
+    fn maximum(&mut self, index: usize) -> i32 {
+        if let Some(&basin) = self.maximum_basin.get(&index) {
+            return basin;
+        }
+        let basin = self.maxima.len() as i32;
+        self.maxima.push(index);
+        self.maximum_basin.insert(index, basin);
+        basin
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+    use super::*;
+    use crate::parsers::atom_config::Site;
+    use crate::parsers::parser_utils::build_crystal_structure;
+    use crate::utils::geometry::frac_to_cart;
+    use crate::utils::linalg::{norm, sub};
+
+    const LENGTH: f64 = 10.0;
+    const POINTS: usize = 40;
+
+    /// 边长 10 Å 的立方晶胞中两个原子的高斯电荷密度，charges 为各原子的电子数
+    fn two_atoms(charges: [f64; 2]) -> (CrystalStructure, VolumetricData) {
+        let lattice = [[LENGTH, 0.0, 0.0], [0.0, LENGTH, 0.0], [0.0, 0.0, LENGTH]];
+        let sites = vec![
+            Site::new("Na1".to_string(), "Na".to_string(), [0.25, 0.5, 0.5]),
+            Site::new("Cl2".to_string(), "Cl".to_string(), [0.75, 0.5, 0.5]),
+        ];
+        let sigma: f64 = 0.6;
+        let norm_factor = (2.0 * PI * sigma * sigma).powf(1.5);
+        let mut values = Vec::with_capacity(POINTS.pow(3));
+        for k in 0..POINTS {
+            for j in 0..POINTS {
+                for i in 0..POINTS {
+                    let frac = [i, j, k].map(|n| n as f64 / POINTS as f64);
+                    let value: f64 = sites
+                        .iter()
+                        .zip(charges)
+                        .map(|(site, charge)| {
+                            let d = periodic_distance(&lattice, frac, site.frac);
+                            charge * (-d * d / (2.0 * sigma * sigma)).exp() / norm_factor
+                        })
+                        .sum();
+                    values.push(value);
+                }
+            }
+        }
+        let data = VolumetricData::new("total".to_string(), "density".to_string(), [POINTS; 3], lattice, [0.0; 3], values);
+        (build_crystal_structure(lattice, sites, &HashMap::new()), data)
+    }
+
+    #[test]
+    fn separated_atoms() {
+        let (structure, data) = two_atoms([6.0, 2.0]);
+        let (result, basins) = bader_analysis(&structure, &data, &BaderSettings::default()).unwrap();
+        assert!((result.total_charge - 8.0).abs() < 1e-6);
+        assert!((result.atoms[0].charge - 6.0).abs() < 1e-3);
+        assert!((result.atoms[1].charge - 2.0).abs() < 1e-3);
+        assert_eq!(result.vacuum_volume, 0.0);
+        let volume: f64 = result.atoms.iter().map(|atom| atom.volume).sum();
+        assert!((volume - LENGTH.powi(3)).abs() < 1e-6);
+
+        // 每个原子恰有一个极大值，位于原子所在的格点上
+        assert_eq!(result.maxima.len(), 2);
+        for maximum in &result.maxima {
+            assert!(maximum.distance < 1e-9);
+        }
+        assert!(result.atoms.iter().all(|atom| atom.maxima == 1));
+
+        let indicator = basin_indicator(&basins, &[1]);
+        let voxel = LENGTH.powi(3) / POINTS.pow(3) as f64;
+        let count = indicator.values.iter().filter(|&&v| v == 1.0).count();
+        assert!((count as f64 * voxel - result.atoms[1].volume).abs() < 1e-6);
+    }
+
+    #[test]
+    fn symmetric_basins() {
+        // 两个相同的原子平分晶胞，边界修正后体积偏差应在一层格点以内
+        let (structure, data) = two_atoms([4.0, 4.0]);
+        let (result, _) = bader_analysis(&structure, &data, &BaderSettings::default()).unwrap();
+        let half = LENGTH.powi(3) / 2.0;
+        let layer = LENGTH.powi(3) / POINTS as f64;
+        for atom in &result.atoms {
+            assert!((atom.volume - half).abs() <= layer, "{} vs {}", atom.volume, half);
+            assert!((atom.charge - 4.0).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn vacuum_threshold() {
+        let (structure, data) = two_atoms([6.0, 2.0]);
+        let settings = BaderSettings { vacuum_threshold: Some(1e-4), ..BaderSettings::default() };
+        let (result, basins) = bader_analysis(&structure, &data, &settings).unwrap();
+        assert!(result.vacuum_volume > 0.5 * LENGTH.powi(3));
+        assert!(result.vacuum_charge < 1e-2);
+        assert!((result.atoms[0].charge + result.atoms[1].charge + result.vacuum_charge - 8.0).abs() < 1e-6);
+        // 真空格点在原子盆数据中为 -1，且远离两个原子
+        let lattice = &structure.lattice;
+        let origin = frac_to_cart(lattice, structure.sites[0].frac);
+        for (index, &value) in basins.values.iter().enumerate() {
+            if value < 0.0 {
+                let p = [index % POINTS, (index / POINTS) % POINTS, index / (POINTS * POINTS)];
+                let position = frac_to_cart(lattice, p.map(|n| n as f64 / POINTS as f64));
+                assert!(norm(sub(position, origin)) > 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn empty_structure() {
+        let (mut structure, data) = two_atoms([1.0, 1.0]);
+        structure.sites.clear();
+        assert!(bader_analysis(&structure, &data, &BaderSettings::default()).is_err());
+    }
+
+    #[test]
+    fn non_density_data() {
+        let (structure, mut data) = two_atoms([1.0, 1.0]);
+        for kind in ["potential", "elf", "basins"] {
+            data.kind = kind.to_string();
+            assert!(bader_analysis(&structure, &data, &BaderSettings::default()).is_err());
+        }
+        data.kind = "cube".to_string();
+        assert!(bader_analysis(&structure, &data, &BaderSettings::default()).is_ok());
+    }
+}
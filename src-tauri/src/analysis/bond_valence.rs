@@ -0,0 +1,302 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::analysis::fragments::BOND_TOLERANCE;
+use crate::analysis::neighbors::neighbor_list;
+use crate::parsers::atom_config::{CrystalStructure, Site};
+use crate::utils::elements::element;
+use crate::utils::linalg::{Mat3, Vec3};
+
+/// 常见阴离子及其形式价态，按电负性从高到低排列
+pub const ANION_VALENCES: &[(&str, i32)] = &[
+    ("F", -1), ("O", -2), ("Cl", -1), ("N", -3), ("Br", -1),
+    ("I", -1), ("S", -2), ("Se", -2), ("Te", -2),
+];
+
+/// 键价参数 (阳离子, 价态, 阴离子, R0/Å, b/Å)
+/// reference: N. E. Brese, M. O'Keeffe, Acta Cryst. B47 (1991) 192; I. D. Brown, Acta Cryst. B41 (1985) 244
+const BOND_VALENCE_PARAMETERS: &[(&str, i32, &str, f64, f64)] = &[
+    ("H", 1, "O", 0.569, 0.37), ("Li", 1, "O", 1.466, 0.37), ("Na", 1, "O", 1.803, 0.37),
+    ("K", 1, "O", 2.132, 0.37), ("Rb", 1, "O", 2.263, 0.37), ("Cs", 1, "O", 2.417, 0.37),
+    ("Ag", 1, "O", 1.805, 0.37), ("Cu", 1, "O", 1.593, 0.37), ("Be", 2, "O", 1.381, 0.37),
+    ("Mg", 2, "O", 1.693, 0.37), ("Ca", 2, "O", 1.967, 0.37), ("Sr", 2, "O", 2.118, 0.37),
+    ("Ba", 2, "O", 2.285, 0.37), ("Mn", 2, "O", 1.790, 0.37), ("Fe", 2, "O", 1.734, 0.37),
+    ("Co", 2, "O", 1.692, 0.37), ("Ni", 2, "O", 1.654, 0.37), ("Cu", 2, "O", 1.679, 0.37),
+    ("Zn", 2, "O", 1.704, 0.37), ("Cd", 2, "O", 1.904, 0.37), ("Sn", 2, "O", 1.984, 0.37),
+    ("Pb", 2, "O", 2.112, 0.37), ("B", 3, "O", 1.371, 0.37), ("Al", 3, "O", 1.651, 0.37),
+    ("Ga", 3, "O", 1.730, 0.37), ("In", 3, "O", 1.902, 0.37), ("Sc", 3, "O", 1.849, 0.37),
+    ("Y", 3, "O", 2.019, 0.37), ("La", 3, "O", 2.172, 0.37), ("Ce", 3, "O", 2.151, 0.37),
+    ("Nd", 3, "O", 2.117, 0.37), ("Gd", 3, "O", 2.065, 0.37), ("Bi", 3, "O", 2.094, 0.37),
+    ("Sb", 3, "O", 1.973, 0.37), ("Ti", 3, "O", 1.791, 0.37), ("V", 3, "O", 1.743, 0.37),
+    ("Cr", 3, "O", 1.724, 0.37), ("Mn", 3, "O", 1.760, 0.37), ("Fe", 3, "O", 1.759, 0.37),
+    ("Co", 3, "O", 1.700, 0.37), ("C", 4, "O", 1.390, 0.37), ("Si", 4, "O", 1.624, 0.37),
+    ("Ge", 4, "O", 1.748, 0.37), ("Sn", 4, "O", 1.905, 0.37), ("Pb", 4, "O", 2.042, 0.37),
+    ("Ti", 4, "O", 1.815, 0.37), ("Zr", 4, "O", 1.937, 0.37), ("Hf", 4, "O", 1.923, 0.37),
+    ("V", 4, "O", 1.784, 0.37), ("Mn", 4, "O", 1.753, 0.37), ("Ce", 4, "O", 2.028, 0.37),
+    ("N", 5, "O", 1.432, 0.37), ("P", 5, "O", 1.617, 0.37), ("As", 5, "O", 1.767, 0.37),
+    ("Sb", 5, "O", 1.942, 0.37), ("V", 5, "O", 1.803, 0.37), ("Nb", 5, "O", 1.911, 0.37),
+    ("Ta", 5, "O", 1.920, 0.37), ("S", 6, "O", 1.624, 0.37), ("Cr", 6, "O", 1.794, 0.37),
+    ("Mo", 6, "O", 1.907, 0.37), ("W", 6, "O", 1.917, 0.37), ("U", 6, "O", 2.075, 0.37),
+    ("Li", 1, "F", 1.360, 0.37), ("Na", 1, "F", 1.677, 0.37), ("K", 1, "F", 1.992, 0.37),
+    ("Mg", 2, "F", 1.578, 0.37), ("Ca", 2, "F", 1.842, 0.37), ("Sr", 2, "F", 2.019, 0.37),
+    ("Ba", 2, "F", 2.188, 0.37), ("Al", 3, "F", 1.545, 0.37), ("Fe", 3, "F", 1.679, 0.37),
+    ("Ti", 4, "F", 1.760, 0.37), ("Li", 1, "Cl", 1.910, 0.37), ("Na", 1, "Cl", 2.150, 0.37),
+    ("K", 1, "Cl", 2.519, 0.37), ("Cs", 1, "Cl", 2.791, 0.37), ("Ag", 1, "Cl", 2.090, 0.37),
+    ("Mg", 2, "Cl", 2.080, 0.37), ("Ca", 2, "Cl", 2.370, 0.37), ("Zn", 2, "S", 2.090, 0.37),
+    ("Cd", 2, "S", 2.290, 0.37), ("Mn", 2, "S", 2.200, 0.37), ("Fe", 2, "S", 2.120, 0.37),
+    ("Co", 2, "S", 2.060, 0.37), ("Ni", 2, "S", 1.980, 0.37), ("Pb", 2, "S", 2.550, 0.37),
+    ("Ga", 3, "S", 2.163, 0.37), ("In", 3, "S", 2.370, 0.37), ("Sn", 4, "S", 2.390, 0.37),
+    ("Mo", 4, "S", 2.235, 0.37), ("Li", 1, "N", 1.610, 0.37), ("B", 3, "N", 1.470, 0.37),
+    ("Al", 3, "N", 1.790, 0.37), ("Ga", 3, "N", 1.840, 0.37), ("Si", 4, "N", 1.770, 0.37),
+    ("Ti", 4, "N", 1.930, 0.37),
+];
+
+/// 键价和计算参数
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct BvsSettings {
+    pub cutoff: f64, // 成键距离上限（Å）
+    pub oxidation_states: HashMap<String, i32>, // 按位点标签或元素指定价态，正价按阳离子、负价按阴离子处理，未指定时自动判断
+}
+
+impl Default for BvsSettings {
+    fn default() -> Self {
+        BvsSettings {
+            cutoff: 3.5,
+            oxidation_states: HashMap::new(),
+        }
+    }
+}
+
+/// 单个键的键价
+#[derive(Serialize, Clone, Debug)]
+pub struct BondValence {
+    pub index: usize,
+    pub label: String,
+    pub image: [i32; 3],
+    pub distance: f64,
+    pub valence: f64,
+}
+
+/// 位点的键价和
+#[derive(Serialize, Clone, Debug)]
+pub struct SiteValence {
+    pub index: usize,
+    pub label: String,
+    pub element: String,
+    pub oxidation_state: Option<i32>, // 阳离子缺少参数时为空
+    pub bond_valence_sum: f64,        // 阴离子为负值
+    pub deviation: Option<f64>,       // BVS 与形式价态之差
+    pub bonds: Vec<BondValence>,
+}
+
+/// 键价和分析结果
+#[derive(Serialize, Clone, Debug)]
+pub struct BvsResult {
+    pub sites: Vec<SiteValence>,
+    pub global_instability_index: f64,
+    pub missing_parameters: Vec<String>, // 缺少键价参数的阳离子-阴离子对
+}
+
+/// 阴离子的形式价态
+pub fn anion_valence(element: &str) -> Option<i32> {
+    ANION_VALENCES
+        .iter()
+        .find(|(name, _)| *name == element)
+        .map(|(_, valence)| *valence)
+}
+
+/// 阴离子元素的优先级（电负性越高越靠前），不在表中的元素排在最后
+fn anion_priority(element: &str) -> usize {
+    ANION_VALENCES
+        .iter()
+        .position(|(name, _)| *name == element)
+        .unwrap_or(ANION_VALENCES.len())
+}
+
+/// 按位点判断是否为阴离子：元素须在 anions 中，且不与优先级更高的阴离子元素成键，
+/// 如硫酸根中的 S、硝酸根中的 N 与 O 成键，按阳离子处理；成键按共价半径之和判断
+pub fn anion_sites(lattice: &Mat3, sites: &[Site], anions: &[String]) -> Vec<bool> {
+    let candidates: Vec<bool> = sites.iter().map(|site| anions.contains(&site.element)).collect();
+    let radius = |site: &Site| element(&site.element).and_then(|e| e.covalent_radius).unwrap_or(1.5);
+    let r_max = sites
+        .iter()
+        .zip(&candidates)
+        .filter(|(_, &candidate)| candidate)
+        .map(|(site, _)| radius(site))
+        .fold(0.0, f64::max);
+    if r_max == 0.0 {
+        return candidates;
+    }
+    let frac: Vec<Vec3> = sites.iter().map(|s| s.frac).collect();
+    let neighbors = neighbor_list(lattice, &frac, 2.0 * r_max * BOND_TOLERANCE);
+    (0..sites.len())
+        .map(|i| {
+            candidates[i]
+                && !neighbors[i].iter().any(|n| {
+                    candidates[n.index]
+                        && anion_priority(&sites[n.index].element) < anion_priority(&sites[i].element)
+                        && n.distance <= (radius(&sites[i]) + radius(&sites[n.index])) * BOND_TOLERANCE
+                })
+        })
+        .collect()
+}
+
+/// 查询键价参数 (R0, b)
+fn parameters(cation: &str, valence: i32, anion: &str) -> Option<(f64, f64)> {
+    BOND_VALENCE_PARAMETERS
+        .iter()
+        .find(|(c, v, a, _, _)| *c == cation && *v == valence && *a == anion)
+        .map(|(_, _, _, r0, b)| (*r0, *b))
+}
+
+/// 阳离子在表中出现的全部价态
+fn tabulated_valences(cation: &str) -> Vec<i32> {
+    let mut valences: Vec<i32> = BOND_VALENCE_PARAMETERS
+        .iter()
+        .filter(|(c, _, _, _, _)| *c == cation)
+        .map(|(_, v, _, _, _)| *v)
+        .collect();
+    valences.sort();
+    valences.dedup();
+    valences
+}
+
+/// 计算键价和与全局不稳定指数 GII
+/// 阴、阳离子按位点判断（见 anion_sites），指定的价态优先；
+/// 阳离子价态未指定时，选取使 |BVS - V| 最小的表中价态
+pub fn compute_bond_valence_sums(structure: &CrystalStructure, settings: &BvsSettings) -> BvsResult {
+    let sites = &structure.sites;
+    let frac: Vec<Vec3> = sites.iter().map(|s| s.frac).collect();
+    let neighbors = neighbor_list(&structure.lattice, &frac, settings.cutoff);
+    let given_state = |site: &Site| {
+        settings
+            .oxidation_states
+            .get(&site.label)
+            .or_else(|| settings.oxidation_states.get(&site.element))
+            .copied()
+    };
+    let anion_elements: Vec<String> = ANION_VALENCES.iter().map(|(name, _)| name.to_string()).collect();
+    let anions: Vec<bool> = anion_sites(&structure.lattice, sites, &anion_elements)
+        .into_iter()
+        .zip(sites)
+        .map(|(anion, site)| match given_state(site) {
+            Some(valence) if valence != 0 => valence < 0,
+            _ => anion,
+        })
+        .collect();
+    let is_anion = |index: usize| anions[index];
+
+    // 给定阳离子价态时，该阳离子与周围阴离子的键价
+    let bond_valences = |center: usize, valence: i32| -> Vec<(usize, Option<f64>)> {
+        neighbors[center]
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| is_anion(n.index))
+            .map(|(k, n)| {
+                let value = parameters(&sites[center].element, valence, &sites[n.index].element)
+                    .map(|(r0, b)| ((r0 - n.distance) / b).exp());
+                (k, value)
+            })
+            .collect()
+    };
+
+    // 确定各阳离子的价态
+    let mut states: Vec<Option<i32>> = vec![None; sites.len()];
+    for (index, site) in sites.iter().enumerate() {
+        if is_anion(index) {
+            continue;
+        }
+        states[index] = match given_state(site) {
+            Some(valence) => Some(valence),
+            None => tabulated_valences(&site.element)
+                .into_iter()
+                .map(|valence| {
+                    let sum: f64 = bond_valences(index, valence).iter().filter_map(|(_, v)| *v).sum();
+                    (valence, (sum - valence as f64).abs())
+                })
+                .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+                .map(|(valence, _)| valence),
+        };
+    }
+
+    // 计算阳离子的键价，并将同一键的键价计入阴离子
+    let mut results: Vec<SiteValence> = sites
+        .iter()
+        .enumerate()
+        .map(|(index, site)| SiteValence {
+            index,
+            label: site.label.clone(),
+            element: site.element.clone(),
+            oxidation_state: if is_anion(index) {
+                given_state(site).or_else(|| anion_valence(&site.element))
+            } else {
+                states[index]
+            },
+            bond_valence_sum: 0.0,
+            deviation: None,
+            bonds: Vec::new(),
+        })
+        .collect();
+    let mut missing: Vec<String> = Vec::new();
+    for index in 0..sites.len() {
+        if is_anion(index) {
+            continue;
+        }
+        let Some(valence) = states[index] else {
+            let pair = format!("{} (no tabulated oxidation state)", sites[index].element);
+            if !missing.contains(&pair) {
+                missing.push(pair);
+            }
+            continue;
+        };
+        for (k, value) in bond_valences(index, valence) {
+            let neighbor = &neighbors[index][k];
+            let Some(value) = value else {
+                let pair = format!("{}{:+}-{}", sites[index].element, valence, sites[neighbor.index].element);
+                if !missing.contains(&pair) {
+                    missing.push(pair);
+                }
+                continue;
+            };
+            results[index].bond_valence_sum += value;
+            results[index].bonds.push(BondValence {
+                index: neighbor.index,
+                label: sites[neighbor.index].label.clone(),
+                image: neighbor.image,
+                distance: neighbor.distance,
+                valence: value,
+            });
+            results[neighbor.index].bond_valence_sum -= value;
+            results[neighbor.index].bonds.push(BondValence {
+                index,
+                label: sites[index].label.clone(),
+                image: neighbor.image.map(|t| -t),
+                distance: neighbor.distance,
+                valence: value,
+            });
+        }
+    }
+
+    // GII = sqrt(Σ (BVS - V)² / N)
+    let mut squares = 0.0;
+    let mut count = 0;
+    for result in results.iter_mut() {
+        if let Some(valence) = result.oxidation_state {
+            let deviation = result.bond_valence_sum - valence as f64;
+            result.deviation = Some(deviation);
+            squares += deviation * deviation;
+            count += 1;
+        }
+    }
+    let global_instability_index = if count > 0 {
+        (squares / count as f64).sqrt()
+    } else {
+        0.0
+    };
+
+    BvsResult {
+        sites: results,
+        global_instability_index,
+        missing_parameters: missing,
+    }
+}
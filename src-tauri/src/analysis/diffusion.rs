@@ -0,0 +1,402 @@
+use serde::{Deserialize, Serialize};
+use crate::parsers::trajectory::Frame;
+use crate::utils::elements::element;
+use crate::utils::geometry::frac_to_cart;
+use crate::utils::linalg::{add, scale, sub, Vec3};
+
+/// 1 Å²/fs 换算为 cm²/s
+const ANGSTROM2_PER_FS_TO_CM2_PER_S: f64 = 0.1;
+
+/// 自动选择时间原点间隔时，时间原点数的上限
+const MAX_AUTO_ORIGINS: usize = 200;
+
+/// 均方位移与速度自相关函数的计算参数
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct DiffusionSettings {
+    pub timestep: f64,        // 相邻两帧的时间间隔（fs）
+    pub start: usize,         // 跳过开头的平衡段帧数
+    pub stride: usize,        // 每隔 stride 帧取一帧
+    pub max_lag: f64,         // 最大时间间隔占分析时长的比例
+    pub origin_stride: usize, // 时间原点的间隔（抽样后的帧数），0 为自动
+    pub fit_range: [f64; 2],  // 线性拟合区间占最大时间间隔的比例
+    pub remove_drift: bool,   // 扣除体系质心的漂移
+    pub vacf: bool,           // 同时计算速度自相关函数
+}
+
+impl Default for DiffusionSettings {
+    fn default() -> Self {
+        DiffusionSettings {
+            timestep: 1.0,
+            start: 0,
+            stride: 1,
+            max_lag: 0.5,
+            origin_stride: 0,
+            fit_range: [0.2, 0.8],
+            remove_drift: true,
+            vacf: true,
+        }
+    }
+}
+
+/// 均方位移的线性拟合 MSD = slope·t + intercept
+#[derive(Serialize, Clone, Debug)]
+pub struct LinearFit {
+    pub slope: f64,      // Å²/ps
+    pub intercept: f64,  // Å²
+    pub r_squared: f64,
+    pub range: [f64; 2], // 拟合区间（ps）
+}
+
+/// 单个元素的扩散结果
+#[derive(Serialize, Clone, Debug)]
+pub struct SpeciesDiffusion {
+    pub element: String,
+    pub count: usize,
+    pub msd: Vec<f64>,                 // 均方位移（Å²），与 time 对应
+    pub msd_components: [Vec<f64>; 3], // 沿笛卡尔 x、y、z 的分量
+    pub fit: LinearFit,
+    pub diffusion: f64,                // 扩散系数 D = slope / 6（cm²/s）
+    pub diffusion_components: [f64; 3], // 沿 x、y、z 的一维扩散系数 slope / 2（cm²/s）
+    pub vacf: Option<Vec<f64>>,        // 归一化的速度自相关函数，与 time 对应
+}
+
+/// 扩散分析结果，所有曲线共用同一组时间间隔
+#[derive(Serialize, Clone, Debug)]
+pub struct DiffusionResult {
+    pub time: Vec<f64>, // 时间间隔（ps）
+    pub species: Vec<SpeciesDiffusion>,
+    pub frame_count: usize,              // 参与分析的帧数
+    pub origin_stride: usize,            // 实际使用的时间原点间隔
+    pub velocity_source: Option<String>, // file：文件中的速度；positions：由相邻帧位置差分
+}
+
+/// 对轨迹计算各元素的均方位移、扩散系数与速度自相关函数
+/// 帧通过 read_frame 逐帧读取，只保留展开后的笛卡尔坐标与速度；
+/// 相邻帧之间按最小镜像展开坐标，要求原子在一帧间隔内移动不超过半个晶胞
+pub fn diffusion_analysis(
+    frame_count: usize,
+    read_frame: impl Fn(usize) -> Result<Frame, String>,
+    settings: &DiffusionSettings,
+) -> Result<DiffusionResult, String> {
+    if settings.timestep <= 0.0 {
+        return Err("Timestep must be positive".to_string());
+    }
+    let [fit_start, fit_end] = settings.fit_range;
+    if !(0.0..1.0).contains(&fit_start) || fit_end <= fit_start || fit_end > 1.0 {
+        return Err("Fit range must satisfy 0 <= start < end <= 1".to_string());
+    }
+    let stride = settings.stride.max(1);
+    let indices: Vec<usize> = (settings.start..frame_count).step_by(stride).collect();
+    if indices.len() < 3 {
+        return Err("At least three frames are needed for diffusion analysis".to_string());
+    }
+    let dt = settings.timestep * stride as f64;
+
+    // 逐帧展开坐标
+    let first = read_frame(indices[0])?;
+    let elements: Vec<String> = first.sites.iter().map(|site| site.element.clone()).collect();
+    let atom_count = elements.len();
+    let mut positions: Vec<Vec<Vec3>> = Vec::with_capacity(indices.len());
+    let mut file_velocities: Option<Vec<Vec<Vec3>>> = Some(Vec::with_capacity(indices.len()));
+    let mut previous: Vec<Vec3> = Vec::new();
+    for (n, &index) in indices.iter().enumerate() {
+        let frame = if n == 0 { first.clone() } else { read_frame(index)? };
+        if frame.sites.len() != atom_count {
+            return Err(format!("Frame {} has {} atoms, expected {}", index, frame.sites.len(), atom_count));
+        }
+        let current: Vec<Vec3> = frame.sites.iter().map(|site| site.frac).collect();
+        let cart = if n == 0 {
+            current.iter().map(|&frac| frac_to_cart(&frame.lattice, frac)).collect()
+        } else {
+            let last = &positions[n - 1];
+            current
+                .iter()
+                .zip(&previous)
+                .zip(last)
+                .map(|((&now, &before), &position)| {
+                    let step = sub(now, before).map(|d| d - d.round());
+                    add(position, frac_to_cart(&frame.lattice, step))
+                })
+                .collect()
+        };
+        positions.push(cart);
+        previous = current;
+        file_velocities = match (file_velocities, frame.velocities) {
+            (Some(mut all), Some(velocities)) => {
+                all.push(velocities);
+                Some(all)
+            }
+            _ => None,
+        };
+    }
+
+    if settings.remove_drift {
+        remove_drift(&mut positions, &elements);
+    }
+
+    let frames = positions.len();
+    let max_lag = ((frames - 1) as f64 * settings.max_lag.clamp(0.0, 1.0)).floor().max(2.0) as usize;
+    let max_lag = max_lag.min(frames - 1);
+    let origin_stride = match settings.origin_stride {
+        0 => (frames / MAX_AUTO_ORIGINS).max(1),
+        stride => stride,
+    };
+    let time: Vec<f64> = (0..=max_lag).map(|lag| lag as f64 * dt / 1000.0).collect();
+
+    // 元素分组，按首次出现的顺序
+    let mut species: Vec<String> = Vec::new();
+    for element in &elements {
+        if !species.contains(element) {
+            species.push(element.clone());
+        }
+    }
+    let species_of: Vec<usize> = elements
+        .iter()
+        .map(|element| species.iter().position(|s| s == element).unwrap())
+        .collect();
+    let counts: Vec<usize> = (0..species.len())
+        .map(|s| species_of.iter().filter(|&&k| k == s).count())
+        .collect();
+
+    // 均方位移，对时间原点与同种原子平均
+    let mut msd = vec![vec![[0.0; 3]; max_lag + 1]; species.len()];
+    for lag in 1..=max_lag {
+        let origins: Vec<usize> = (0..frames - lag).step_by(origin_stride).collect();
+        for &origin in &origins {
+            for (atom, &s) in species_of.iter().enumerate() {
+                let d = sub(positions[origin + lag][atom], positions[origin][atom]);
+                for k in 0..3 {
+                    msd[s][lag][k] += d[k] * d[k];
+                }
+            }
+        }
+        for (s, &count) in counts.iter().enumerate() {
+            let norm = (origins.len() * count) as f64;
+            msd[s][lag] = msd[s][lag].map(|v| v / norm);
+        }
+    }
+
+    // 速度自相关函数：优先使用文件中的速度，否则由相邻帧的位置向前差分
+    let (velocities, velocity_source) = match file_velocities {
+        _ if !settings.vacf => (None, None),
+        Some(velocities) => (Some(velocities), Some("file")),
+        None => {
+            let velocities = positions
+                .windows(2)
+                .map(|pair| pair[1].iter().zip(&pair[0]).map(|(&b, &a)| scale(sub(b, a), 1.0 / dt)).collect())
+                .collect();
+            (Some(velocities), Some("positions"))
+        }
+    };
+    let vacf = velocities.map(|velocities| velocity_autocorrelation(&velocities, &species_of, species.len(), max_lag, origin_stride));
+
+    let fit_lags = ((fit_start * max_lag as f64).ceil() as usize).max(1)..=((fit_end * max_lag as f64).floor() as usize);
+    let species = species
+        .into_iter()
+        .enumerate()
+        .map(|(s, element)| {
+            let total: Vec<f64> = msd[s].iter().map(|m| m[0] + m[1] + m[2]).collect();
+            let fit = linear_fit(&time, &total, fit_lags.clone())?;
+            let component_slopes = [0, 1, 2].map(|k| {
+                let series: Vec<f64> = msd[s].iter().map(|m| m[k]).collect();
+                linear_fit(&time, &series, fit_lags.clone()).map(|f| f.slope).unwrap_or(0.0)
+            });
+            // 斜率单位 Å²/ps，换算为 Å²/fs 后再换算为 cm²/s
+            let to_cm2 = |slope: f64| slope / 1000.0 * ANGSTROM2_PER_FS_TO_CM2_PER_S;
+            Ok(SpeciesDiffusion {
+                diffusion: to_cm2(fit.slope / 6.0),
+                diffusion_components: component_slopes.map(|slope| to_cm2(slope / 2.0)),
+                msd: total,
+                msd_components: [0, 1, 2].map(|k| msd[s].iter().map(|m| m[k]).collect()),
+                fit,
+                count: counts[s],
+                vacf: vacf.as_ref().map(|vacf| vacf[s].clone()),
+                element,
+            })
+        })
+        .collect::<Result<_, String>>()?;
+
+    Ok(DiffusionResult {
+        time,
+        species,
+        frame_count: frames,
+        origin_stride,
+        velocity_source: velocity_source.map(str::to_string),
+    })
+}
+
+/// 每帧扣除按原子量加权的质心位移
+fn remove_drift(positions: &mut [Vec<Vec3>], elements: &[String]) {
+    let masses: Vec<f64> = elements
+        .iter()
+        .map(|symbol| element(symbol).map(|e| e.mass).unwrap_or(1.0))
+        .collect();
+    let total: f64 = masses.iter().sum();
+    let center = |frame: &[Vec3]| {
+        frame
+            .iter()
+            .zip(&masses)
+            .fold([0.0; 3], |acc, (&r, &m)| add(acc, scale(r, m / total)))
+    };
+    let origin = center(&positions[0]);
+    for frame in positions.iter_mut() {
+        let drift = sub(center(frame), origin);
+        for r in frame.iter_mut() {
+            *r = sub(*r, drift);
+        }
+    }
+}
+
+/// 各元素归一化的速度自相关函数 <v(0)·v(t)> / <v(0)·v(0)>
+fn velocity_autocorrelation(
+    velocities: &[Vec<Vec3>],
+    species_of: &[usize],
+    species_count: usize,
+    max_lag: usize,
+    origin_stride: usize,
+) -> Vec<Vec<f64>> {
+    let frames = velocities.len();
+    let max_lag = max_lag.min(frames.saturating_sub(1));
+    let mut vacf = vec![vec![0.0; max_lag + 1]; species_count];
+    for lag in 0..=max_lag {
+        let origins: Vec<usize> = (0..frames - lag).step_by(origin_stride).collect();
+        let mut sums = vec![0.0; species_count];
+        for &origin in &origins {
+            for (atom, &s) in species_of.iter().enumerate() {
+                let (a, b) = (velocities[origin][atom], velocities[origin + lag][atom]);
+                sums[s] += a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+            }
+        }
+        for s in 0..species_count {
+            vacf[s][lag] = sums[s] / origins.len() as f64;
+        }
+    }
+    for series in vacf.iter_mut() {
+        let c0 = series[0];
+        if c0 > 0.0 {
+            series.iter_mut().for_each(|v| *v /= c0);
+        }
+    }
+    vacf
+}
+
+/// 对 lags 范围内的点做最小二乘直线拟合
+fn linear_fit(x: &[f64], y: &[f64], lags: std::ops::RangeInclusive<usize>) -> Result<LinearFit, String> {
+    let points: Vec<(f64, f64)> = lags.filter(|&lag| lag < x.len()).map(|lag| (x[lag], y[lag])).collect();
+    if points.len() < 2 {
+        return Err("Fit range contains fewer than two points".to_string());
+    }
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let sxx: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+    let sxy: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    let syy: f64 = points.iter().map(|p| (p.1 - mean_y).powi(2)).sum();
+    let slope = sxy / sxx;
+    let intercept = mean_y - slope * mean_x;
+    let r_squared = if syy > 0.0 { sxy * sxy / (sxx * syy) } else { 1.0 };
+    Ok(LinearFit {
+        slope,
+        intercept,
+        r_squared,
+        range: [points[0].0, points[points.len() - 1].0],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::atom_config::Site;
+    use crate::utils::geometry::wrap_frac;
+
+    const LENGTH: f64 = 10.0;
+
+    /// 边长 10 Å 的立方晶胞，position(n) 给出第 n 帧各原子的笛卡尔坐标（Å），写入帧时折回晶胞
+    fn trajectory(position: impl Fn(usize) -> Vec<Vec3>) -> impl Fn(usize) -> Result<Frame, String> {
+        move |n| {
+            let sites = position(n)
+                .into_iter()
+                .map(|r| Site::new("Li".to_string(), "Li".to_string(), wrap_frac(r.map(|x| x / LENGTH))))
+                .collect();
+            Ok(Frame {
+                lattice: [[LENGTH, 0.0, 0.0], [0.0, LENGTH, 0.0], [0.0, 0.0, LENGTH]],
+                sites,
+                velocities: None,
+            })
+        }
+    }
+
+    #[test]
+    fn ballistic_motion() {
+        // 匀速运动：MSD = (v·t)²，每帧 0.3 Å，40 帧内多次穿过晶胞边界
+        let frames = 40;
+        let read = trajectory(|n| vec![[1.0 + 0.3 * n as f64, 5.0, 5.0]]);
+        let settings = DiffusionSettings { timestep: 2.0, remove_drift: false, max_lag: 1.0, ..DiffusionSettings::default() };
+        let result = diffusion_analysis(frames, read, &settings).unwrap();
+        let li = &result.species[0];
+        assert_eq!(result.time.len(), frames);
+        for (lag, &msd) in li.msd.iter().enumerate() {
+            let expected = (0.3 * lag as f64).powi(2);
+            assert!((msd - expected).abs() < 1e-9, "lag {}: {} vs {}", lag, msd, expected);
+            assert!(li.msd_components[1][lag].abs() < 1e-12);
+        }
+        // 速度由位置差分得到，恒为常数
+        let vacf = li.vacf.as_ref().unwrap();
+        assert!(vacf.iter().all(|&v| (v - 1.0).abs() < 1e-9));
+        assert_eq!(result.velocity_source.as_deref(), Some("positions"));
+    }
+
+    #[test]
+    fn unwrap_across_boundary() {
+        // 原子从 9.5 Å 出发每帧沿 -x、+y 各移动 0.8 Å，第一帧后即跨过边界
+        let frames = 10;
+        let read = trajectory(|n| vec![[9.5 - 0.8 * n as f64, 9.5 + 0.8 * n as f64, 0.1]]);
+        let settings = DiffusionSettings { remove_drift: false, max_lag: 1.0, origin_stride: frames, ..DiffusionSettings::default() };
+        let result = diffusion_analysis(frames, read, &settings).unwrap();
+        let li = &result.species[0];
+        let last = frames - 1;
+        let expected = (0.8 * last as f64).powi(2);
+        assert!((li.msd_components[0][last] - expected).abs() < 1e-9);
+        assert!((li.msd_components[1][last] - expected).abs() < 1e-9);
+        assert!((li.msd[last] - 2.0 * expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn diffusion_coefficient_units() {
+        // 只取第 0 帧为时间原点，每个方向 x² = 2·D·t，MSD = 6·D·t 严格线性
+        // D = 1e-5 cm²/s = 0.1 Å²/ps = 1e-4 Å²/fs
+        let frames = 101;
+        let timestep = 2.0;
+        let d = 1e-4;
+        let read = trajectory(|n| {
+            let x = (2.0 * d * n as f64 * timestep).sqrt();
+            vec![[1.0 + x, 1.0 + x, 1.0 + x]]
+        });
+        let settings = DiffusionSettings {
+            timestep,
+            remove_drift: false,
+            max_lag: 1.0,
+            origin_stride: frames,
+            vacf: false,
+            ..DiffusionSettings::default()
+        };
+        let result = diffusion_analysis(frames, read, &settings).unwrap();
+        let li = &result.species[0];
+        assert!((li.fit.slope - 0.6).abs() < 1e-9);
+        assert!((li.fit.r_squared - 1.0).abs() < 1e-9);
+        assert!((li.diffusion - 1e-5).abs() < 1e-14);
+        assert!(li.diffusion_components.iter().all(|&component| (component - 1e-5).abs() < 1e-14));
+        assert!(li.vacf.is_none());
+    }
+
+    #[test]
+    fn invalid_settings() {
+        let read = trajectory(|_| vec![[1.0, 1.0, 1.0]]);
+        let settings = DiffusionSettings { timestep: 0.0, ..DiffusionSettings::default() };
+        assert!(diffusion_analysis(10, &read, &settings).is_err());
+        let settings = DiffusionSettings { fit_range: [0.8, 0.2], ..DiffusionSettings::default() };
+        assert!(diffusion_analysis(10, &read, &settings).is_err());
+        assert!(diffusion_analysis(2, &read, &DiffusionSettings::default()).is_err());
+    }
+}
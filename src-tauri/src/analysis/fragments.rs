@@ -0,0 +1,192 @@
+use crate::analysis::neighbors::neighbor_list;
+use crate::parsers::atom_config::Site;
+use crate::utils::elements::element;
+use crate::utils::geometry::wrap_frac;
+use crate::utils::linalg::{Mat3, Vec3};
+
+/// 成键判据：距离小于两原子共价半径之和乘以该系数
+pub const BOND_TOLERANCE: f64 = 1.15;
+
+/// 通过成键关系连通的一组位点
+#[derive(Clone, Debug)]
+pub struct Fragment {
+    pub sites: Vec<usize>,
+    pub images: Vec<[i32; 3]>, // 各位点（折回晶胞后）需要平移的晶胞数，平移后片段连成一体
+    pub periodic: bool,        // 片段经周期性边界与自身相连（骨架、无机晶体），无法展开为有限分子
+}
+
+/// 按共价半径判断成键，用广度优先搜索划分连通片段
+/// 缺少共价半径的元素按 1.5 Å 计
+pub fn find_fragments(lattice: &Mat3, sites: &[Site]) -> Vec<Fragment> {
+    let radii: Vec<f64> = sites
+        .iter()
+        .map(|site| element(&site.element).and_then(|e| e.covalent_radius).unwrap_or(1.5))
+        .collect();
+    let r_max = radii.iter().copied().fold(0.0, f64::max);
+    let frac: Vec<Vec3> = sites.iter().map(|site| wrap_frac(site.frac)).collect();
+    let neighbors = neighbor_list(lattice, &frac, 2.0 * r_max * BOND_TOLERANCE);
+
+    let mut images: Vec<Option<[i32; 3]>> = vec![None; sites.len()];
+    let mut fragments = Vec::new();
+    for start in 0..sites.len() {
+        if images[start].is_some() {
+            continue;
+        }
+        images[start] = Some([0, 0, 0]);
+        let mut fragment = Fragment { sites: vec![start], images: vec![[0, 0, 0]], periodic: false };
+        let mut queue = vec![start];
+        while let Some(i) = queue.pop() {
+            let image = images[i].unwrap();
+            for neighbor in &neighbors[i] {
+                let j = neighbor.index;
+                if neighbor.distance < 1e-8 || neighbor.distance > (radii[i] + radii[j]) * BOND_TOLERANCE {
+                    continue;
+                }
+                let target = [0, 1, 2].map(|k| image[k] + neighbor.image[k]);
+                match images[j] {
+                    None => {
+                        images[j] = Some(target);
+                        fragment.sites.push(j);
+                        fragment.images.push(target);
+                        queue.push(j);
+                    }
+                    // 同一位点经不同路径到达不同镜像，说明片段沿周期方向无限延伸
+                    Some(existing) if existing != target => fragment.periodic = true,
+                    Some(_) => {}
+                }
+            }
+        }
+        fragments.push(fragment);
+    }
+    fragments
+}
+
+/// 按片段生成绘制位置，返回 (有限片段的原子位置, 留在晶胞内绘制的位点)
+/// grow 为 false 时每个有限分子只画一份，整体平移使几何中心落在晶胞内；
+/// grow 为 true 时晶胞内每个原子所属的分子都完整画出，同一分子的同一镜像只画一次；
+/// 周期性片段无法展开，其位点按晶胞视图处理
+pub fn fragment_positions(lattice: &Mat3, sites: &[Site], grow: bool) -> (Vec<(usize, Vec3)>, Vec<usize>) {
+    let mut placed = Vec::new();
+    let mut cell_sites = Vec::new();
+    for fragment in find_fragments(lattice, sites) {
+        if fragment.periodic {
+            cell_sites.extend(&fragment.sites);
+            continue;
+        }
+        let unwrapped: Vec<Vec3> = fragment
+            .sites
+            .iter()
+            .zip(&fragment.images)
+            .map(|(&i, image)| {
+                let frac = wrap_frac(sites[i].frac);
+                [0, 1, 2].map(|k| frac[k] + image[k] as f64)
+            })
+            .collect();
+        let shifts: Vec<[i32; 3]> = if grow {
+            // 位点 i 在晶胞内的那份拷贝对应平移 -image_i
+            let mut shifts: Vec<[i32; 3]> = fragment.images.iter().map(|image| image.map(|v| -v)).collect();
+            shifts.sort_unstable();
+            shifts.dedup();
+            shifts
+        } else {
+            let count = unwrapped.len() as f64;
+            let center = [0, 1, 2].map(|k| unwrapped.iter().map(|f| f[k]).sum::<f64>() / count);
+            vec![center.map(|c| -c.floor() as i32)]
+        };
+        for shift in shifts {
+            for (&i, frac) in fragment.sites.iter().zip(&unwrapped) {
+                placed.push((i, [0, 1, 2].map(|k| frac[k] + shift[k] as f64)));
+            }
+        }
+    }
+    (placed, cell_sites)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::geometry::frac_to_cart;
+    use crate::utils::linalg::{norm, sub};
+
+    fn cubic(length: f64) -> Mat3 {
+        [[length, 0.0, 0.0], [0.0, length, 0.0], [0.0, 0.0, length]]
+    }
+
+    fn site(element: &str, frac: Vec3) -> Site {
+        Site::new(element.to_string(), element.to_string(), frac)
+    }
+
+    /// 10 Å 立方晶胞中跨过 a 方向晶胞面的 N₂ 分子（键长 1.0 Å）与一个孤立的 Ar 原子
+    fn straddling() -> (Mat3, Vec<Site>) {
+        let sites = vec![
+            site("N", [0.96, 0.5, 0.5]),
+            site("N", [0.06, 0.5, 0.5]),
+            site("Ar", [0.5, 0.2, 0.2]),
+        ];
+        (cubic(10.0), sites)
+    }
+
+    #[test]
+    fn molecule_across_face() {
+        let (lattice, sites) = straddling();
+        let fragments = find_fragments(&lattice, &sites);
+        assert_eq!(fragments.len(), 2);
+        let molecule = &fragments[0];
+        assert_eq!(molecule.sites, vec![0, 1]);
+        assert_eq!(molecule.images, vec![[0, 0, 0], [1, 0, 0]]);
+        assert!(!molecule.periodic);
+
+        // 每个分子只画一份，键长保持 1.0 Å，几何中心在晶胞内
+        let (placed, cell_sites) = fragment_positions(&lattice, &sites, false);
+        assert!(cell_sites.is_empty());
+        assert_eq!(placed.len(), 3);
+        let bond = norm(sub(frac_to_cart(&lattice, placed[0].1), frac_to_cart(&lattice, placed[1].1)));
+        assert!((bond - 1.0).abs() < 1e-9);
+        let center = (placed[0].1[0] + placed[1].1[0]) / 2.0;
+        assert!((0.0..1.0).contains(&center));
+    }
+
+    #[test]
+    fn grow_molecules() {
+        // 晶胞内两个 N 原子各自所属的分子都完整画出：两份 N₂，各有一个原子在晶胞内
+        let (lattice, sites) = straddling();
+        let (placed, _) = fragment_positions(&lattice, &sites, true);
+        let nitrogen: Vec<&(usize, Vec3)> = placed.iter().filter(|(i, _)| *i < 2).collect();
+        assert_eq!(nitrogen.len(), 4);
+        for pair in nitrogen.chunks(2) {
+            let bond = norm(sub(frac_to_cart(&lattice, pair[0].1), frac_to_cart(&lattice, pair[1].1)));
+            assert!((bond - 1.0).abs() < 1e-9);
+            assert!(pair.iter().any(|(_, f)| (0.0..1.0).contains(&f[0])));
+        }
+        for (i, nitrogen_site) in sites.iter().enumerate().take(2) {
+            let inside = nitrogen.iter().filter(|(j, f)| *j == i && (f[0] - nitrogen_site.frac[0]).abs() < 1e-9).count();
+            assert_eq!(inside, 1);
+        }
+    }
+
+    #[test]
+    fn isolated_atom() {
+        let (lattice, sites) = straddling();
+        for grow in [false, true] {
+            let (placed, _) = fragment_positions(&lattice, &sites, grow);
+            let argon: Vec<&(usize, Vec3)> = placed.iter().filter(|(i, _)| *i == 2).collect();
+            assert_eq!(argon.len(), 1);
+            assert_eq!(argon[0].1, sites[2].frac);
+        }
+    }
+
+    #[test]
+    fn periodic_chain() {
+        // 沿 a 方向间距 1.5 Å 的碳链，经周期性边界与自身相连
+        let lattice = [[3.0, 0.0, 0.0], [0.0, 5.0, 0.0], [0.0, 0.0, 5.0]];
+        let sites = vec![site("C", [0.0, 0.5, 0.5]), site("C", [0.5, 0.5, 0.5])];
+        let fragments = find_fragments(&lattice, &sites);
+        assert_eq!(fragments.len(), 1);
+        assert!(fragments[0].periodic);
+        for grow in [false, true] {
+            let (placed, cell_sites) = fragment_positions(&lattice, &sites, grow);
+            assert!(placed.is_empty());
+            assert_eq!(cell_sites, vec![0, 1]);
+        }
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+use serde::Deserialize;
+use crate::parsers::atom_config::VolumetricData;
+use crate::utils::geometry::frac_to_display;
+use crate::utils::linalg::{cross, dot, inverse, mat_vec, norm, scale, sub, Vec3};
+
+/// 等值面参数
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct IsosurfaceSettings {
+    pub data_index: usize, // 使用结构中的第几个格点数据（0 为总量，1 为磁化/自旋向下）
+    pub level: f64,        // 等值面数值
+    pub both_signs: bool,  // 同时提取 -level 处符号相反的面，用于波函数与差分密度
+    pub step: usize,       // 格点抽样间隔，1 为使用全部格点
+}
+
+impl Default for IsosurfaceSettings {
+    fn default() -> Self {
+        IsosurfaceSettings {
+            data_index: 0,
+            level: 0.05,
+            both_signs: false,
+            step: 1,
+        }
+    }
+}
+
+/// 三角网格，坐标与法向为绘制坐标，法向指向数值绝对值减小的一侧
+#[derive(Clone, Debug, Default)]
+pub struct IsoMesh {
+    pub level: f64,
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    pub triangles: Vec<[u32; 3]>,
+}
+
+/// 立方体角点序号按 x + 2y + 4z 编号，沿体对角线 0-7 分为 6 个四面体
+/// 相邻立方体共享面上的对角线一致，生成的曲面没有裂缝
+const TETRAHEDRA: [[usize; 4]; 6] = [
+    [0, 1, 3, 7],
+    [0, 3, 2, 7],
+    [0, 2, 6, 7],
+    [0, 6, 4, 7],
+    [0, 4, 5, 7],
+    [0, 5, 1, 7],
+];
+
+/// 周期性等值面提取：把每个格点立方体分为 6 个四面体的 marching cubes 变体（marching tetrahedra），
+/// 没有经典 marching cubes 的二义性，曲面封闭
+/// 格点立方体跨越晶胞边界时按周期性取值，曲面覆盖整个晶胞
+/// level 为负时提取 f < level 区域的边界
+pub fn extract_isosurface(data: &VolumetricData, level: f64, step: usize) -> IsoMesh {
+    let step = step.max(1);
+    let counts = data.dims.map(|n| n.div_ceil(step));
+    let positive = level >= 0.0;
+    // 区域内部：正值面为 f > level，负值面为 f < level
+    let inside = |value: f64| if positive { value > level } else { value < level };
+    let corner_offset = |corner: usize| [corner & 1, (corner >> 1) & 1, (corner >> 2) & 1];
+
+    // 抽样后的格点 (i, j, k)，对应原格点 (i·step, j·step, k·step)，最后一层折回到 0
+    let grid_index = |p: [usize; 3]| {
+        [0, 1, 2].map(|axis| if p[axis] >= counts[axis] { 0 } else { (p[axis] * step) as i64 })
+    };
+    let sample = |p: [usize; 3]| {
+        let [i, j, k] = grid_index(p);
+        data.value(i, j, k)
+    };
+    let frac_of = |p: [usize; 3]| {
+        [0, 1, 2].map(|axis| (p[axis] * step).min(data.dims[axis]) as f64 / data.dims[axis] as f64)
+    };
+
+    // 数值对分数坐标的梯度换算为笛卡尔梯度：∂f/∂r = L⁻¹ · ∂f/∂u
+    let inverse_lattice = inverse(&data.lattice).unwrap_or([[0.0; 3]; 3]);
+    let gradient = |p: [usize; 3]| -> Vec3 {
+        let [i, j, k] = grid_index(p);
+        let s = step as i64;
+        let du = [
+            (data.value(i + s, j, k) - data.value(i - s, j, k)) * data.dims[0] as f64 / (2 * step) as f64,
+            (data.value(i, j + s, k) - data.value(i, j - s, k)) * data.dims[1] as f64 / (2 * step) as f64,
+            (data.value(i, j, k + s) - data.value(i, j, k - s)) * data.dims[2] as f64 / (2 * step) as f64,
+        ];
+        mat_vec(&inverse_lattice, du)
+    };
+
+    let mut mesh = IsoMesh { level, ..Default::default() };
+    let mut edge_vertices: HashMap<([usize; 3], [usize; 3]), u32> = HashMap::new();
+    let mut edge_vertex = |mesh: &mut IsoMesh, a: [usize; 3], b: [usize; 3]| -> u32 {
+        let key = if a <= b { (a, b) } else { (b, a) };
+        if let Some(&index) = edge_vertices.get(&key) {
+            return index;
+        }
+        let (fa, fb) = (sample(a), sample(b));
+        let t = if (fb - fa).abs() > 1e-300 { ((level - fa) / (fb - fa)).clamp(0.0, 1.0) } else { 0.5 };
+        let (ua, ub) = (frac_of(a), frac_of(b));
+        let frac = [0, 1, 2].map(|k| ua[k] + (ub[k] - ua[k]) * t);
+        let position = frac_to_display(&data.lattice, frac);
+        let (ga, gb) = (gradient(a), gradient(b));
+        let mut normal = [0, 1, 2].map(|k| ga[k] + (gb[k] - ga[k]) * t);
+        // 法向指向区域外侧：正值面为 -∇f，负值面为 +∇f
+        if positive {
+            normal = scale(normal, -1.0);
+        }
+        let length = norm(normal);
+        let normal = if length > 0.0 { scale(normal, 1.0 / length) } else { [0.0; 3] };
+        let index = mesh.positions.len() as u32;
+        mesh.positions.push(position.map(|v| v as f32));
+        mesh.normals.push(normal.map(|v| v as f32));
+        edge_vertices.insert(key, index);
+        index
+    };
+
+    for k in 0..counts[2] {
+        for j in 0..counts[1] {
+            for i in 0..counts[0] {
+                let corners: [[usize; 3]; 8] = std::array::from_fn(|c| {
+                    let o = corner_offset(c);
+                    [i + o[0], j + o[1], k + o[2]]
+                });
+                let states: [bool; 8] = corners.map(|c| inside(sample(c)));
+                if states.iter().all(|&s| s) || states.iter().all(|&s| !s) {
+                    continue;
+                }
+                for tetrahedron in TETRAHEDRA {
+                    let (inner, outer): (Vec<usize>, Vec<usize>) =
+                        tetrahedron.iter().partition(|&&c| states[c]);
+                    let polygon: Vec<u32> = match (inner.len(), outer.len()) {
+                        (1, 3) => outer.iter().map(|&o| edge_vertex(&mut mesh, corners[inner[0]], corners[o])).collect(),
+                        (3, 1) => inner.iter().map(|&n| edge_vertex(&mut mesh, corners[n], corners[outer[0]])).collect(),
+                        (2, 2) => vec![
+                            edge_vertex(&mut mesh, corners[inner[0]], corners[outer[0]]),
+                            edge_vertex(&mut mesh, corners[inner[0]], corners[outer[1]]),
+                            edge_vertex(&mut mesh, corners[inner[1]], corners[outer[1]]),
+                            edge_vertex(&mut mesh, corners[inner[1]], corners[outer[0]]),
+                        ],
+                        _ => continue,
+                    };
+                    for n in 1..polygon.len() - 1 {
+                        push_triangle(&mut mesh, [polygon[0], polygon[n], polygon[n + 1]]);
+                    }
+                }
+            }
+        }
+    }
+    mesh
+}
+
+/// 添加三角形，按顶点法向调整绕序使正面朝外（逆时针）；退化三角形被丢弃
+fn push_triangle(mesh: &mut IsoMesh, triangle: [u32; 3]) {
+    let [a, b, c] = triangle.map(|v| mesh.positions[v as usize].map(|x| x as f64));
+    let face = cross(sub(b, a), sub(c, a));
+    if norm(face) < 1e-14 {
+        return;
+    }
+    let normal = triangle
+        .iter()
+        .map(|&v| mesh.normals[v as usize].map(|x| x as f64))
+        .fold([0.0; 3], |acc, n| [acc[0] + n[0], acc[1] + n[1], acc[2] + n[2]]);
+    if dot(face, normal) < 0.0 {
+        mesh.triangles.push([triangle[0], triangle[2], triangle[1]]);
+    } else {
+        mesh.triangles.push(triangle);
+    }
+}
+
+/// 把网格编码为小端二进制，便于前端直接构造类型化数组：
+/// u32 网格数，随后每个网格依次为 f32 level、u32 顶点数 nv、u32 三角形数 nt、
+/// f32 × 3nv 顶点坐标、f32 × 3nv 法向、u32 × 3nt 顶点序号；各段均按 4 字节对齐
+pub fn encode_meshes(meshes: &[IsoMesh]) -> Vec<u8> {
+    let size: usize = meshes
+        .iter()
+        .map(|m| 12 + 24 * m.positions.len() + 12 * m.triangles.len())
+        .sum();
+    let mut bytes = Vec::with_capacity(4 + size);
+    bytes.extend_from_slice(&(meshes.len() as u32).to_le_bytes());
+    for mesh in meshes {
+        bytes.extend_from_slice(&(mesh.level as f32).to_le_bytes());
+        bytes.extend_from_slice(&(mesh.positions.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&(mesh.triangles.len() as u32).to_le_bytes());
+        for value in mesh.positions.iter().chain(&mesh.normals).flatten() {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        for index in mesh.triangles.iter().flatten() {
+            bytes.extend_from_slice(&index.to_le_bytes());
+        }
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use super::*;
+
+    const LENGTH: f64 = 10.0;
+    const POINTS: usize = 24;
+    const SIGMA: f64 = 1.5;
+
+    /// 10 Å 立方晶胞中心的高斯函数，峰值为 sign
+    fn gaussian(sign: f64) -> VolumetricData {
+        let lattice = [[LENGTH, 0.0, 0.0], [0.0, LENGTH, 0.0], [0.0, 0.0, LENGTH]];
+        let mut values = Vec::with_capacity(POINTS.pow(3));
+        for k in 0..POINTS {
+            for j in 0..POINTS {
+                for i in 0..POINTS {
+                    let r = [i, j, k].map(|n| (n as f64 / POINTS as f64 - 0.5) * LENGTH);
+                    values.push(sign * (-dot(r, r) / (2.0 * SIGMA * SIGMA)).exp());
+                }
+            }
+        }
+        VolumetricData::new("total".to_string(), "density".to_string(), [POINTS; 3], lattice, [0.0; 3], values)
+    }
+
+    /// 网格封闭且绕序一致：每条有向边恰好出现一次，且其反向边也存在
+    fn assert_closed(mesh: &IsoMesh) {
+        let mut edges = HashSet::new();
+        for triangle in &mesh.triangles {
+            for n in 0..3 {
+                assert!(edges.insert((triangle[n], triangle[(n + 1) % 3])));
+            }
+        }
+        for &(a, b) in &edges {
+            assert!(edges.contains(&(b, a)));
+        }
+    }
+
+    #[test]
+    fn closed_gaussian_blob() {
+        // f = 0.5 的球面半径为 σ·√(2 ln 2)，晶胞中心即绘制坐标原点
+        let radius = SIGMA * (2.0 * 2f64.ln()).sqrt();
+        for sign in [1.0, -1.0] {
+            let mesh = extract_isosurface(&gaussian(sign), 0.5 * sign, 1);
+            assert!(!mesh.triangles.is_empty());
+            assert_closed(&mesh);
+            for (position, normal) in mesh.positions.iter().zip(&mesh.normals) {
+                let p = position.map(|x| x as f64);
+                assert!((norm(p) - radius).abs() < 0.1, "{} vs {}", norm(p), radius);
+                // 法向指向球外
+                assert!(dot(p, normal.map(|x| x as f64)) > 0.0);
+            }
+            // 三角形正面朝外
+            for triangle in &mesh.triangles {
+                let [a, b, c] = triangle.map(|v| mesh.positions[v as usize].map(|x| x as f64));
+                assert!(dot(cross(sub(b, a), sub(c, a)), a) > 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn coarse_step_and_empty_level() {
+        let data = gaussian(1.0);
+        let coarse = extract_isosurface(&data, 0.5, 2);
+        assert_closed(&coarse);
+        assert!(coarse.triangles.len() < extract_isosurface(&data, 0.5, 1).triangles.len());
+        assert!(extract_isosurface(&data, 2.0, 1).triangles.is_empty());
+    }
+
+    #[test]
+    fn encoded_layout() {
+        let mesh = IsoMesh {
+            level: -0.25,
+            positions: vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+            normals: vec![[0.0, 0.0, 1.0]; 3],
+            triangles: vec![[0, 1, 2]],
+        };
+        let bytes = encode_meshes(&[mesh, IsoMesh::default()]);
+        assert_eq!(bytes.len(), 4 + (12 + 24 * 3 + 12) + 12);
+        let word = |n: usize| <[u8; 4]>::try_from(&bytes[4 * n..4 * n + 4]).unwrap();
+        let u32_at = |n: usize| u32::from_le_bytes(word(n));
+        let f32_at = |n: usize| f32::from_le_bytes(word(n));
+        assert_eq!(u32_at(0), 2);
+        assert_eq!(f32_at(1), -0.25);
+        assert_eq!((u32_at(2), u32_at(3)), (3, 1));
+        // 顶点坐标（第二个顶点的 x）、法向（第一个法向的 z）、顶点序号
+        assert_eq!(f32_at(4 + 3), 1.0);
+        assert_eq!(f32_at(4 + 9 + 2), 1.0);
+        assert_eq!([u32_at(22), u32_at(23), u32_at(24)], [0, 1, 2]);
+        // 空网格只有头部
+        assert_eq!([u32_at(26), u32_at(27)], [0, 0]);
+    }
+}
@@ -0,0 +1,316 @@
+use serde::Serialize;
+use crate::analysis::neighbors::neighbor_list;
+use crate::parsers::atom_config::CrystalStructure;
+use crate::utils::geometry::{frac_to_cart, lattice_from_parameters, lattice_parameters};
+use crate::utils::linalg::{cross, dot, norm, sub, vec_mat, Mat3, Vec3};
+
+/// 参与测量的原子：位点序号与所在的晶胞平移
+type AtomRef = (usize, [i32; 3]);
+
+/// 带标准差的测量结果（距离单位 Å，角度单位度）
+#[derive(Serialize, Clone, Debug)]
+pub struct Measurement {
+    pub labels: Vec<String>,
+    pub value: f64,
+    pub esd: Option<f64>, // 文件未提供标准差时为空
+}
+
+/// 距离表中的一项，对应 CIF 的 _geom_bond 循环
+#[derive(Serialize, Clone, Debug)]
+pub struct BondEntry {
+    pub label_1: String,
+    pub label_2: String,
+    pub index: usize,          // 第二个原子的位点序号
+    pub image: [i32; 3],       // 第二个原子所在的晶胞平移
+    pub site_symmetry: String, // CIF 记法的对称代码，如 1_554
+    pub distance: f64,
+    pub esd: Option<f64>,
+}
+
+/// 最小镜像约定下，使原子 j 距离原子 i 最近的晶胞平移
+pub fn minimum_image(lattice: &Mat3, fi: Vec3, fj: Vec3) -> [i32; 3] {
+    let diff = sub(fj, fi);
+    let base = diff.map(|d| -d.round() as i32);
+    // 斜交晶胞中四舍五入不一定最近，在周围再搜索一圈
+    let mut best = (f64::MAX, base);
+    for da in -1..=1 {
+        for db in -1..=1 {
+            for dc in -1..=1 {
+                let image = [base[0] + da, base[1] + db, base[2] + dc];
+                let d = [
+                    diff[0] + image[0] as f64,
+                    diff[1] + image[1] as f64,
+                    diff[2] + image[2] as f64,
+                ];
+                let distance = norm(vec_mat(d, lattice));
+                if distance < best.0 {
+                    best = (distance, image);
+                }
+            }
+        }
+    }
+    best.1
+}
+
+/// 两原子间距离
+pub fn measure_distance(structure: &CrystalStructure, i: usize, j: usize) -> Result<Measurement, String> {
+    check_indices(structure, &[i, j])?;
+    let image_j = minimum_image(&structure.lattice, structure.sites[i].frac, structure.sites[j].frac);
+    let atoms = [(i, [0, 0, 0]), (j, image_j)];
+    Ok(measure(structure, &atoms, |p| norm(sub(p[1], p[0]))))
+}
+
+/// 键角 i-j-k，j 为顶点
+pub fn measure_angle(
+    structure: &CrystalStructure,
+    i: usize,
+    j: usize,
+    k: usize,
+) -> Result<Measurement, String> {
+    check_indices(structure, &[i, j, k])?;
+    let frac_j = structure.sites[j].frac;
+    let image_i = minimum_image(&structure.lattice, frac_j, structure.sites[i].frac);
+    let image_k = minimum_image(&structure.lattice, frac_j, structure.sites[k].frac);
+    let atoms = [(i, image_i), (j, [0, 0, 0]), (k, image_k)];
+    Ok(measure(structure, &atoms, |p| angle(sub(p[0], p[1]), sub(p[2], p[1]))))
+}
+
+/// 二面角 i-j-k-l（IUPAC 符号约定，范围 -180° ~ 180°）
+pub fn measure_dihedral(
+    structure: &CrystalStructure,
+    i: usize,
+    j: usize,
+    k: usize,
+    l: usize,
+) -> Result<Measurement, String> {
+    check_indices(structure, &[i, j, k, l])?;
+    // 沿 i→j→k→l 依次取最小镜像，保证四个原子连成一条链
+    let mut atoms: Vec<AtomRef> = vec![(i, [0, 0, 0])];
+    for &next in &[j, k, l] {
+        let (previous, image) = *atoms.last().unwrap();
+        let step = minimum_image(
+            &structure.lattice,
+            structure.sites[previous].frac,
+            structure.sites[next].frac,
+        );
+        atoms.push((next, [image[0] + step[0], image[1] + step[1], image[2] + step[2]]));
+    }
+    Ok(measure(structure, &atoms, |p| {
+        dihedral(sub(p[1], p[0]), sub(p[2], p[1]), sub(p[3], p[2]))
+    }))
+}
+
+/// 列出原子 i 周围 r_max 以内的全部距离
+pub fn distance_table(
+    structure: &CrystalStructure,
+    i: usize,
+    r_max: f64,
+) -> Result<Vec<BondEntry>, String> {
+    check_indices(structure, &[i])?;
+    let frac: Vec<Vec3> = structure.sites.iter().map(|s| s.frac).collect();
+    let neighbors = neighbor_list(&structure.lattice, &frac, r_max);
+
+    Ok(neighbors[i]
+        .iter()
+        .map(|neighbor| {
+            let atoms = [(i, [0, 0, 0]), (neighbor.index, neighbor.image)];
+            let distance = measure(structure, &atoms, |p| norm(sub(p[1], p[0])));
+            BondEntry {
+                label_1: structure.sites[i].label.clone(),
+                label_2: structure.sites[neighbor.index].label.clone(),
+                index: neighbor.index,
+                image: neighbor.image,
+                site_symmetry: site_symmetry_code(neighbor.image),
+                distance: distance.value,
+                esd: distance.esd,
+            }
+        })
+        .collect())
+}
+
+/// 检查位点序号是否有效
+fn check_indices(structure: &CrystalStructure, indices: &[usize]) -> Result<(), String> {
+    match indices.iter().find(|&&index| index >= structure.sites.len()) {
+        Some(index) => Err(format!(
+            "Atom index {} out of range (structure has {} atoms)",
+            index,
+            structure.sites.len()
+        )),
+        None => Ok(()),
+    }
+}
+
+/// 计算几何量并用数值微分传播晶格参数与分数坐标的标准差（忽略协方差）
+fn measure(structure: &CrystalStructure, atoms: &[AtomRef], f: impl Fn(&[Vec3]) -> f64) -> Measurement {
+    // 参数依次为晶格参数和每个不同位点的分数坐标
+    let mut params: Vec<f64> = lattice_parameters(&structure.lattice).to_vec();
+    let mut sigmas: Vec<f64> = structure.cell_esd.to_vec();
+    let mut offsets: Vec<usize> = Vec::new();
+    let mut unique: Vec<usize> = Vec::new();
+    for &(index, _) in atoms {
+        let slot = match unique.iter().position(|&u| u == index) {
+            Some(slot) => slot,
+            None => {
+                let site = &structure.sites[index];
+                params.extend_from_slice(&site.frac);
+                sigmas.extend_from_slice(&site.frac_esd);
+                unique.push(index);
+                unique.len() - 1
+            }
+        };
+        offsets.push(6 + 3 * slot);
+    }
+
+    let evaluate_in = |lattice: &Mat3, p: &[f64]| -> f64 {
+        let positions: Vec<Vec3> = atoms
+            .iter()
+            .zip(offsets.iter())
+            .map(|((_, image), &offset)| {
+                let frac = [
+                    p[offset] + image[0] as f64,
+                    p[offset + 1] + image[1] as f64,
+                    p[offset + 2] + image[2] as f64,
+                ];
+                frac_to_cart(lattice, frac)
+            })
+            .collect();
+        f(&positions)
+    };
+    // 数值取自原晶格；由晶格参数重建的晶格总是右手系，只用于求导
+    let evaluate = |p: &[f64]| evaluate_in(&lattice_from_parameters(p[0], p[1], p[2], p[3], p[4], p[5]), p);
+
+    let value = evaluate_in(&structure.lattice, &params);
+    let esd = if sigmas.iter().all(|&s| s == 0.0) {
+        None
+    } else {
+        let mut variance = 0.0;
+        for k in 0..params.len() {
+            if sigmas[k] == 0.0 {
+                continue;
+            }
+            let h = 1e-6 * params[k].abs().max(1.0);
+            let mut plus = params.clone();
+            let mut minus = params.clone();
+            plus[k] += h;
+            minus[k] -= h;
+            // 角度差折回 (-180, 180]，避免二面角跨越 ±180° 时出错
+            let mut delta = evaluate(&plus) - evaluate(&minus);
+            if delta > 180.0 {
+                delta -= 360.0;
+            } else if delta < -180.0 {
+                delta += 360.0;
+            }
+            let derivative = delta / (2.0 * h);
+            variance += (derivative * sigmas[k]).powi(2);
+        }
+        Some(variance.sqrt())
+    };
+
+    Measurement {
+        labels: atoms
+            .iter()
+            .map(|&(index, _)| structure.sites[index].label.clone())
+            .collect(),
+        value,
+        esd,
+    }
+}
+
+/// 两向量夹角（度）
+fn angle(u: Vec3, v: Vec3) -> f64 {
+    (dot(u, v) / (norm(u) * norm(v))).clamp(-1.0, 1.0).acos().to_degrees()
+}
+
+/// 由三个键向量计算二面角（度）：atan2(|b2|·b1·(b2×b3), (b1×b2)·(b2×b3))
+fn dihedral(b1: Vec3, b2: Vec3, b3: Vec3) -> f64 {
+    let n1 = cross(b1, b2);
+    let n2 = cross(b2, b3);
+    let y = norm(b2) * dot(b1, n2);
+    let x = dot(n1, n2);
+    y.atan2(x).to_degrees()
+}
+
+/// 将晶胞平移写为 CIF 对称代码（恒等操作），如 [0, 0, -1] -> 1_554
+fn site_symmetry_code(image: [i32; 3]) -> String {
+    if image.iter().all(|t| (-5..=4).contains(t)) {
+        format!("1_{}{}{}", 5 + image[0], 5 + image[1], 5 + image[2])
+    } else {
+        format!("1_{}_{}_{}", 5 + image[0], 5 + image[1], 5 + image[2])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use super::*;
+    use crate::parsers::atom_config::Site;
+    use crate::parsers::parser_utils::build_crystal_structure;
+
+    fn structure(lattice: Mat3, fracs: &[Vec3]) -> CrystalStructure {
+        let sites = fracs
+            .iter()
+            .enumerate()
+            .map(|(n, &frac)| Site::new(format!("C{}", n + 1), "C".to_string(), frac))
+            .collect();
+        build_crystal_structure(lattice, sites, &HashMap::new())
+    }
+
+    fn cubic(a: f64) -> Mat3 {
+        [[a, 0.0, 0.0], [0.0, a, 0.0], [0.0, 0.0, a]]
+    }
+
+    #[test]
+    fn distance_across_boundary() {
+        let structure = structure(cubic(10.0), &[[0.05, 0.5, 0.5], [0.95, 0.5, 0.5]]);
+        let distance = measure_distance(&structure, 0, 1).unwrap();
+        assert!((distance.value - 1.0).abs() < 1e-12);
+        assert_eq!(distance.labels, ["C1", "C2"]);
+        assert!(distance.esd.is_none());
+        assert!(measure_distance(&structure, 0, 2).is_err());
+    }
+
+    #[test]
+    fn angle_across_boundary() {
+        // 顶点在晶胞角上，两个端点在相对的面附近
+        let structure = structure(cubic(10.0), &[[0.1, 0.0, 0.0], [0.0, 0.0, 0.0], [0.0, 0.9, 0.0]]);
+        let angle = measure_angle(&structure, 0, 1, 2).unwrap();
+        assert!((angle.value - 90.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn signed_dihedral() {
+        // i = (1, 0, 0)、j = 0、k = (0, 0, 1)、l = (0, 1, 1)，IUPAC 约定为 +90°
+        let fracs = [[0.1, 0.0, 0.0], [0.0, 0.0, 0.0], [0.0, 0.0, 0.1], [0.0, 0.1, 0.1]];
+        let dihedral = measure_dihedral(&structure(cubic(10.0), &fracs), 0, 1, 2, 3).unwrap();
+        assert!((dihedral.value - 90.0).abs() < 1e-9, "{}", dihedral.value);
+        let mirrored = fracs.map(|[x, y, z]| [x, -y, z]);
+        let dihedral = measure_dihedral(&structure(cubic(10.0), &mirrored), 0, 1, 2, 3).unwrap();
+        assert!((dihedral.value + 90.0).abs() < 1e-9, "{}", dihedral.value);
+    }
+
+    #[test]
+    fn left_handed_cell() {
+        // c 沿 -z 的左手晶胞，原子的笛卡尔坐标与右手晶胞中相同
+        let lattice = [[10.0, 0.0, 0.0], [0.0, 10.0, 0.0], [0.0, 0.0, -10.0]];
+        let fracs = [[0.1, 0.0, 0.0], [0.0, 0.0, 0.0], [0.0, 0.0, -0.1], [0.0, 0.1, -0.1]];
+        let mut structure = structure(lattice, &fracs);
+        let dihedral = measure_dihedral(&structure, 0, 1, 2, 3).unwrap();
+        assert!((dihedral.value - 90.0).abs() < 1e-9, "{}", dihedral.value);
+        structure.cell_esd = [0.01, 0.01, 0.01, 0.0, 0.0, 0.0];
+        let dihedral = measure_dihedral(&structure, 0, 1, 2, 3).unwrap();
+        assert!((dihedral.value - 90.0).abs() < 1e-9, "{}", dihedral.value);
+        assert!(dihedral.esd.unwrap() < 1e-6);
+    }
+
+    #[test]
+    fn esd_propagation() {
+        // d = x·a，σ_d² = (x·σ_a)² + (a·σ_x)²
+        let mut structure = structure(cubic(10.0), &[[0.0, 0.0, 0.0], [0.2, 0.0, 0.0]]);
+        structure.cell_esd = [0.01, 0.01, 0.01, 0.1, 0.1, 0.1];
+        structure.sites[1].frac_esd = [0.001, 0.001, 0.001];
+        let distance = measure_distance(&structure, 0, 1).unwrap();
+        let expected = ((0.2f64 * 0.01).powi(2) + (10.0f64 * 0.001).powi(2)).sqrt();
+        assert!((distance.value - 2.0).abs() < 1e-12);
+        assert!((distance.esd.unwrap() - expected).abs() < 1e-6, "{:?} vs {}", distance.esd, expected);
+    }
+}
@@ -0,0 +1,18 @@
+pub mod powder;
+pub mod neutron;
+pub mod neighbors;
+pub mod rdf;
+pub mod measure;
+pub mod polyhedra;
+pub mod bond_valence;
+pub mod symmetry;
+pub mod structure_matcher;
+pub mod voronoi;
+pub mod pores;
+pub mod structure_info;
+pub mod isosurface;
+pub mod slices;
+pub mod volumetric;
+pub mod bader;
+pub mod diffusion;
+pub mod fragments;
\ No newline at end of file
@@ -0,0 +1,94 @@
+use crate::parsers::atom_config::Site;
+use crate::utils::elements::element;
+use crate::utils::geometry::{cell_volume, reciprocal_lattice};
+use crate::utils::linalg::{norm, vec_mat, Mat3, Vec3};
+
+/// 周期性边界条件下的近邻
+#[derive(Clone, Debug)]
+pub struct Neighbor {
+    pub index: usize,    // 近邻原子的序号
+    pub image: [i32; 3], // 近邻所在的晶胞平移
+    pub distance: f64,   // 距离（Å）
+    pub vector: Vec3,    // 由中心原子指向近邻的笛卡尔向量
+}
+
+/// 晶格在三个方向上的面间距，用于确定需要搜索的晶胞镜像数
+pub fn perpendicular_widths(lattice: &Mat3) -> Vec3 {
+    let reciprocal = reciprocal_lattice(lattice);
+    reciprocal.map(|b| 1.0 / norm(b))
+}
+
+/// 搜索每个原子在截断半径内的全部周期性近邻（不含自身）
+/// 截断半径可以大于晶胞尺寸，此时同一原子的多个镜像都会被返回
+pub fn neighbor_list(lattice: &Mat3, frac: &[Vec3], cutoff: f64) -> Vec<Vec<Neighbor>> {
+    let widths = perpendicular_widths(lattice);
+    let ranges = widths.map(|w| (cutoff / w + 0.5).ceil() as i32);
+
+    let mut neighbors = vec![Vec::new(); frac.len()];
+    if cell_volume(lattice) < 1e-12 {
+        return neighbors;
+    }
+
+    for (i, fi) in frac.iter().enumerate() {
+        for (j, fj) in frac.iter().enumerate() {
+            // 先取最近的镜像，再在其周围搜索
+            let diff = [fj[0] - fi[0], fj[1] - fi[1], fj[2] - fi[2]];
+            let shift = diff.map(|d| -d.round());
+            for ta in -ranges[0]..=ranges[0] {
+                for tb in -ranges[1]..=ranges[1] {
+                    for tc in -ranges[2]..=ranges[2] {
+                        let image = [
+                            shift[0] as i32 + ta,
+                            shift[1] as i32 + tb,
+                            shift[2] as i32 + tc,
+                        ];
+                        if i == j && image == [0, 0, 0] {
+                            continue;
+                        }
+                        let d = [
+                            diff[0] + image[0] as f64,
+                            diff[1] + image[1] as f64,
+                            diff[2] + image[2] as f64,
+                        ];
+                        let vector = vec_mat(d, lattice);
+                        let distance = norm(vector);
+                        if distance <= cutoff {
+                            neighbors[i].push(Neighbor {
+                                index: j,
+                                image,
+                                distance,
+                                vector,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // 按距离排序，方便按壳层取用
+    neighbors.iter_mut().for_each(|list| {
+        list.sort_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap())
+    });
+    neighbors
+}
+
+/// 各位点的配位数：距离小于两原子共价半径之和乘以 tolerance 的近邻数
+/// 缺少共价半径的元素按 1.5 Å 计
+pub fn coordination_numbers(lattice: &Mat3, sites: &[Site], tolerance: f64) -> Vec<usize> {
+    let radii: Vec<f64> = sites
+        .iter()
+        .map(|site| element(&site.element).and_then(|e| e.covalent_radius).unwrap_or(1.5))
+        .collect();
+    let r_max = radii.iter().copied().fold(0.0, f64::max);
+    let frac: Vec<Vec3> = sites.iter().map(|site| site.frac).collect();
+    neighbor_list(lattice, &frac, 2.0 * r_max * tolerance)
+        .iter()
+        .enumerate()
+        .map(|(i, list)| {
+            list.iter()
+                .filter(|n| n.distance > 1e-8 && n.distance <= (radii[i] + radii[n.index]) * tolerance)
+                .count()
+        })
+        .collect()
+}
@@ -0,0 +1,103 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use crate::analysis::powder::{compute_powder_pattern, PowderPattern, PowderSettings};
+use crate::parsers::atom_config::CrystalStructure;
+
+/// 天然同位素丰度下的中子相干散射长度（fm）
+/// reference: V. F. Sears, Neutron News 3 (1992) 26; https://www.ncnr.nist.gov/resources/n-lengths/
+const NATURAL_SCATTERING_LENGTHS: &[(&str, f64)] = &[
+    ("H", -3.739), ("He", 3.26), ("Li", -1.90), ("Be", 7.79), ("B", 5.30),
+    ("C", 6.646), ("N", 9.36), ("O", 5.803), ("F", 5.654), ("Ne", 4.566),
+    ("Na", 3.63), ("Mg", 5.375), ("Al", 3.449), ("Si", 4.1491), ("P", 5.13),
+    ("S", 2.847), ("Cl", 9.577), ("Ar", 1.909), ("K", 3.67), ("Ca", 4.70),
+    ("Sc", 12.29), ("Ti", -3.438), ("V", -0.3824), ("Cr", 3.635), ("Mn", -3.73),
+    ("Fe", 9.45), ("Co", 2.49), ("Ni", 10.3), ("Cu", 7.718), ("Zn", 5.680),
+    ("Ga", 7.288), ("Ge", 8.185), ("As", 6.58), ("Se", 7.970), ("Br", 6.795),
+    ("Kr", 7.81), ("Rb", 7.09), ("Sr", 7.02), ("Y", 7.75), ("Zr", 7.16),
+    ("Nb", 7.054), ("Mo", 6.715), ("Tc", 6.8), ("Ru", 7.03), ("Rh", 5.88),
+    ("Pd", 5.91), ("Ag", 5.922), ("Cd", 4.87), ("In", 4.065), ("Sn", 6.225),
+    ("Sb", 5.57), ("Te", 5.80), ("I", 5.28), ("Xe", 4.92), ("Cs", 5.42),
+    ("Ba", 5.07), ("La", 8.24), ("Ce", 4.84), ("Pr", 4.58), ("Nd", 7.69),
+    ("Pm", 12.6), ("Sm", 0.80), ("Eu", 7.22), ("Gd", 6.5), ("Tb", 7.38),
+    ("Dy", 16.9), ("Ho", 8.01), ("Er", 7.79), ("Tm", 7.07), ("Yb", 12.43),
+    ("Lu", 7.21), ("Hf", 7.7), ("Ta", 6.91), ("W", 4.86), ("Re", 9.2),
+    ("Os", 10.7), ("Ir", 10.6), ("Pt", 9.60), ("Au", 7.63), ("Hg", 12.692),
+    ("Tl", 8.776), ("Pb", 9.405), ("Bi", 8.532), ("Th", 10.31), ("U", 8.417),
+];
+
+/// 常用同位素的中子相干散射长度（fm），键为 "质量数+元素符号"
+const ISOTOPE_SCATTERING_LENGTHS: &[(&str, f64)] = &[
+    ("1H", -3.7406), ("2H", 6.671), ("3H", 4.792), ("3He", 5.74), ("4He", 3.26),
+    ("6Li", 2.00), ("7Li", -2.22), ("10B", -0.1), ("11B", 6.65), ("12C", 6.6511),
+    ("13C", 6.19), ("14N", 9.37), ("15N", 6.44), ("16O", 5.803), ("17O", 5.78),
+    ("18O", 5.84), ("46Ti", 4.93), ("48Ti", -6.08), ("50V", 7.6), ("51V", -0.402),
+    ("54Fe", 4.2), ("56Fe", 9.94), ("57Fe", 2.3), ("58Ni", 14.4), ("60Ni", 2.8),
+    ("62Ni", -8.7), ("63Cu", 6.43), ("65Cu", 10.61), ("64Zn", 5.22), ("66Zn", 5.97),
+    ("68Zn", 7.56), ("92Mo", 6.91), ("112Sn", 6.0), ("120Sn", 6.49), ("154Sm", 9.3),
+    ("153Eu", 8.22), ("160Gd", 9.15), ("204Pb", 9.90), ("206Pb", 9.22), ("207Pb", 9.28),
+    ("208Pb", 9.50), ("238U", 8.402),
+];
+
+/// 中子粉末衍射参数
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct NeutronSettings {
+    #[serde(flatten)]
+    pub powder: PowderSettings,
+    pub isotopes: HashMap<String, String>, // 元素 -> 同位素，如 {"H": "2H", "Li": "7Li"}
+}
+
+/// 查询元素或同位素的相干散射长度（fm）
+/// 元素符号 D、T 分别视为 2H、3H
+pub fn scattering_length(element: &str, isotope: Option<&str>) -> Option<f64> {
+    let isotope = match element {
+        "D" => Some("2H"),
+        "T" => Some("3H"),
+        _ => isotope,
+    };
+    match isotope {
+        Some(key) => ISOTOPE_SCATTERING_LENGTHS
+            .iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, b)| *b),
+        None => NATURAL_SCATTERING_LENGTHS
+            .iter()
+            .find(|(name, _)| *name == element)
+            .map(|(_, b)| *b),
+    }
+}
+
+/// 计算中子粉末衍射谱，散射振幅取相干散射长度，不随 sinθ/λ 变化
+pub fn neutron_powder_pattern(
+    structure: &CrystalStructure,
+    settings: &NeutronSettings,
+) -> Result<PowderPattern, String> {
+    // 同位素须属于所指定的元素，如 {"Li": "7Li"}
+    for (element, isotope) in &settings.isotopes {
+        if isotope.trim_start_matches(|c: char| c.is_ascii_digit()) != element {
+            return Err(format!("Isotope {} does not belong to element {}", isotope, element));
+        }
+    }
+
+    // 预先解析每种元素的散射长度
+    let mut lengths: HashMap<String, f64> = HashMap::new();
+    for site in &structure.sites {
+        if lengths.contains_key(&site.element) {
+            continue;
+        }
+        let isotope = settings.isotopes.get(&site.element).map(|s| s.as_str());
+        let b = scattering_length(&site.element, isotope).ok_or_else(|| match isotope {
+            Some(isotope) => format!("No neutron scattering length for isotope {}", isotope),
+            None => format!("No neutron scattering length for element {}", site.element),
+        })?;
+        lengths.insert(site.element.clone(), b);
+    }
+
+    // 中子无偏振因子，仅保留 Lorentz 因子 1/(sin²θ·cosθ)
+    compute_powder_pattern(
+        structure,
+        &settings.powder,
+        |site, _| lengths[&site.element],
+        |theta| 1.0 / (theta.sin().powi(2) * theta.cos()),
+    )
+}
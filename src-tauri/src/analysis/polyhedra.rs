@@ -0,0 +1,152 @@
+use serde::{Deserialize, Serialize};
+use crate::analysis::bond_valence::{anion_sites, ANION_VALENCES};
+use crate::analysis::neighbors::neighbor_list;
+use crate::parsers::atom_config::CrystalStructure;
+use crate::utils::convex_hull::convex_hull;
+use crate::utils::geometry::frac_to_display;
+use crate::utils::linalg::{add, dot, norm, Vec3};
+
+/// 配位多面体的计算参数
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct PolyhedraSettings {
+    pub anions: Vec<String>, // 可作为配体的元素，与电负性更高的配体元素成键的位点（如 SO4 中的 S）仍作为中心
+    pub max_bond_length: f64, // 配位键长上限（Å）
+    pub tolerance: f64,       // 配位壳层取最短键长的倍数
+}
+
+impl Default for PolyhedraSettings {
+    fn default() -> Self {
+        PolyhedraSettings {
+            anions: ANION_VALENCES.iter().map(|(name, _)| name.to_string()).collect(),
+            max_bond_length: 3.2,
+            tolerance: 1.25,
+        }
+    }
+}
+
+/// 多面体的一个顶点（配体）
+#[derive(Serialize, Clone, Debug)]
+pub struct Ligand {
+    pub index: usize,
+    pub label: String,
+    pub image: [i32; 3],
+    pub distance: f64,
+}
+
+/// 配位多面体的几何与畸变参数
+#[derive(Serialize, Clone, Debug)]
+pub struct Polyhedron {
+    pub center: usize,
+    pub center_label: String,
+    pub element: String,
+    pub center_position: Vec3,  // 绘制坐标（与前端原子坐标一致）
+    pub ligands: Vec<Ligand>,
+    pub vertices: Vec<Vec3>,     // 配体的绘制坐标
+    pub faces: Vec<Vec<usize>>,  // 多边形面，顶点从外侧看为逆时针
+    pub triangles: Vec<[usize; 3]>,
+    pub coordination_number: usize,
+    pub mean_bond_length: f64,
+    pub volume: f64,
+    pub distortion_index: f64,                // Baur 畸变指数
+    pub quadratic_elongation: Option<f64>,    // Robinson 二次伸长，仅四面体、八面体、立方体
+    pub bond_angle_variance: Option<f64>,     // 键角方差（度²），仅四面体、八面体、立方体
+}
+
+/// 计算每个阳离子的配位多面体
+pub fn compute_polyhedra(
+    structure: &CrystalStructure,
+    settings: &PolyhedraSettings,
+) -> Vec<Polyhedron> {
+    let lattice = &structure.lattice;
+    let frac: Vec<Vec3> = structure.sites.iter().map(|s| s.frac).collect();
+    let neighbors = neighbor_list(lattice, &frac, settings.max_bond_length);
+    let anions = anion_sites(lattice, &structure.sites, &settings.anions);
+    let is_anion = |index: usize| anions[index];
+
+    let mut polyhedra = Vec::new();
+    for (center, list) in neighbors.iter().enumerate() {
+        if is_anion(center) {
+            continue;
+        }
+        // 近邻已按距离排序，配位壳层取最短键长的一定倍数以内的配体
+        let shell: Vec<_> = list.iter().filter(|n| is_anion(n.index)).collect();
+        let Some(shortest) = shell.first().map(|n| n.distance) else {
+            continue;
+        };
+        let shell: Vec<_> = shell
+            .into_iter()
+            .filter(|n| n.distance <= shortest * settings.tolerance)
+            .collect();
+
+        let center_position = frac_to_display(lattice, structure.sites[center].frac);
+        let bonds: Vec<Vec3> = shell.iter().map(|n| n.vector).collect();
+        let lengths: Vec<f64> = shell.iter().map(|n| n.distance).collect();
+        let cn = shell.len();
+        let mean_bond_length = lengths.iter().sum::<f64>() / cn as f64;
+        let distortion_index = lengths
+            .iter()
+            .map(|l| (l - mean_bond_length).abs() / mean_bond_length)
+            .sum::<f64>()
+            / cn as f64;
+
+        let hull = convex_hull(&bonds);
+        let (faces, triangles, volume) = match &hull {
+            Some(hull) => (hull.faces.clone(), hull.triangles(), hull.volume),
+            None => (Vec::new(), Vec::new(), 0.0),
+        };
+
+        // 理想多面体：中心到顶点距离 l0 与体积 V 的关系，以及相邻配体的理想键角
+        let ideal = match (cn, faces.len()) {
+            (4, 4) => Some(((9.0 * 3f64.sqrt() / 8.0 * volume).cbrt(), 109.4712)),
+            (6, 8) => Some(((3.0 / 4.0 * volume).cbrt(), 90.0)),
+            (8, 6) => Some(((3.0 * 3f64.sqrt() / 8.0 * volume).cbrt(), 70.5288)),
+            _ => None,
+        };
+        let quadratic_elongation = ideal.map(|(l0, _)| {
+            lengths.iter().map(|l| (l / l0).powi(2)).sum::<f64>() / cn as f64
+        });
+        let bond_angle_variance = match (&hull, ideal) {
+            (Some(hull), Some((_, theta0))) => {
+                // 只统计由多面体棱相连的配体之间的键角
+                let edges = hull.edges();
+                let sum: f64 = edges
+                    .iter()
+                    .map(|&(a, b)| {
+                        let cos = dot(bonds[a], bonds[b]) / (norm(bonds[a]) * norm(bonds[b]));
+                        (cos.clamp(-1.0, 1.0).acos().to_degrees() - theta0).powi(2)
+                    })
+                    .sum();
+                Some(sum / (edges.len() as f64 - 1.0))
+            }
+            _ => None,
+        };
+
+        polyhedra.push(Polyhedron {
+            center,
+            center_label: structure.sites[center].label.clone(),
+            element: structure.sites[center].element.clone(),
+            center_position,
+            ligands: shell
+                .iter()
+                .map(|n| Ligand {
+                    index: n.index,
+                    label: structure.sites[n.index].label.clone(),
+                    image: n.image,
+                    distance: n.distance,
+                })
+                .collect(),
+            vertices: bonds.iter().map(|b| add(center_position, *b)).collect(),
+            faces,
+            triangles,
+            coordination_number: cn,
+            mean_bond_length,
+            volume,
+            distortion_index,
+            quadratic_elongation,
+            bond_angle_variance,
+        });
+    }
+
+    polyhedra
+}
@@ -0,0 +1,257 @@
+use std::collections::{HashMap, VecDeque};
+use serde::{Deserialize, Serialize};
+use crate::analysis::neighbors::perpendicular_widths;
+use crate::analysis::voronoi::{radical_voronoi, voronoi_network, VoronoiNetwork};
+use crate::parsers::atom_config::CrystalStructure;
+use crate::utils::geometry::{cell_volume, frac_to_display};
+use crate::utils::linalg::{norm, vec_mat, Vec3};
+
+/// 空隙格点数的上限
+const MAX_VOID_POINTS: f64 = 1e7;
+
+/// 孔隙分析参数
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct PoreSettings {
+    pub radii: HashMap<String, f64>, // 各元素的原子半径（Å），缺省时使用范德华半径
+    pub probe_radius: f64,           // 探针分子半径（Å）
+    pub grid_spacing: f64,           // 空隙格点间距（Å）
+}
+
+impl Default for PoreSettings {
+    fn default() -> Self {
+        PoreSettings {
+            radii: HashMap::new(),
+            probe_radius: 1.2,
+            grid_spacing: 0.25,
+        }
+    }
+}
+
+/// 单个原子的 Voronoi 胞
+#[derive(Serialize, Clone, Debug)]
+pub struct AtomVoronoi {
+    pub index: usize,
+    pub label: String,
+    pub radius: f64,
+    pub volume: f64,      // Voronoi 胞体积（Å³）
+    pub face_count: usize,
+}
+
+/// 空隙格点图：每个格点到最近原子表面的距离，可直接绘制等值面
+#[derive(Serialize, Clone, Debug)]
+pub struct VoidMap {
+    pub dims: [usize; 3],
+    pub values: Vec<f64>, // 按 a 最快、c 最慢的顺序存放
+}
+
+/// 孔隙分析结果
+#[derive(Serialize, Clone, Debug)]
+pub struct PoreResult {
+    pub cells: Vec<AtomVoronoi>,
+    pub largest_cavity_diameter: f64, // 最大空腔直径 Di（Å）
+    pub pore_limiting_diameter: f64,  // 孔道限制直径 Df（Å），不存在贯通孔道时为 0
+    pub cavity_position: Vec3,        // 最大空腔中心的绘制坐标
+    pub probe_radius: f64,
+    pub accessible_volume: f64,       // 探针中心可到达且贯通的空间体积（Å³）
+    pub non_accessible_volume: f64,   // 探针可容纳但孤立的空腔体积（Å³）
+    pub accessible_fraction: f64,
+    pub network: VoronoiNetwork,
+    pub void_map: VoidMap,
+}
+
+/// 基于径向 Voronoi 剖分的孔隙分析
+/// 最大空腔直径取网络节点到原子表面的最大距离，孔道限制直径取网络中贯通整个晶体的路径上瓶颈的最大值
+pub fn analyze_pores(structure: &CrystalStructure, settings: &PoreSettings) -> Result<PoreResult, String> {
+    let lattice = &structure.lattice;
+    if structure.sites.is_empty() {
+        return Err("Structure has no atoms".to_string());
+    }
+    if !(settings.grid_spacing > 0.0 && settings.probe_radius >= 0.0) {
+        return Err("Grid spacing must be positive and probe radius must not be negative".to_string());
+    }
+    if lattice.iter().map(|v| (norm(*v) / settings.grid_spacing).ceil()).product::<f64>() > MAX_VOID_POINTS {
+        return Err(format!("Grid spacing {} Å is too fine for this cell", settings.grid_spacing));
+    }
+    let frac: Vec<Vec3> = structure.sites.iter().map(|s| s.frac).collect();
+    let radii: Vec<f64> = structure
+        .sites
+        .iter()
+        .map(|site| {
+            settings
+                .radii
+                .get(&site.element)
+                .copied()
+                .ok_or_else(|| format!("No radius given for {}", site.element))
+        })
+        .collect::<Result<_, _>>()?;
+
+    let voronoi = radical_voronoi(lattice, &frac, &radii);
+    let cells = voronoi
+        .iter()
+        .enumerate()
+        .map(|(index, cell)| AtomVoronoi {
+            index,
+            label: structure.sites[index].label.clone(),
+            radius: radii[index],
+            volume: cell.volume(),
+            face_count: cell.faces.len(),
+        })
+        .collect();
+
+    let network = voronoi_network(lattice, &frac, &radii, &voronoi);
+    let cavity = network
+        .nodes
+        .iter()
+        .max_by(|a, b| a.radius.total_cmp(&b.radius))
+        .ok_or_else(|| "Empty Voronoi network".to_string())?;
+    let largest_cavity_diameter = 2.0 * cavity.radius.max(0.0);
+    let cavity_position = frac_to_display(lattice, cavity.frac);
+    let pore_limiting_diameter = 2.0 * percolation_radius(&network).unwrap_or(0.0).max(0.0);
+
+    let void_map = void_map(structure, &radii, cavity.radius.max(0.0), settings.grid_spacing);
+    let (accessible_points, isolated_points) = accessible_points(&void_map, settings.probe_radius);
+    let total = void_map.values.len() as f64;
+    let volume = cell_volume(lattice);
+
+    Ok(PoreResult {
+        cells,
+        largest_cavity_diameter,
+        pore_limiting_diameter,
+        cavity_position,
+        probe_radius: settings.probe_radius,
+        accessible_volume: volume * accessible_points as f64 / total,
+        non_accessible_volume: volume * isolated_points as f64 / total,
+        accessible_fraction: accessible_points as f64 / total,
+        network,
+        void_map,
+    })
+}
+
+/// 能贯通晶体的最大球半径
+/// 按瓶颈从大到小依次加入网络的边，用带晶胞平移的并查集检测首次出现平移不为零的回路
+fn percolation_radius(network: &VoronoiNetwork) -> Option<f64> {
+    let n = network.nodes.len();
+    let mut parent: Vec<usize> = (0..n).collect();
+    let mut offset = vec![[0i32; 3]; n]; // 节点相对父节点的晶胞平移
+
+    fn find(parent: &mut [usize], offset: &mut [[i32; 3]], i: usize) -> (usize, [i32; 3]) {
+        if parent[i] == i {
+            return (i, [0; 3]);
+        }
+        let (root, to_root) = find(parent, offset, parent[i]);
+        offset[i] = [0, 1, 2].map(|k| offset[i][k] + to_root[k]);
+        parent[i] = root;
+        (root, offset[i])
+    }
+
+    let mut order: Vec<usize> = (0..network.edges.len()).collect();
+    order.sort_by(|&a, &b| network.edges[b].bottleneck.total_cmp(&network.edges[a].bottleneck));
+    for index in order {
+        let edge = &network.edges[index];
+        let (root_a, offset_a) = find(&mut parent, &mut offset, edge.from);
+        let (root_b, offset_b) = find(&mut parent, &mut offset, edge.to);
+        // 以根节点为参照，终点节点的位置为 offset_b，而由起点沿边到达的位置为 offset_a + image
+        let reached = [0, 1, 2].map(|k| offset_a[k] + edge.image[k]);
+        if root_a == root_b {
+            if reached != offset_b {
+                return Some(edge.bottleneck);
+            }
+        } else {
+            parent[root_b] = root_a;
+            offset[root_b] = [0, 1, 2].map(|k| reached[k] - offset_b[k]);
+        }
+    }
+    None
+}
+
+/// 在晶胞格点上计算到最近原子表面的距离
+/// 距离场的最大值约为最大空腔半径，因此每个原子只需更新半径 r + max_radius 以内的格点
+fn void_map(structure: &CrystalStructure, radii: &[f64], max_radius: f64, spacing: f64) -> VoidMap {
+    let lattice = &structure.lattice;
+    let dims = lattice.map(|v| ((norm(v) / spacing).ceil() as usize).max(1));
+    let widths = perpendicular_widths(lattice);
+    let cap = max_radius + spacing;
+    let mut values = vec![cap; dims[0] * dims[1] * dims[2]];
+
+    for (site, radius) in structure.sites.iter().zip(radii) {
+        let reach = radius + cap;
+        let center = site.frac;
+        let range: [(i64, i64); 3] = [0, 1, 2].map(|k| {
+            let n = dims[k] as f64;
+            let half = reach / widths[k];
+            (((center[k] - half) * n).floor() as i64, ((center[k] + half) * n).ceil() as i64)
+        });
+        for ic in range[2].0..=range[2].1 {
+            for ib in range[1].0..=range[1].1 {
+                for ia in range[0].0..=range[0].1 {
+                    let diff = [
+                        ia as f64 / dims[0] as f64 - center[0],
+                        ib as f64 / dims[1] as f64 - center[1],
+                        ic as f64 / dims[2] as f64 - center[2],
+                    ];
+                    let distance = norm(vec_mat(diff, lattice)) - radius;
+                    let index = ia.rem_euclid(dims[0] as i64) as usize
+                        + dims[0] * (ib.rem_euclid(dims[1] as i64) as usize
+                            + dims[1] * ic.rem_euclid(dims[2] as i64) as usize);
+                    if distance < values[index] {
+                        values[index] = distance;
+                    }
+                }
+            }
+        }
+    }
+    VoidMap { dims, values }
+}
+
+/// 探针中心可放置的格点中，属于贯通孔道与孤立空腔的格点数
+/// 广度优先搜索连通区域并记录格点的展开平移，同一格点以不同平移到达说明该区域贯通
+fn accessible_points(map: &VoidMap, probe_radius: f64) -> (usize, usize) {
+    let dims = map.dims;
+    let total = map.values.len();
+    let mut visited: Vec<Option<[i32; 3]>> = vec![None; total];
+    let (mut accessible, mut isolated) = (0, 0);
+
+    for seed in 0..total {
+        if visited[seed].is_some() || map.values[seed] < probe_radius {
+            continue;
+        }
+        visited[seed] = Some([0; 3]);
+        let mut queue = VecDeque::from([seed]);
+        let (mut count, mut percolates) = (0, false);
+        while let Some(point) = queue.pop_front() {
+            count += 1;
+            let shift = visited[point].unwrap();
+            let position = [point % dims[0], (point / dims[0]) % dims[1], point / (dims[0] * dims[1])];
+            for (axis, step) in [(0, 1), (0, -1), (1, 1), (1, -1), (2, 1), (2, -1)] {
+                let mut next = position.map(|p| p as i64);
+                let mut next_shift = shift;
+                next[axis] += step;
+                if next[axis] < 0 {
+                    next[axis] += dims[axis] as i64;
+                    next_shift[axis] -= 1;
+                } else if next[axis] >= dims[axis] as i64 {
+                    next[axis] -= dims[axis] as i64;
+                    next_shift[axis] += 1;
+                }
+                let index = next[0] as usize + dims[0] * (next[1] as usize + dims[1] * next[2] as usize);
+                if map.values[index] < probe_radius {
+                    continue;
+                }
+                match visited[index] {
+                    Some(existing) => percolates |= existing != next_shift,
+                    None => {
+                        visited[index] = Some(next_shift);
+                        queue.push_back(index);
+                    }
+                }
+            }
+        }
+        if percolates {
+            accessible += count;
+        } else {
+            isolated += count;
+        }
+    }
+    (accessible, isolated)
+}
@@ -0,0 +1,179 @@
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+use crate::parsers::atom_config::{CrystalStructure, Site};
+use crate::utils::geometry::reciprocal_lattice;
+use crate::utils::linalg::{dot, norm, vec_mat};
+
+/// 单个反射：(2θ, d, hkl, 强度)
+type Reflection = (f64, f64, [i32; 3], f64);
+
+/// 粉末衍射计算参数（角度单位为度）
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct PowderSettings {
+    pub wavelength: f64,    // 入射波长（Å）
+    pub two_theta_min: f64, // 2θ 下限
+    pub two_theta_max: f64, // 2θ 上限
+    pub step: f64,          // 谱线采样步长
+    pub fwhm: f64,          // 峰的半高宽
+    pub eta: f64,           // pseudo-Voigt 峰形中 Lorentz 成分的比例
+}
+
+impl Default for PowderSettings {
+    fn default() -> Self {
+        PowderSettings {
+            wavelength: 1.5406,
+            two_theta_min: 5.0,
+            two_theta_max: 90.0,
+            step: 0.02,
+            fwhm: 0.1,
+            eta: 0.5,
+        }
+    }
+}
+
+/// 合并等价反射后的衍射峰
+#[derive(Serialize, Clone, Debug)]
+pub struct PowderPeak {
+    pub two_theta: f64,
+    pub d_spacing: f64,
+    pub hkl: [i32; 3],       // 代表性的晶面指数
+    pub multiplicity: usize, // 合并的反射数目
+    pub intensity: f64,      // 相对强度，最强峰为 100
+}
+
+/// 粉末衍射结果：峰列表与展宽后的谱线
+#[derive(Serialize, Clone, Debug)]
+pub struct PowderPattern {
+    pub peaks: Vec<PowderPeak>,
+    pub profile: Vec<(f64, f64)>, // (2θ, 强度)
+}
+
+/// 一次计算允许枚举的最多 hkl 数目
+const MAX_HKL: f64 = 2e7;
+
+/// 计算粉末衍射谱
+/// `scattering` 返回位点在 sinθ/λ 处的散射振幅，`lorentz_polarization` 返回 θ（弧度）处的强度修正因子
+pub fn compute_powder_pattern(
+    structure: &CrystalStructure,
+    settings: &PowderSettings,
+    scattering: impl Fn(&Site, f64) -> f64,
+    lorentz_polarization: impl Fn(f64) -> f64,
+) -> Result<PowderPattern, String> {
+    if !(settings.wavelength > 0.0 && settings.wavelength.is_finite()) {
+        return Err("Wavelength must be positive".to_string());
+    }
+    if !(settings.two_theta_min >= 0.0 && settings.two_theta_max > settings.two_theta_min && settings.step > 0.0) {
+        return Err("Invalid 2θ range or step".to_string());
+    }
+    let lattice = &structure.lattice;
+    let reciprocal = reciprocal_lattice(lattice);
+    let wavelength = settings.wavelength;
+
+    // 2θ 上限对应的最大 1/d
+    let two_theta_max = settings.two_theta_max.min(180.0);
+    let s_max = 2.0 * (two_theta_max.to_radians() / 2.0).sin() / wavelength;
+    let h_max = (norm(lattice[0]) * s_max).ceil() as i32;
+    let k_max = (norm(lattice[1]) * s_max).ceil() as i32;
+    let l_max = (norm(lattice[2]) * s_max).ceil() as i32;
+    if [h_max, k_max, l_max].iter().map(|&n| 2.0 * n as f64 + 1.0).product::<f64>() > MAX_HKL {
+        return Err("Too many reflections, use a longer wavelength or a smaller 2θ range".to_string());
+    }
+
+    // 逐个计算反射
+    let mut reflections: Vec<Reflection> = Vec::new();
+    for h in -h_max..=h_max {
+        for k in -k_max..=k_max {
+            for l in -l_max..=l_max {
+                if h == 0 && k == 0 && l == 0 {
+                    continue;
+                }
+                let hkl = [h as f64, k as f64, l as f64];
+                let s = norm(vec_mat(hkl, &reciprocal));
+                if s > s_max {
+                    continue;
+                }
+                let sin_theta = wavelength * s / 2.0;
+                if sin_theta > 1.0 {
+                    continue;
+                }
+                let theta = sin_theta.asin();
+                let two_theta = 2.0 * theta.to_degrees();
+                if two_theta < settings.two_theta_min {
+                    continue;
+                }
+
+                // 结构因子 F = Σ occ·f·exp(2πi h·x)
+                let (mut re, mut im) = (0.0, 0.0);
+                for site in &structure.sites {
+                    let amplitude = site.occupancy * scattering(site, s / 2.0);
+                    let phase = 2.0 * PI * dot(hkl, site.frac);
+                    re += amplitude * phase.cos();
+                    im += amplitude * phase.sin();
+                }
+                let intensity = (re * re + im * im) * lorentz_polarization(theta);
+                reflections.push((two_theta, 1.0 / s, [h, k, l], intensity));
+            }
+        }
+    }
+
+    // 合并 2θ 相同的反射
+    reflections.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    let mut peaks: Vec<PowderPeak> = Vec::new();
+    let mut groups: Vec<Vec<Reflection>> = Vec::new();
+    for reflection in reflections {
+        match groups.last_mut() {
+            Some(group) if (reflection.0 - group[0].0).abs() < 1e-4 => group.push(reflection),
+            _ => groups.push(vec![reflection]),
+        }
+    }
+    for group in groups {
+        let intensity: f64 = group.iter().map(|r| r.3).sum();
+        // 优先选取非负且数值最大的指数作为代表
+        let hkl = group
+            .iter()
+            .map(|r| r.2)
+            .max_by_key(|hkl| (hkl.iter().filter(|&&v| v >= 0).count(), *hkl))
+            .unwrap();
+        peaks.push(PowderPeak {
+            two_theta: group[0].0,
+            d_spacing: group[0].1,
+            hkl,
+            multiplicity: group.len(),
+            intensity,
+        });
+    }
+
+    // 归一化强度并去除系统消光的峰
+    let max_intensity = peaks.iter().map(|p| p.intensity).fold(0.0, f64::max);
+    if max_intensity > 0.0 {
+        peaks.iter_mut().for_each(|p| p.intensity *= 100.0 / max_intensity);
+    }
+    peaks.retain(|p| p.intensity > 1e-4);
+
+    let profile = broaden_peaks(&peaks, settings);
+    Ok(PowderPattern { peaks, profile })
+}
+
+/// 用 pseudo-Voigt 峰形将衍射峰展宽为连续谱线
+fn broaden_peaks(peaks: &[PowderPeak], settings: &PowderSettings) -> Vec<(f64, f64)> {
+    let fwhm = settings.fwhm.max(1e-6);
+    let eta = settings.eta.clamp(0.0, 1.0);
+    let count = ((settings.two_theta_max - settings.two_theta_min) / settings.step).floor() as usize + 1;
+
+    (0..count)
+        .map(|i| {
+            let x = settings.two_theta_min + i as f64 * settings.step;
+            let y = peaks
+                .iter()
+                .map(|p| {
+                    let t = (x - p.two_theta) / fwhm;
+                    let gauss = (-4.0 * 2f64.ln() * t * t).exp();
+                    let lorentz = 1.0 / (1.0 + 4.0 * t * t);
+                    p.intensity * (eta * lorentz + (1.0 - eta) * gauss)
+                })
+                .sum();
+            (x, y)
+        })
+        .collect()
+}
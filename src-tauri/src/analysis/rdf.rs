@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use crate::analysis::neighbors::neighbor_list;
+use crate::parsers::trajectory::Frame;
+use crate::utils::geometry::cell_volume;
+
+/// 径向分布函数的计算参数
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct RdfSettings {
+    pub r_max: f64,     // 最大半径（Å）
+    pub bin_width: f64, // 直方图宽度（Å）
+}
+
+impl Default for RdfSettings {
+    fn default() -> Self {
+        RdfSettings {
+            r_max: 10.0,
+            bin_width: 0.05,
+        }
+    }
+}
+
+/// 元素对的偏径向分布函数 g_ab(r)
+#[derive(Serialize, Clone, Debug)]
+pub struct PartialRdf {
+    pub pair: (String, String),
+    pub g: Vec<f64>,
+    pub coordination: Vec<f64>, // a 原子周围 b 原子的累计配位数 n_ab(r)
+}
+
+/// 径向分布函数结果，所有曲线共用同一组半径
+#[derive(Serialize, Clone, Debug)]
+pub struct RdfResult {
+    pub r: Vec<f64>, // 每个直方图格子的中心
+    pub total: Vec<f64>,
+    pub coordination: Vec<f64>, // 总的累计配位数 n(r)
+    pub partials: Vec<PartialRdf>,
+    pub frame_count: usize,
+}
+
+/// 计算单个结构或轨迹各帧的平均径向分布函数
+/// 帧由 read_frame 逐帧读取并累加到直方图中，不同时保存全部帧
+pub fn compute_rdf(
+    frame_count: usize,
+    read_frame: impl Fn(usize) -> Result<Frame, String>,
+    settings: &RdfSettings,
+) -> Result<RdfResult, String> {
+    if frame_count == 0 {
+        return Err("No frames to analyse".to_string());
+    }
+    if settings.r_max <= 0.0 || settings.bin_width <= 0.0 {
+        return Err("r_max and bin_width must be positive".to_string());
+    }
+
+    let bins = (settings.r_max / settings.bin_width).ceil() as usize;
+    let r: Vec<f64> = (0..bins)
+        .map(|k| (k as f64 + 0.5) * settings.bin_width)
+        .collect();
+    // 每个格子的球壳体积
+    let shell_volumes: Vec<f64> = (0..bins)
+        .map(|k| {
+            let r_in = k as f64 * settings.bin_width;
+            let r_out = r_in + settings.bin_width;
+            4.0 / 3.0 * PI * (r_out.powi(3) - r_in.powi(3))
+        })
+        .collect();
+
+    // 元素种类按首次出现的顺序排列，偏分布按元素对 (a, b) 累加
+    let mut elements: Vec<String> = Vec::new();
+    let mut total = vec![0.0; bins];
+    let mut total_counts = vec![0.0; bins];
+    let mut partial_g: HashMap<(usize, usize), Vec<f64>> = HashMap::new();
+    let mut partial_counts: HashMap<(usize, usize), Vec<f64>> = HashMap::new();
+
+    // 实际参与平均的帧数（跳过空帧）
+    let mut accumulated = 0;
+    for index in 0..frame_count {
+        let frame = read_frame(index)?;
+        let volume = cell_volume(&frame.lattice);
+        let n_atoms = frame.sites.len();
+        if n_atoms == 0 || volume <= 0.0 {
+            continue;
+        }
+        accumulated += 1;
+        let species: Vec<usize> = frame
+            .sites
+            .iter()
+            .map(|s| {
+                elements.iter().position(|e| *e == s.element).unwrap_or_else(|| {
+                    elements.push(s.element.clone());
+                    elements.len() - 1
+                })
+            })
+            .collect();
+        let n_elements = elements.len();
+        let mut species_count = vec![0usize; n_elements];
+        species.iter().for_each(|&s| species_count[s] += 1);
+
+        let frac: Vec<[f64; 3]> = frame.sites.iter().map(|s| s.frac).collect();
+        let neighbors = neighbor_list(&frame.lattice, &frac, settings.r_max);
+
+        let mut histogram = vec![0.0; bins];
+        let mut partial_histogram = vec![vec![0.0; bins]; n_elements * n_elements];
+        for (i, list) in neighbors.iter().enumerate() {
+            for neighbor in list {
+                let k = (neighbor.distance / settings.bin_width) as usize;
+                if k >= bins {
+                    continue;
+                }
+                histogram[k] += 1.0;
+                partial_histogram[species[i] * n_elements + species[neighbor.index]][k] += 1.0;
+            }
+        }
+
+        // g(r) = V / (N_a N_b) · 计数 / 球壳体积
+        let total_density = n_atoms as f64 / volume;
+        for k in 0..bins {
+            total[k] += histogram[k] / (n_atoms as f64 * total_density * shell_volumes[k]);
+            total_counts[k] += histogram[k] / n_atoms as f64;
+        }
+        for a in 0..n_elements {
+            for b in 0..n_elements {
+                let (n_a, n_b) = (species_count[a] as f64, species_count[b] as f64);
+                if n_a == 0.0 || n_b == 0.0 {
+                    continue;
+                }
+                let histogram = &partial_histogram[a * n_elements + b];
+                let g = partial_g.entry((a, b)).or_insert_with(|| vec![0.0; bins]);
+                let counts = partial_counts.entry((a, b)).or_insert_with(|| vec![0.0; bins]);
+                for k in 0..bins {
+                    g[k] += histogram[k] * volume / (n_a * n_b * shell_volumes[k]);
+                    counts[k] += histogram[k] / n_a;
+                }
+            }
+        }
+    }
+
+    if accumulated == 0 {
+        return Err("All frames are empty".to_string());
+    }
+
+    // 对各帧取平均，并累加得到配位数
+    let average = |values: Option<&Vec<f64>>| -> Vec<f64> {
+        values.map_or_else(|| vec![0.0; bins], |values| values.iter().map(|v| v / accumulated as f64).collect())
+    };
+    let cumulative = |values: Option<&Vec<f64>>| -> Vec<f64> {
+        values.map_or_else(
+            || vec![0.0; bins],
+            |values| {
+                values
+                    .iter()
+                    .scan(0.0, |sum, v| {
+                        *sum += v / accumulated as f64;
+                        Some(*sum)
+                    })
+                    .collect()
+            },
+        )
+    };
+
+    let n_elements = elements.len();
+    let mut partials = Vec::new();
+    for a in 0..n_elements {
+        for b in a..n_elements {
+            partials.push(PartialRdf {
+                pair: (elements[a].clone(), elements[b].clone()),
+                g: average(partial_g.get(&(a, b))),
+                coordination: cumulative(partial_counts.get(&(a, b))),
+            });
+            if a != b {
+                // 另一方向的配位数 n_ba 不同，单独给出
+                partials.push(PartialRdf {
+                    pair: (elements[b].clone(), elements[a].clone()),
+                    g: average(partial_g.get(&(b, a))),
+                    coordination: cumulative(partial_counts.get(&(b, a))),
+                });
+            }
+        }
+    }
+
+    Ok(RdfResult {
+        r,
+        total: average(Some(&total)),
+        coordination: cumulative(Some(&total_counts)),
+        partials,
+        frame_count: accumulated,
+    })
+}
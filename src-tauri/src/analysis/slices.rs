@@ -0,0 +1,284 @@
+use serde::{Deserialize, Serialize};
+use crate::parsers::atom_config::{CrystalStructure, VolumetricData};
+use crate::utils::geometry::{cart_to_frac, cell_volume, frac_to_cart, frac_to_display, reciprocal_lattice};
+use crate::analysis::measure::minimum_image;
+use crate::utils::linalg::{add, cross, dot, norm, scale, sub, vec_mat, Vec3};
+
+/// 切片像素数的上限
+const MAX_SLICE_PIXELS: usize = 4_000_000;
+
+/// 二维切片参数
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct SliceSettings {
+    pub data_index: usize,
+    pub mode: String,        // hkl：晶面切片；atoms：过三个原子的平面
+    pub hkl: [i32; 3],       // mode 为 hkl 时的晶面指数
+    pub position: f64,       // 晶面 hx + ky + lz = position 的位置（分数坐标）
+    pub atoms: [usize; 3],   // mode 为 atoms 时的三个位点序号
+    pub margin: f64,         // mode 为 atoms 时在三原子外扩展的距离（Å）
+    pub resolution: f64,     // 像素间距（Å）
+}
+
+impl Default for SliceSettings {
+    fn default() -> Self {
+        SliceSettings {
+            data_index: 0,
+            mode: "hkl".to_string(),
+            hkl: [0, 0, 1],
+            position: 0.0,
+            atoms: [0, 1, 2],
+            margin: 2.0,
+            resolution: 0.1,
+        }
+    }
+}
+
+/// 平面上的二维标量图，像素 (m, n) 的绘制坐标为 origin + (u_min + m·Δ)·axis_u + (v_min + n·Δ)·axis_v
+#[derive(Serialize, Clone, Debug)]
+pub struct SliceResult {
+    pub origin: Vec3,        // 平面上的参考点（绘制坐标）
+    pub axis_u: Vec3,        // 平面内的单位向量
+    pub axis_v: Vec3,
+    pub normal: Vec3,
+    pub u_range: [f64; 2],   // 图像在 u、v 方向的范围（Å）
+    pub v_range: [f64; 2],
+    pub width: usize,        // u 方向的像素数
+    pub height: usize,
+    pub values: Vec<f64>,    // 按 u 最快的顺序存放
+    pub outline: Vec<[f64; 2]>, // 晶面上一个二维晶胞（hkl）或三个原子（atoms）在 (u, v) 中的坐标
+    pub min: f64,
+    pub max: f64,
+}
+
+/// 一维曲线
+#[derive(Serialize, Clone, Debug)]
+pub struct Profile {
+    pub x: Vec<f64>, // 沿线或沿轴的距离（Å）
+    pub values: Vec<f64>,
+}
+
+/// 沿晶面或三原子平面切取格点数据，采样点用三线性插值
+pub fn slice_plane(
+    structure: &CrystalStructure,
+    data: &VolumetricData,
+    settings: &SliceSettings,
+) -> Result<SliceResult, String> {
+    if settings.resolution <= 0.0 {
+        return Err("Resolution must be positive".to_string());
+    }
+    let lattice = &data.lattice;
+
+    // 平面参考点（笛卡尔坐标）、平面内的两个方向以及二维轮廓
+    let (origin, first, second, outline_points): (Vec3, Vec3, Vec3, Vec<Vec3>) = match settings.mode.as_str() {
+        "hkl" => {
+            let hkl = settings.hkl;
+            if hkl == [0, 0, 0] {
+                return Err("Miller indices must not all be zero".to_string());
+            }
+            let (t1, t2) = in_plane_vectors(lattice, hkl)?;
+            // 平面 h·x = position 上离原点最近的点
+            let g = vec_mat(hkl.map(|v| v as f64), &reciprocal_lattice(lattice));
+            let origin = scale(g, settings.position / dot(g, g));
+            (origin, t1, t2, vec![[0.0; 3], t1, add(t1, t2), t2])
+        }
+        "atoms" => {
+            let sites = &structure.sites;
+            let points: Vec<Vec3> = settings
+                .atoms
+                .iter()
+                .map(|&i| sites.get(i).map(|s| s.frac).ok_or_else(|| format!("Atom {} out of range", i)))
+                .collect::<Result<_, String>>()?;
+            // 第二、三个原子取离第一个原子最近的镜像
+            let nearest = |f: Vec3| {
+                let image = minimum_image(lattice, points[0], f);
+                frac_to_cart(lattice, [0, 1, 2].map(|k| f[k] + image[k] as f64))
+            };
+            let p0 = frac_to_cart(lattice, points[0]);
+            let (p1, p2) = (nearest(points[1]), nearest(points[2]));
+            if norm(cross(sub(p1, p0), sub(p2, p0))) < 1e-6 {
+                return Err("The three atoms are collinear".to_string());
+            }
+            (p0, sub(p1, p0), sub(p2, p0), vec![[0.0; 3], sub(p1, p0), sub(p2, p0)])
+        }
+        mode => return Err(format!("Unknown slice mode {}", mode)),
+    };
+
+    let axis_u = scale(first, 1.0 / norm(first));
+    let normal = cross(first, second);
+    let normal = scale(normal, 1.0 / norm(normal));
+    let axis_v = cross(normal, axis_u);
+
+    let outline: Vec<[f64; 2]> = outline_points.iter().map(|p| [dot(*p, axis_u), dot(*p, axis_v)]).collect();
+    let margin = if settings.mode == "atoms" { settings.margin.max(0.0) } else { 0.0 };
+    let bound = |axis: usize, pick: fn(f64, f64) -> f64, start: f64| outline.iter().map(|p| p[axis]).fold(start, pick);
+    let u_range = [bound(0, f64::min, f64::MAX) - margin, bound(0, f64::max, f64::MIN) + margin];
+    let v_range = [bound(1, f64::min, f64::MAX) - margin, bound(1, f64::max, f64::MIN) + margin];
+    let width = ((u_range[1] - u_range[0]) / settings.resolution).round() as usize + 1;
+    let height = ((v_range[1] - v_range[0]) / settings.resolution).round() as usize + 1;
+    if width.saturating_mul(height) > MAX_SLICE_PIXELS {
+        return Err(format!("Resolution {} Å is too fine for this plane", settings.resolution));
+    }
+
+    let mut values = Vec::with_capacity(width * height);
+    for n in 0..height {
+        for m in 0..width {
+            let u = u_range[0] + m as f64 * settings.resolution;
+            let v = v_range[0] + n as f64 * settings.resolution;
+            let point = add(origin, add(scale(axis_u, u), scale(axis_v, v)));
+            values.push(data.interpolate(cart_to_frac(lattice, point)));
+        }
+    }
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    Ok(SliceResult {
+        origin: frac_to_display(lattice, cart_to_frac(lattice, origin)),
+        axis_u,
+        axis_v,
+        normal,
+        u_range,
+        v_range,
+        width,
+        height,
+        values,
+        outline,
+        min,
+        max,
+    })
+}
+
+/// (hkl) 晶面内两个最短且不共线的晶格矢量（笛卡尔坐标）
+fn in_plane_vectors(lattice: &[[f64; 3]; 3], hkl: [i32; 3]) -> Result<(Vec3, Vec3), String> {
+    let range = hkl.iter().map(|v| v.abs()).max().unwrap_or(1).max(1);
+    let mut candidates: Vec<Vec3> = Vec::new();
+    for a in -range..=range {
+        for b in -range..=range {
+            for c in -range..=range {
+                if [a, b, c] != [0, 0, 0] && a * hkl[0] + b * hkl[1] + c * hkl[2] == 0 {
+                    candidates.push(frac_to_cart(lattice, [a as f64, b as f64, c as f64]));
+                }
+            }
+        }
+    }
+    candidates.sort_by(|p, q| norm(*p).total_cmp(&norm(*q)));
+    let first = *candidates.first().ok_or("No lattice vectors in plane")?;
+    let second = candidates
+        .iter()
+        .find(|t| norm(cross(first, **t)) > 1e-6 * norm(first) * norm(**t))
+        .ok_or("No lattice vectors in plane")?;
+    // 第二个矢量与第一个成锐角，使二维晶胞更规整
+    let second = if dot(first, *second) < 0.0 { scale(*second, -1.0) } else { *second };
+    Ok((first, second))
+}
+
+/// 两点（分数坐标，可超出晶胞）之间的线剖面，共 points 个等距采样点
+pub fn line_profile(data: &VolumetricData, start: Vec3, end: Vec3, points: usize) -> Result<Profile, String> {
+    if points < 2 {
+        return Err("A line profile needs at least two points".to_string());
+    }
+    let length = norm(frac_to_cart(&data.lattice, sub(end, start)));
+    let mut profile = Profile { x: Vec::with_capacity(points), values: Vec::with_capacity(points) };
+    for n in 0..points {
+        let t = n as f64 / (points - 1) as f64;
+        profile.x.push(t * length);
+        profile.values.push(data.interpolate(add(start, scale(sub(end, start), t))));
+    }
+    Ok(profile)
+}
+
+/// 沿某一晶轴的平面平均（如功函数计算中沿 z 的平均静电势）
+/// 对每一层格点求平均，x 为该层到 0 层的垂直距离
+pub fn planar_average(data: &VolumetricData, axis: usize) -> Result<Profile, String> {
+    if axis > 2 {
+        return Err(format!("Invalid axis {}", axis));
+    }
+    let dims = data.dims;
+    let lattice = &data.lattice;
+    // 沿晶轴的层间距：晶胞体积除以另外两个基矢量所围的面积
+    let (p, q) = [(1, 2), (0, 2), (0, 1)][axis];
+    let spacing = cell_volume(lattice) / norm(cross(lattice[p], lattice[q])) / dims[axis] as f64;
+    let mut sums = vec![0.0; dims[axis]];
+    for k in 0..dims[2] {
+        for j in 0..dims[1] {
+            for i in 0..dims[0] {
+                let layer = [i, j, k][axis];
+                sums[layer] += data.values[i + dims[0] * (j + dims[1] * k)];
+            }
+        }
+    }
+    let per_layer = (dims[0] * dims[1] * dims[2] / dims[axis]) as f64;
+    Ok(Profile {
+        x: (0..dims[axis]).map(|n| n as f64 * spacing).collect(),
+        values: sums.into_iter().map(|s| s / per_layer).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use super::*;
+    use crate::parsers::atom_config::Site;
+    use crate::parsers::parser_utils::build_crystal_structure;
+
+    fn grid(dims: [usize; 3], lattice: [[f64; 3]; 3], value: impl Fn(usize, usize, usize) -> f64) -> VolumetricData {
+        let mut values = Vec::with_capacity(dims[0] * dims[1] * dims[2]);
+        for k in 0..dims[2] {
+            for j in 0..dims[1] {
+                for i in 0..dims[0] {
+                    values.push(value(i, j, k));
+                }
+            }
+        }
+        VolumetricData::new("total".to_string(), "potential".to_string(), dims, lattice, [0.0; 3], values)
+    }
+
+    #[test]
+    fn planar_average_on_oblique_cell() {
+        // c 方向层间距 = V / |a × b| / 4 = 160 / 20 / 4 = 2 Å；每层平均值为 k + 10·<i> = k + 5
+        let lattice = [[4.0, 0.0, 0.0], [1.0, 5.0, 0.0], [1.0, 1.0, 8.0]];
+        let data = grid([2, 3, 4], lattice, |i, _, k| k as f64 + 10.0 * i as f64);
+        let profile = planar_average(&data, 2).unwrap();
+        assert_eq!(profile.x.len(), 4);
+        for (n, (&x, &value)) in profile.x.iter().zip(&profile.values).enumerate() {
+            assert!((x - 2.0 * n as f64).abs() < 1e-12);
+            assert!((value - (n as f64 + 5.0)).abs() < 1e-12);
+        }
+        let profile = planar_average(&data, 0).unwrap();
+        assert_eq!(profile.values, vec![1.5, 11.5]);
+        assert!(planar_average(&data, 3).is_err());
+    }
+
+    #[test]
+    fn atoms_plane_across_boundary() {
+        // 第二、三个原子隔着 a 方向的晶胞面，应取距第一个原子 1 Å 的镜像
+        let lattice = [[10.0, 0.0, 0.0], [0.0, 10.0, 0.0], [0.0, 0.0, 10.0]];
+        let sites = vec![
+            Site::new("O1".to_string(), "O".to_string(), [0.95, 0.5, 0.5]),
+            Site::new("H1".to_string(), "H".to_string(), [0.05, 0.5, 0.5]),
+            Site::new("H2".to_string(), "H".to_string(), [0.05, 0.6, 0.5]),
+        ];
+        let structure = build_crystal_structure(lattice, sites, &HashMap::new());
+        let data = grid([10; 3], lattice, |_, _, k| k as f64);
+        let settings = SliceSettings { mode: "atoms".to_string(), ..SliceSettings::default() };
+        let slice = slice_plane(&structure, &data, &settings).unwrap();
+        let length = |p: [f64; 2]| (p[0] * p[0] + p[1] * p[1]).sqrt();
+        assert!((length(slice.outline[1]) - 1.0).abs() < 1e-9);
+        assert!((length(slice.outline[2]) - 2f64.sqrt()).abs() < 1e-9);
+        // 平面垂直于 c，数值为常数 5
+        assert!((slice.normal[2].abs() - 1.0).abs() < 1e-12);
+        assert!(slice.values.iter().all(|&v| (v - 5.0).abs() < 1e-9));
+        assert_eq!(slice.values.len(), slice.width * slice.height);
+    }
+
+    #[test]
+    fn slice_size_limit() {
+        let lattice = [[10.0, 0.0, 0.0], [0.0, 10.0, 0.0], [0.0, 0.0, 10.0]];
+        let structure = build_crystal_structure(lattice, Vec::new(), &HashMap::new());
+        let data = grid([4; 3], lattice, |_, _, _| 1.0);
+        let fine = SliceSettings { resolution: 1e-4, ..SliceSettings::default() };
+        assert!(slice_plane(&structure, &data, &fine).is_err());
+        let slice = slice_plane(&structure, &data, &SliceSettings::default()).unwrap();
+        assert_eq!((slice.width, slice.height), (101, 101));
+    }
+}
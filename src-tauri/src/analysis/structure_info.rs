@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use serde::Serialize;
+use crate::parsers::atom_config::CrystalStructure;
+use crate::utils::elements::element;
+use crate::utils::geometry::{cell_volume, lattice_parameters};
+
+/// 阿伏伽德罗常数（mol⁻¹）
+const AVOGADRO: f64 = 6.02214076e23;
+
+/// 一种元素在晶胞中的原子数
+#[derive(Serialize, Clone, Debug)]
+pub struct ElementCount {
+    pub element: String,
+    pub count: f64, // 按占有率加权
+    pub sites: usize,
+}
+
+/// 结构信息面板的数据
+#[derive(Serialize, Clone, Debug)]
+pub struct StructureInfo {
+    pub formula: String,         // 晶胞内容，如 Na4Cl4
+    pub reduced_formula: String, // 约化化学式，如 NaCl
+    pub z: u32,                  // 晶胞中约化化学式的个数
+    pub atom_count: f64,         // 按占有率加权的原子数
+    pub lattice_parameters: [f64; 6],
+    pub volume: f64,             // 晶胞体积（Å³）
+    pub formula_mass: f64,       // 约化化学式的式量（g/mol）
+    pub density: f64,            // 密度（g/cm³）
+    pub packing_fraction: f64,   // 原子球体积之和占晶胞体积的比例
+    pub elements: Vec<ElementCount>,
+}
+
+/// 计算晶胞的化学式、密度与堆积分数，radii 为计算堆积分数所用的原子半径（Å）
+/// 元素按在文件中首次出现的顺序排列
+pub fn structure_info(
+    structure: &CrystalStructure,
+    radii: &HashMap<String, f64>,
+) -> Result<StructureInfo, String> {
+    let mut elements: Vec<ElementCount> = Vec::new();
+    for site in &structure.sites {
+        match elements.iter_mut().find(|e| e.element == site.element) {
+            Some(entry) => {
+                entry.count += site.occupancy;
+                entry.sites += 1;
+            }
+            None => elements.push(ElementCount {
+                element: site.element.clone(),
+                count: site.occupancy,
+                sites: 1,
+            }),
+        }
+    }
+
+    let volume = cell_volume(&structure.lattice);
+    let mut cell_mass = 0.0;
+    let mut sphere_volume = 0.0;
+    for site in &structure.sites {
+        let data = element(&site.element).ok_or_else(|| format!("Unknown element {}", site.element))?;
+        cell_mass += site.occupancy * data.mass;
+        let radius = radii.get(&site.element).copied().unwrap_or(0.0);
+        sphere_volume += site.occupancy * 4.0 / 3.0 * PI * radius.powi(3);
+    }
+
+    // 占有率全部为整数时按最大公约数约化，否则 Z 取 1
+    let integral = elements.iter().all(|e| (e.count - e.count.round()).abs() < 1e-3);
+    let z = if integral {
+        elements.iter().map(|e| e.count.round() as u32).fold(0, gcd).max(1)
+    } else {
+        1
+    };
+
+    Ok(StructureInfo {
+        formula: formula(&elements, 1.0),
+        reduced_formula: formula(&elements, z as f64),
+        z,
+        atom_count: elements.iter().map(|e| e.count).sum(),
+        lattice_parameters: lattice_parameters(&structure.lattice),
+        volume,
+        formula_mass: cell_mass / z as f64,
+        // 1 Å³ = 1e-24 cm³
+        density: if volume > 0.0 { cell_mass / AVOGADRO / (volume * 1e-24) } else { 0.0 },
+        packing_fraction: if volume > 0.0 { sphere_volume / volume } else { 0.0 },
+        elements,
+    })
+}
+
+/// 由各元素原子数生成化学式，原子数先除以 divisor，非整数时保留至多三位小数
+fn formula(elements: &[ElementCount], divisor: f64) -> String {
+    elements
+        .iter()
+        .map(|e| {
+            let count = e.count / divisor;
+            if (count - 1.0).abs() < 1e-3 {
+                e.element.clone()
+            } else if (count - count.round()).abs() < 1e-3 {
+                format!("{}{}", e.element, count.round())
+            } else {
+                let text = format!("{:.3}", count);
+                format!("{}{}", e.element, text.trim_end_matches('0').trim_end_matches('.'))
+            }
+        })
+        .collect()
+}
+
+/// 最大公约数
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
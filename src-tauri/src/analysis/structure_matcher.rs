@@ -0,0 +1,370 @@
+use serde::{Deserialize, Serialize};
+use crate::analysis::measure::minimum_image;
+use crate::analysis::symmetry::primitive_cell;
+use crate::parsers::atom_config::{CrystalStructure, Site};
+use crate::utils::geometry::{cell_volume, lattice_parameters, reduce_lattice};
+use crate::utils::linalg::{det, dot, inverse, norm, scale, sub, vec_mat, Mat3, Vec3};
+
+/// 结构比对的容差
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct MatchSettings {
+    pub ltol: f64,          // 晶格矢量长度的相对容差
+    pub stol: f64,          // 原子位移容差，以 (V/N)^(1/3) 为单位
+    pub angle_tol: f64,     // 晶格夹角容差（度）
+    pub scale_volume: bool, // 比较前把第二个结构缩放到相同的单原子体积（如比较弛豫前后的结构）
+    pub primitive: bool,    // 比较前约化为原胞，使超胞与原胞也能匹配
+    pub symprec: f64,       // 寻找原胞时的原子位置容差（Å）
+}
+
+impl Default for MatchSettings {
+    fn default() -> Self {
+        MatchSettings {
+            ltol: 0.2,
+            stol: 0.3,
+            angle_tol: 5.0,
+            scale_volume: true,
+            primitive: true,
+            symprec: 0.1,
+        }
+    }
+}
+
+/// 两个结构之间的一对对应原子
+#[derive(Serialize, Clone, Debug)]
+pub struct AtomMapping {
+    pub index_1: usize, // 在第一个结构中的位点序号
+    pub label_1: String,
+    pub index_2: usize, // 在第二个结构中的位点序号
+    pub label_2: String,
+    pub displacement: f64, // 扣除整体平移后的位移（Å）
+}
+
+/// 结构比对结果
+#[derive(Serialize, Clone, Debug)]
+pub struct MatchResult {
+    pub matched: bool,
+    pub message: String,
+    pub rms_displacement: Option<f64>, // Å，按第一个结构的晶格计算
+    pub max_displacement: Option<f64>,
+    pub lattice_1: [f64; 6],                       // 约化晶胞的晶格参数
+    pub lattice_2: [f64; 6],
+    pub lattice_transform: Option<[[i32; 3]; 3]>, // 第二个约化晶胞到与第一个对应晶胞的整数变换
+    pub mapping: Vec<AtomMapping>,
+}
+
+/// 约化后的结构：晶格、位点与位点在原结构中的序号
+struct ReducedStructure {
+    lattice: Mat3,
+    sites: Vec<Site>,
+    origin: Vec<usize>,
+}
+
+/// 判断两个结构是否描述同一晶体
+/// 依次检查化学组成，约化晶胞，搜索长度与夹角在容差内的晶格对应，再在每种晶格对应下寻找原子对应
+pub fn match_structures(
+    structure_1: &CrystalStructure,
+    structure_2: &CrystalStructure,
+    settings: &MatchSettings,
+) -> MatchResult {
+    let reduced_1 = reduce_structure(structure_1, settings);
+    let mut reduced_2 = reduce_structure(structure_2, settings);
+
+    let mut result = MatchResult {
+        matched: false,
+        message: String::new(),
+        rms_displacement: None,
+        max_displacement: None,
+        lattice_1: lattice_parameters(&reduced_1.lattice),
+        lattice_2: lattice_parameters(&reduced_2.lattice),
+        lattice_transform: None,
+        mapping: Vec::new(),
+    };
+
+    if !same_composition(&structure_1.sites, &structure_2.sites) {
+        result.message = "Different compositions".to_string();
+        return result;
+    }
+    if reduced_1.sites.len() != reduced_2.sites.len() {
+        result.message = format!(
+            "Different number of atoms in the reduced cells ({} and {})",
+            reduced_1.sites.len(),
+            reduced_2.sites.len()
+        );
+        return result;
+    }
+
+    if settings.scale_volume {
+        let factor = (cell_volume(&reduced_1.lattice) / cell_volume(&reduced_2.lattice)).cbrt();
+        reduced_2.lattice = reduced_2.lattice.map(|v| scale(v, factor));
+    }
+
+    let mut best: Option<(f64, [[i32; 3]; 3], Vec<AtomMapping>)> = None;
+    for transform in lattice_mappings(&reduced_1.lattice, &reduced_2.lattice, settings) {
+        let Some(candidate) = match_sites(&reduced_1, &reduced_2, &transform, settings) else {
+            continue;
+        };
+        let rms = rms(&candidate);
+        if best.as_ref().is_none_or(|(best_rms, _, _)| rms < *best_rms) {
+            best = Some((rms, transform, candidate));
+        }
+    }
+
+    match best {
+        Some((rms, transform, mapping)) => {
+            result.matched = true;
+            result.message = "Structures match".to_string();
+            result.rms_displacement = Some(rms);
+            result.max_displacement = mapping.iter().map(|m| m.displacement).reduce(f64::max);
+            result.lattice_transform = Some(transform);
+            result.mapping = mapping;
+        }
+        None => result.message = "No lattice and atom mapping within tolerances".to_string(),
+    }
+    result
+}
+
+/// 约化为原胞（可选）并进行晶格约化，分数坐标随之变换
+fn reduce_structure(structure: &CrystalStructure, settings: &MatchSettings) -> ReducedStructure {
+    let (lattice, sites, origin) = if settings.primitive {
+        primitive_cell(&structure.lattice, &structure.sites, settings.symprec)
+    } else {
+        (structure.lattice, structure.sites.clone(), (0..structure.sites.len()).collect())
+    };
+    let (reduced, transform) = reduce_lattice(&lattice);
+    // 约化晶格 = T·L，分数坐标 f' = f·T⁻¹
+    let inverse_transform = inverse(&transform.map(|row| row.map(|v| v as f64))).expect("Singular transform");
+    let sites = sites
+        .into_iter()
+        .map(|site| Site { frac: vec_mat(site.frac, &inverse_transform), ..site })
+        .collect();
+    ReducedStructure { lattice: reduced, sites, origin }
+}
+
+/// 两个结构中各元素所占比例是否相同
+fn same_composition(sites_1: &[Site], sites_2: &[Site]) -> bool {
+    let count = |sites: &[Site], element: &str| sites.iter().filter(|s| s.element == element).count();
+    sites_1.iter().chain(sites_2).all(|site| {
+        count(sites_1, &site.element) * sites_2.len() == count(sites_2, &site.element) * sites_1.len()
+    })
+}
+
+/// 第二个晶格中与第一个晶格基矢量的长度、夹角都在容差内的基矢量组合
+/// 返回整数矩阵 M（对应晶胞 = M·L₂），要求 det(M) = 1 以保持体积与手性
+fn lattice_mappings(lattice_1: &Mat3, lattice_2: &Mat3, settings: &MatchSettings) -> Vec<[[i32; 3]; 3]> {
+    let lengths = lattice_1.map(norm);
+    let [_, _, _, alpha, beta, gamma] = lattice_parameters(lattice_1);
+
+    // 约化晶格中的对应基矢量只需在小整数组合中寻找
+    let mut vectors: Vec<([i32; 3], Vec3)> = Vec::new();
+    for i in -2..=2 {
+        for j in -2..=2 {
+            for k in -2..=2 {
+                if (i, j, k) != (0, 0, 0) {
+                    vectors.push(([i, j, k], vec_mat([i as f64, j as f64, k as f64], lattice_2)));
+                }
+            }
+        }
+    }
+    let candidates: Vec<Vec<&([i32; 3], Vec3)>> = lengths
+        .iter()
+        .map(|&length| {
+            vectors
+                .iter()
+                .filter(|(_, v)| (norm(*v) - length).abs() <= settings.ltol * length)
+                .collect()
+        })
+        .collect();
+
+    let angle = |u: Vec3, v: Vec3| (dot(u, v) / (norm(u) * norm(v))).clamp(-1.0, 1.0).acos().to_degrees();
+    let mut mappings = Vec::new();
+    for (ia, a) in &candidates[0] {
+        for (ib, b) in &candidates[1] {
+            if (angle(*a, *b) - gamma).abs() > settings.angle_tol {
+                continue;
+            }
+            for (ic, c) in &candidates[2] {
+                if (angle(*b, *c) - alpha).abs() > settings.angle_tol
+                    || (angle(*a, *c) - beta).abs() > settings.angle_tol
+                {
+                    continue;
+                }
+                let transform = [*ia, *ib, *ic];
+                if det(&transform.map(|row| row.map(|v| v as f64))).round() as i32 == 1 {
+                    mappings.push(transform);
+                }
+            }
+        }
+    }
+    mappings
+}
+
+/// 在给定晶格对应下寻找原子对应
+/// 以数量最少的元素中的一个原子为参照枚举整体平移，同种原子按最小镜像距离从近到远配对，
+/// 扣除平均位移后所有位移都小于 stol·(V/N)^(1/3) 时视为匹配，返回 RMS 最小的对应
+fn match_sites(
+    reduced_1: &ReducedStructure,
+    reduced_2: &ReducedStructure,
+    transform: &[[i32; 3]; 3],
+    settings: &MatchSettings,
+) -> Option<Vec<AtomMapping>> {
+    let lattice = &reduced_1.lattice;
+    let inverse_transform = inverse(&transform.map(|row| row.map(|v| v as f64)))?;
+    let frac_2: Vec<Vec3> = reduced_2.sites.iter().map(|s| vec_mat(s.frac, &inverse_transform)).collect();
+    let sites_1 = &reduced_1.sites;
+    let sites_2 = &reduced_2.sites;
+    let n = sites_1.len();
+    let tolerance = settings.stol * (cell_volume(lattice) / n as f64).cbrt();
+
+    let reference = (0..n)
+        .min_by_key(|&i| sites_1.iter().filter(|s| s.element == sites_1[i].element).count())?;
+
+    let mut best: Option<(f64, Vec<AtomMapping>)> = None;
+    for target in (0..n).filter(|&j| sites_2[j].element == sites_1[reference].element) {
+        let shift = sub(sites_1[reference].frac, frac_2[target]);
+        let shifted: Vec<Vec3> = frac_2.iter().map(|f| [0, 1, 2].map(|i| f[i] + shift[i])).collect();
+
+        // 所有同种原子对按距离排序后贪心配对
+        let mut pairs = Vec::new();
+        for (i, site) in sites_1.iter().enumerate() {
+            for j in (0..n).filter(|&j| sites_2[j].element == site.element) {
+                let d = displacement(lattice, site.frac, shifted[j]);
+                if norm(d) < 2.0 * tolerance {
+                    pairs.push((norm(d), i, j, d));
+                }
+            }
+        }
+        pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut partner = vec![None; n];
+        let mut used = vec![false; n];
+        for (_, i, j, d) in pairs {
+            if partner[i].is_none() && !used[j] {
+                partner[i] = Some((j, d));
+                used[j] = true;
+            }
+        }
+        let Some(partner) = partner.into_iter().collect::<Option<Vec<_>>>() else {
+            continue;
+        };
+
+        // 扣除整体平移
+        let mean = scale(
+            partner.iter().fold([0.0; 3], |acc, (_, d)| [acc[0] + d[0], acc[1] + d[1], acc[2] + d[2]]),
+            1.0 / n as f64,
+        );
+        let mapping: Vec<AtomMapping> = partner
+            .iter()
+            .enumerate()
+            .map(|(i, (j, d))| AtomMapping {
+                index_1: reduced_1.origin[i],
+                label_1: sites_1[i].label.clone(),
+                index_2: reduced_2.origin[*j],
+                label_2: sites_2[*j].label.clone(),
+                displacement: norm(sub(*d, mean)),
+            })
+            .collect();
+        if mapping.iter().any(|m| m.displacement > tolerance) {
+            continue;
+        }
+        let rms = rms(&mapping);
+        if best.as_ref().is_none_or(|(best_rms, _)| rms < *best_rms) {
+            best = Some((rms, mapping));
+        }
+    }
+    best.map(|(_, mapping)| mapping)
+}
+
+/// 最小镜像下由 a 指向 b 的笛卡尔位移
+fn displacement(lattice: &Mat3, a: Vec3, b: Vec3) -> Vec3 {
+    let image = minimum_image(lattice, a, b);
+    let d = [0, 1, 2].map(|i| b[i] + image[i] as f64 - a[i]);
+    vec_mat(d, lattice)
+}
+
+/// 位移的均方根
+fn rms(mapping: &[AtomMapping]) -> f64 {
+    (mapping.iter().map(|m| m.displacement * m.displacement).sum::<f64>() / mapping.len() as f64).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use super::*;
+    use crate::parsers::parser_utils::build_crystal_structure;
+    use crate::utils::geometry::lattice_from_parameters;
+
+    fn structure(lattice: Mat3, sites: &[(&str, Vec3)]) -> CrystalStructure {
+        let sites = sites
+            .iter()
+            .enumerate()
+            .map(|(n, &(element, frac))| Site::new(format!("{}{}", element, n + 1), element.to_string(), frac))
+            .collect();
+        build_crystal_structure(lattice, sites, &HashMap::new())
+    }
+
+    /// NaCl 惯用晶胞，a 为晶格常数
+    fn rock_salt(a: f64) -> CrystalStructure {
+        let mut sites = Vec::new();
+        for [x, y, z] in [[0.0, 0.0, 0.0], [0.0, 0.5, 0.5], [0.5, 0.0, 0.5], [0.5, 0.5, 0.0]] {
+            sites.push(("Na", [x, y, z]));
+            sites.push(("Cl", [x + 0.5, y, z]));
+        }
+        structure(lattice_from_parameters(a, a, a, 90.0, 90.0, 90.0), &sites)
+    }
+
+    #[test]
+    fn conventional_and_primitive_cells() {
+        let half = 5.64 / 2.0;
+        let primitive = structure(
+            [[0.0, half, half], [half, 0.0, half], [half, half, 0.0]],
+            &[("Cl", [0.5, 0.5, 0.5]), ("Na", [0.0, 0.0, 0.0])],
+        );
+        let result = match_structures(&rock_salt(5.64), &primitive, &MatchSettings::default());
+        assert!(result.matched, "{}", result.message);
+        assert!(result.rms_displacement.unwrap() < 1e-6);
+        assert_eq!(result.mapping.len(), 2);
+
+        let settings = MatchSettings { primitive: false, ..MatchSettings::default() };
+        let result = match_structures(&rock_salt(5.64), &primitive, &settings);
+        assert!(!result.matched);
+        assert!(result.message.starts_with("Different number of atoms"));
+    }
+
+    #[test]
+    fn shifted_and_scaled_cell() {
+        // 整体平移、改变原子顺序并膨胀 3% 的同一结构，一个原子偏离约 0.05 Å
+        let reference = rock_salt(5.64);
+        let mut sites: Vec<(&str, Vec3)> = reference
+            .sites
+            .iter()
+            .rev()
+            .map(|site| (site.element.as_str(), site.frac.map(|v| v + 0.13)))
+            .collect();
+        sites[0].1[0] += 0.008;
+        let expanded = structure(lattice_from_parameters(5.81, 5.81, 5.81, 90.0, 90.0, 90.0), &sites);
+        let result = match_structures(&reference, &expanded, &MatchSettings::default());
+        assert!(result.matched, "{}", result.message);
+        assert!(result.max_displacement.unwrap() > 0.0);
+
+        let settings = MatchSettings { scale_volume: false, ..MatchSettings::default() };
+        let result = match_structures(&reference, &expanded, &settings);
+        assert!(result.matched, "{}", result.message);
+    }
+
+    #[test]
+    fn different_structures() {
+        let reference = rock_salt(5.64);
+        let bromide = structure(reference.lattice, &[("Na", [0.0, 0.0, 0.0]), ("Br", [0.5, 0.5, 0.5])]);
+        let result = match_structures(&reference, &bromide, &MatchSettings::default());
+        assert_eq!(result.message, "Different compositions");
+
+        // 组成相同的 CsCl 型结构
+        let cesium_chloride = structure(
+            lattice_from_parameters(2.8, 2.8, 2.8, 90.0, 90.0, 90.0),
+            &[("Na", [0.0, 0.0, 0.0]), ("Cl", [0.5, 0.5, 0.5])],
+        );
+        let result = match_structures(&reference, &cesium_chloride, &MatchSettings::default());
+        assert!(!result.matched);
+        assert!(result.mapping.is_empty());
+    }
+}
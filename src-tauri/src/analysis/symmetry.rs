@@ -0,0 +1,328 @@
+use crate::analysis::neighbors::perpendicular_widths;
+use crate::parsers::atom_config::Site;
+use crate::utils::geometry::{cart_to_frac, cell_volume, reduce_lattice, wrap_frac};
+use crate::utils::linalg::{det, dot, norm, scale, vec_mat, Mat3, Vec3};
+
+/// 以分数坐标表示的对称操作 x' = R·x + t
+#[derive(Clone, Debug)]
+pub struct SymmetryOperation {
+    pub rotation: [[i32; 3]; 3],
+    pub translation: Vec3,
+}
+
+impl SymmetryOperation {
+    /// 作用于分数坐标
+    pub fn apply(&self, frac: Vec3) -> Vec3 {
+        let r = &self.rotation;
+        [0, 1, 2].map(|i| {
+            r[i][0] as f64 * frac[0] + r[i][1] as f64 * frac[1] + r[i][2] as f64 * frac[2]
+                + self.translation[i]
+        })
+    }
+}
+
+/// 两个分数坐标在周期性边界下的最短距离（Å）
+pub fn periodic_distance(lattice: &Mat3, a: Vec3, b: Vec3) -> f64 {
+    let diff = [0, 1, 2].map(|i| b[i] - a[i] - (b[i] - a[i]).round());
+    let mut best = f64::MAX;
+    // 斜交晶胞中四舍五入不一定最近，在周围再搜索一圈
+    for da in -1..=1 {
+        for db in -1..=1 {
+            for dc in -1..=1 {
+                let d = [diff[0] + da as f64, diff[1] + db as f64, diff[2] + dc as f64];
+                best = best.min(norm(vec_mat(d, lattice)));
+            }
+        }
+    }
+    best
+}
+
+/// 搜索结构的全部对称操作（含纯平移），symprec 为原子位置容差（Å）
+/// 在约化后的晶胞中搜索，再变换回原晶胞的分数坐标，斜交晶胞也不会遗漏操作
+pub fn find_symmetry_operations(lattice: &Mat3, sites: &[Site], symprec: f64) -> Vec<SymmetryOperation> {
+    // 约化基矢量 L' = T·L，分数坐标（列向量）x = Tᵀ·x'，故 R = Tᵀ·R'·T⁻ᵀ，t = Tᵀ·t'
+    let (reduced, transform) = reduce_lattice(lattice);
+    let reduced_sites: Vec<Site> = sites
+        .iter()
+        .map(|site| Site { frac: cart_to_frac(&reduced, vec_mat(site.frac, lattice)), ..site.clone() })
+        .collect();
+    let t = transpose_int(&transform);
+    let t_inv = transpose_int(&inverse_int(&transform));
+    reduced_cell_operations(&reduced, &reduced_sites, symprec)
+        .into_iter()
+        .map(|operation| {
+            let rotation = multiply_int(&multiply_int(&t, &operation.rotation), &t_inv);
+            let translation = wrap_frac([0, 1, 2].map(|i| {
+                (0..3).map(|j| t[i][j] as f64 * operation.translation[j]).sum::<f64>()
+            }));
+            SymmetryOperation { rotation, translation }
+        })
+        .collect()
+}
+
+fn transpose_int(m: &[[i32; 3]; 3]) -> [[i32; 3]; 3] {
+    [0, 1, 2].map(|i| [0, 1, 2].map(|j| m[j][i]))
+}
+
+fn multiply_int(a: &[[i32; 3]; 3], b: &[[i32; 3]; 3]) -> [[i32; 3]; 3] {
+    [0, 1, 2].map(|i| [0, 1, 2].map(|j| (0..3).map(|k| a[i][k] * b[k][j]).sum()))
+}
+
+/// 行列式为 ±1 的整数矩阵的逆（伴随矩阵除以行列式）
+fn inverse_int(m: &[[i32; 3]; 3]) -> [[i32; 3]; 3] {
+    let cofactor = |i: usize, j: usize| {
+        let (r0, r1) = ((i + 1) % 3, (i + 2) % 3);
+        let (c0, c1) = ((j + 1) % 3, (j + 2) % 3);
+        m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]
+    };
+    let det: i32 = (0..3).map(|j| m[0][j] * cofactor(0, j)).sum();
+    [0, 1, 2].map(|i| [0, 1, 2].map(|j| cofactor(j, i) * det))
+}
+
+/// 在约化晶胞中搜索对称操作
+/// 旋转部分取元素为 -1、0、1 且保持度规张量的整数矩阵，只对约化后的晶胞完备
+fn reduced_cell_operations(lattice: &Mat3, sites: &[Site], symprec: f64) -> Vec<SymmetryOperation> {
+    if sites.is_empty() {
+        return Vec::new();
+    }
+    // 度规张量 G = L·Lᵀ
+    let metric: Mat3 = [0, 1, 2].map(|i| [0, 1, 2].map(|j| dot(lattice[i], lattice[j])));
+    let lengths = [0, 1, 2].map(|i| metric[i][i].sqrt());
+
+    // 以数量最少的元素中的第一个原子作为参照，减少平移的候选数
+    let reference = sites
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, s)| sites.iter().filter(|o| o.element == s.element).count())
+        .map(|(index, _)| index)
+        .unwrap();
+
+    let grid = SiteGrid::new(lattice, sites, symprec);
+    let mut operations = Vec::new();
+    for code in 0..19683 {
+        let mut rotation = [[0i32; 3]; 3];
+        let mut c = code;
+        for row in rotation.iter_mut() {
+            for value in row.iter_mut() {
+                *value = c % 3 - 1;
+                c /= 3;
+            }
+        }
+        // Rᵀ·G·R = G
+        let preserves_metric = (0..3).all(|i| {
+            (0..3).all(|j| {
+                let mut value = 0.0;
+                for k in 0..3 {
+                    for l in 0..3 {
+                        value += rotation[k][i] as f64 * metric[k][l] * rotation[l][j] as f64;
+                    }
+                }
+                (value - metric[i][j]).abs() < symprec * (lengths[i] + lengths[j])
+            })
+        });
+        if !preserves_metric {
+            continue;
+        }
+
+        // 参照原子可以映射到任何同种原子，由此确定平移
+        let rotated_reference = SymmetryOperation {
+            rotation,
+            translation: [0.0; 3],
+        }
+        .apply(sites[reference].frac);
+        for target in sites.iter().filter(|s| s.element == sites[reference].element) {
+            let translation = [0, 1, 2].map(|i| {
+                let t = target.frac[i] - rotated_reference[i];
+                t - t.floor()
+            });
+            let operation = SymmetryOperation { rotation, translation };
+            if maps_structure(&grid, &operation) {
+                operations.push(operation);
+            }
+        }
+    }
+    operations
+}
+
+/// 原胞：由把结构映射到自身的纯平移确定
+/// 返回原胞晶格、原胞中的位点以及每个位点在原结构中的序号；结构本身已是原胞时原样返回
+pub fn primitive_cell(lattice: &Mat3, sites: &[Site], symprec: f64) -> (Mat3, Vec<Site>, Vec<usize>) {
+    let unchanged = (*lattice, sites.to_vec(), (0..sites.len()).collect());
+    if sites.is_empty() {
+        return unchanged;
+    }
+    let reference = sites
+        .iter()
+        .min_by_key(|s| sites.iter().filter(|o| o.element == s.element).count())
+        .unwrap();
+    let grid = SiteGrid::new(lattice, sites, symprec);
+    let translations: Vec<Vec3> = sites
+        .iter()
+        .filter(|s| s.element == reference.element)
+        .map(|s| wrap_frac([0, 1, 2].map(|i| s.frac[i] - reference.frac[i])))
+        .filter(|&t| {
+            let operation = SymmetryOperation { rotation: [[1, 0, 0], [0, 1, 0], [0, 0, 1]], translation: t };
+            maps_structure(&grid, &operation)
+        })
+        .collect();
+    if translations.len() <= 1 {
+        return unchanged;
+    }
+    // n 个纯平移构成 n 阶群，n·t 为整数；取整消除参考原子位置误差带入的偏差
+    let n = translations.len() as f64;
+    let translations: Vec<Vec3> = translations
+        .into_iter()
+        .map(|t| wrap_frac(t.map(|v| (v * n).round() / n)))
+        .collect();
+
+    // 候选格矢：纯平移加上原晶胞基矢量的小整数组合，按长度从短到长选取体积正确的三个
+    let mut candidates: Vec<Vec3> = Vec::new();
+    for t in &translations {
+        for da in -1..=1 {
+            for db in -1..=1 {
+                for dc in -1..=1 {
+                    let v = vec_mat([t[0] + da as f64, t[1] + db as f64, t[2] + dc as f64], lattice);
+                    if norm(v) > symprec {
+                        candidates.push(v);
+                    }
+                }
+            }
+        }
+    }
+    candidates.sort_by(|a, b| norm(*a).total_cmp(&norm(*b)));
+    let target_volume = cell_volume(lattice) / translations.len() as f64;
+    let mut primitive = None;
+    'search: for i in 0..candidates.len() {
+        for j in i + 1..candidates.len() {
+            for k in j + 1..candidates.len() {
+                let basis = [candidates[i], candidates[j], candidates[k]];
+                if (det(&basis).abs() - target_volume).abs() < 1e-3 * target_volume {
+                    primitive = Some(basis);
+                    break 'search;
+                }
+            }
+        }
+    }
+    let Some(mut primitive) = primitive else {
+        return unchanged;
+    };
+    if det(&primitive) < 0.0 {
+        primitive[2] = scale(primitive[2], -1.0);
+    }
+
+    // 原子折回原胞并去除平移等价的重复位点
+    let mut primitive_sites: Vec<Site> = Vec::new();
+    let mut origin = Vec::new();
+    for (index, site) in sites.iter().enumerate() {
+        let frac = wrap_frac(cart_to_frac(&primitive, vec_mat(site.frac, lattice)));
+        let duplicate = primitive_sites.iter().any(|other| {
+            other.element == site.element && periodic_distance(&primitive, other.frac, frac) < symprec
+        });
+        if !duplicate {
+            primitive_sites.push(Site { frac, ..site.clone() });
+            origin.push(index);
+        }
+    }
+    (primitive, primitive_sites, origin)
+}
+
+/// 判断对称操作是否把每个原子映射到同种原子上
+fn maps_structure(grid: &SiteGrid, operation: &SymmetryOperation) -> bool {
+    grid.sites
+        .iter()
+        .all(|site| grid.find(&site.element, operation.apply(site.frac)).is_some())
+}
+
+/// 按分数坐标分桶的位点，用于查找与某一位置重合（距离小于 symprec）的同种原子
+/// 桶的面间距不小于 symprec，只需搜索所在的桶与相邻的桶
+struct SiteGrid<'a> {
+    lattice: &'a Mat3,
+    sites: &'a [Site],
+    symprec: f64,
+    counts: [i64; 3],
+    bins: Vec<Vec<usize>>,
+}
+
+impl<'a> SiteGrid<'a> {
+    fn new(lattice: &'a Mat3, sites: &'a [Site], symprec: f64) -> Self {
+        // 平均每个桶约一个原子
+        let spacing = (cell_volume(lattice) / sites.len().max(1) as f64).cbrt().max(symprec);
+        let counts = perpendicular_widths(lattice).map(|w| ((w / spacing).floor() as i64).max(1));
+        let mut grid = SiteGrid {
+            lattice,
+            sites,
+            symprec,
+            counts,
+            bins: vec![Vec::new(); (counts[0] * counts[1] * counts[2]) as usize],
+        };
+        for (index, site) in sites.iter().enumerate() {
+            let bin = grid.bin(grid.key(site.frac));
+            grid.bins[bin].push(index);
+        }
+        grid
+    }
+
+    fn key(&self, frac: Vec3) -> [i64; 3] {
+        let frac = wrap_frac(frac);
+        [0, 1, 2].map(|k| ((frac[k] * self.counts[k] as f64).floor() as i64).min(self.counts[k] - 1))
+    }
+
+    fn bin(&self, key: [i64; 3]) -> usize {
+        let [a, b, c] = [0, 1, 2].map(|k| key[k].rem_euclid(self.counts[k]));
+        ((a * self.counts[1] + b) * self.counts[2] + c) as usize
+    }
+
+    /// 与 frac 重合的 element 原子的序号
+    fn find(&self, element: &str, frac: Vec3) -> Option<usize> {
+        let key = self.key(frac);
+        // 桶数不足 3 时相邻的桶会重复，直接取该方向的全部桶
+        let range = |k: usize| {
+            if self.counts[k] < 3 {
+                0..=self.counts[k] - 1
+            } else {
+                key[k] - 1..=key[k] + 1
+            }
+        };
+        for a in range(0) {
+            for b in range(1) {
+                for c in range(2) {
+                    let found = self.bins[self.bin([a, b, c])].iter().copied().find(|&index| {
+                        let site = &self.sites[index];
+                        site.element == element && periodic_distance(self.lattice, frac, site.frac) < self.symprec
+                    });
+                    if found.is_some() {
+                        return found;
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// 对称等价的位点分组，返回每个位点所属轨道的代表位点序号（轨道中最小的序号）
+pub fn equivalent_sites(lattice: &Mat3, sites: &[Site], symprec: f64) -> Vec<usize> {
+    let operations = find_symmetry_operations(lattice, sites, symprec);
+    let mut parent: Vec<usize> = (0..sites.len()).collect();
+    fn find(parent: &mut [usize], i: usize) -> usize {
+        let mut root = i;
+        while parent[root] != root {
+            root = parent[root];
+        }
+        parent[i] = root;
+        root
+    }
+
+    let grid = SiteGrid::new(lattice, sites, symprec);
+    for operation in &operations {
+        for (i, site) in sites.iter().enumerate() {
+            if let Some(j) = grid.find(&site.element, operation.apply(site.frac)) {
+                let (ri, rj) = (find(&mut parent, i), find(&mut parent, j));
+                if ri != rj {
+                    parent[ri.max(rj)] = ri.min(rj);
+                }
+            }
+        }
+    }
+    (0..sites.len()).map(|i| find(&mut parent, i)).collect()
+}
@@ -0,0 +1,120 @@
+use serde::Deserialize;
+use crate::parsers::atom_config::VolumetricData;
+
+/// 格点数据运算参数：结果 = data[data_index] + factor × 另一个格点数据
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct VolumetricOperation {
+    pub mode: String,      // add / subtract
+    pub data_index: usize, // 当前结构中作为被操作数的格点数据
+    pub other_index: usize, // 另一个文件中使用的格点数据
+    pub factor: f64,       // 另一个格点数据的系数
+}
+
+impl Default for VolumetricOperation {
+    fn default() -> Self {
+        VolumetricOperation {
+            mode: "subtract".to_string(),
+            data_index: 0,
+            other_index: 0,
+            factor: 1.0,
+        }
+    }
+}
+
+/// 判定两个格点数据的晶格是否相同的容差（Å）
+const LATTICE_TOLERANCE: f64 = 1e-3;
+
+/// 用三线性插值把格点数据重采样到新的格点数
+pub fn resample(data: &VolumetricData, dims: [usize; 3]) -> VolumetricData {
+    if data.dims == dims {
+        return data.clone();
+    }
+    let mut values = Vec::with_capacity(dims[0] * dims[1] * dims[2]);
+    for k in 0..dims[2] {
+        for j in 0..dims[1] {
+            for i in 0..dims[0] {
+                let frac = [
+                    i as f64 / dims[0] as f64,
+                    j as f64 / dims[1] as f64,
+                    k as f64 / dims[2] as f64,
+                ];
+                values.push(data.interpolate(frac));
+            }
+        }
+    }
+    VolumetricData::new(data.name.clone(), data.kind.clone(), dims, data.lattice, data.origin, values)
+}
+
+/// 线性组合 a + factor × b；b 的格点数不同时先重采样到 a 的格点
+/// 两者的晶格与数据类型必须一致
+pub fn combine(a: &VolumetricData, b: &VolumetricData, factor: f64, name: String) -> Result<VolumetricData, String> {
+    let mismatch = (0..3)
+        .flat_map(|i| (0..3).map(move |j| (i, j)))
+        .any(|(i, j)| (a.lattice[i][j] - b.lattice[i][j]).abs() > LATTICE_TOLERANCE);
+    if mismatch {
+        return Err("Volumetric grids have different lattices".to_string());
+    }
+    if a.kind != b.kind {
+        return Err(format!("Cannot combine {} data with {} data", a.kind, b.kind));
+    }
+    let b = resample(b, a.dims);
+    let values = a.values.iter().zip(b.values.iter()).map(|(x, y)| x + factor * y).collect();
+    Ok(VolumetricData::new(name, a.kind.clone(), a.dims, a.lattice, a.origin, values))
+}
+
+/// 数值乘以 factor
+pub fn scale_data(data: &VolumetricData, factor: f64, name: String) -> VolumetricData {
+    let values = data.values.iter().map(|v| v * factor).collect();
+    VolumetricData::new(name, data.kind.clone(), data.dims, data.lattice, data.origin, values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LATTICE: [[f64; 3]; 3] = [[4.0, 0.0, 0.0], [0.0, 4.0, 0.0], [0.0, 0.0, 4.0]];
+
+    /// 数值只随 a 方向格点变化的格点数据
+    fn along_a(kind: &str, dims: [usize; 3], value: impl Fn(usize) -> f64) -> VolumetricData {
+        let values = (0..dims[0] * dims[1] * dims[2]).map(|index| value(index % dims[0])).collect();
+        VolumetricData::new("data".to_string(), kind.to_string(), dims, LATTICE, [0.0; 3], values)
+    }
+
+    #[test]
+    fn resample_to_finer_grid() {
+        // 2 个格点的 0, 1 插值到 4 个格点：0, 0.5, 1, 0.5（周期性）
+        let coarse = along_a("density", [2, 2, 2], |i| i as f64);
+        let fine = resample(&coarse, [4, 3, 2]);
+        assert_eq!(fine.dims, [4, 3, 2]);
+        assert_eq!(fine.values.len(), 24);
+        for (index, &value) in fine.values.iter().enumerate() {
+            assert!((value - [0.0, 0.5, 1.0, 0.5][index % 4]).abs() < 1e-12);
+        }
+        assert_eq!(resample(&coarse, [2, 2, 2]).values, coarse.values);
+    }
+
+    #[test]
+    fn combine_mismatched_dims() {
+        // 结果使用第一个数据的格点
+        let a = along_a("density", [4, 4, 4], |_| 1.0);
+        let b = along_a("density", [2, 2, 2], |i| i as f64);
+        let difference = combine(&a, &b, -1.0, "difference".to_string()).unwrap();
+        assert_eq!(difference.dims, [4, 4, 4]);
+        assert_eq!(difference.name, "difference");
+        for (index, &value) in difference.values.iter().enumerate() {
+            assert!((value - [1.0, 0.5, 0.0, 0.5][index % 4]).abs() < 1e-12);
+        }
+        assert!((difference.mean - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn combine_incompatible_data() {
+        let a = along_a("density", [2, 2, 2], |_| 1.0);
+        let potential = along_a("potential", [2, 2, 2], |_| 1.0);
+        assert!(combine(&a, &potential, 1.0, "sum".to_string()).is_err());
+        let mut stretched = a.clone();
+        stretched.lattice[2][2] = 4.1;
+        assert!(combine(&a, &stretched, 1.0, "sum".to_string()).is_err());
+    }
+}
@@ -0,0 +1,361 @@
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use crate::analysis::neighbors::{neighbor_list, perpendicular_widths};
+use crate::analysis::symmetry::periodic_distance;
+use crate::utils::geometry::{cart_to_frac, cell_volume, frac_to_cart, wrap_frac};
+use crate::utils::linalg::{add, cross, dot, norm, scale, sub, Mat3, Vec3};
+
+/// Voronoi 胞的一个面
+#[derive(Clone, Debug)]
+pub struct VoronoiFace {
+    pub vertices: Vec<Vec3>,                  // 相对中心原子的笛卡尔坐标，从外侧看为逆时针
+    pub neighbor: Option<(usize, [i32; 3])>,  // 共享该面的近邻原子及其晶胞平移，初始包围盒的面为空
+}
+
+/// 一个原子的 Voronoi 胞（凸多面体）
+#[derive(Clone, Debug)]
+pub struct VoronoiCell {
+    pub faces: Vec<VoronoiFace>,
+}
+
+impl VoronoiCell {
+    /// 以原点为中心、半边长为 half 的立方体
+    fn cube(half: f64) -> Self {
+        let corner = |x: f64, y: f64, z: f64| [x * half, y * half, z * half];
+        let quads = [
+            [corner(1., -1., -1.), corner(1., 1., -1.), corner(1., 1., 1.), corner(1., -1., 1.)],
+            [corner(-1., -1., -1.), corner(-1., -1., 1.), corner(-1., 1., 1.), corner(-1., 1., -1.)],
+            [corner(-1., 1., -1.), corner(-1., 1., 1.), corner(1., 1., 1.), corner(1., 1., -1.)],
+            [corner(-1., -1., -1.), corner(1., -1., -1.), corner(1., -1., 1.), corner(-1., -1., 1.)],
+            [corner(-1., -1., 1.), corner(1., -1., 1.), corner(1., 1., 1.), corner(-1., 1., 1.)],
+            [corner(-1., -1., -1.), corner(-1., 1., -1.), corner(1., 1., -1.), corner(1., -1., -1.)],
+        ];
+        VoronoiCell {
+            faces: quads
+                .into_iter()
+                .map(|quad| VoronoiFace { vertices: quad.to_vec(), neighbor: None })
+                .collect(),
+        }
+    }
+
+    /// 用半空间 normal·x ≤ offset 切割多面体，切出的新面属于 neighbor
+    fn clip(&mut self, normal: Vec3, offset: f64, neighbor: (usize, [i32; 3])) {
+        const EPS: f64 = 1e-10;
+        let mut cut_points: Vec<Vec3> = Vec::new();
+        let mut clipped = false;
+        for face in &mut self.faces {
+            let n = face.vertices.len();
+            let side: Vec<f64> = face.vertices.iter().map(|v| dot(normal, *v) - offset).collect();
+            if side.iter().all(|&s| s <= EPS) {
+                continue;
+            }
+            clipped = true;
+            // Sutherland–Hodgman 多边形裁剪
+            let mut kept = Vec::new();
+            for k in 0..n {
+                let (a, b) = (face.vertices[k], face.vertices[(k + 1) % n]);
+                let (sa, sb) = (side[k], side[(k + 1) % n]);
+                if sa <= EPS {
+                    kept.push(a);
+                }
+                if (sa <= EPS) != (sb <= EPS) {
+                    let point = add(a, scale(sub(b, a), sa / (sa - sb)));
+                    kept.push(point);
+                    cut_points.push(point);
+                }
+            }
+            face.vertices = kept;
+        }
+        if !clipped {
+            return;
+        }
+        self.faces.retain(|face| face.vertices.len() >= 3);
+
+        // 切口上的点去重后绕法向排序，构成新面
+        let mut unique: Vec<Vec3> = Vec::new();
+        for point in cut_points {
+            if !unique.iter().any(|u| norm(sub(*u, point)) < 1e-8) {
+                unique.push(point);
+            }
+        }
+        if unique.len() < 3 {
+            return;
+        }
+        let center = scale(unique.iter().fold([0.0; 3], |acc, p| add(acc, *p)), 1.0 / unique.len() as f64);
+        let u = sub(unique[0], center);
+        let u = scale(u, 1.0 / norm(u));
+        let v = cross(normal, u);
+        unique.sort_by(|p, q| {
+            let angle = |x: Vec3| dot(sub(x, center), v).atan2(dot(sub(x, center), u));
+            angle(*p).total_cmp(&angle(*q))
+        });
+        self.faces.push(VoronoiFace { vertices: unique, neighbor: Some(neighbor) });
+    }
+
+    /// 多面体体积：以顶点重心为锥顶对各面求锥体体积
+    /// 径向剖分中被较大原子完全覆盖的原子，其胞为空，体积为 0
+    pub fn volume(&self) -> f64 {
+        let Some(apex) = self.centroid() else {
+            return 0.0;
+        };
+        self.faces
+            .iter()
+            .map(|face| {
+                let p0 = face.vertices[0];
+                (1..face.vertices.len() - 1)
+                    .map(|k| {
+                        let (p1, p2) = (face.vertices[k], face.vertices[k + 1]);
+                        dot(sub(p0, apex), cross(sub(p1, apex), sub(p2, apex))).abs() / 6.0
+                    })
+                    .sum::<f64>()
+            })
+            .sum()
+    }
+
+    /// 顶点重心，空胞返回 None
+    fn centroid(&self) -> Option<Vec3> {
+        let points: Vec<Vec3> = self.faces.iter().flat_map(|f| f.vertices.iter().copied()).collect();
+        if points.is_empty() {
+            return None;
+        }
+        Some(scale(points.iter().fold([0.0; 3], |acc, p| add(acc, *p)), 1.0 / points.len() as f64))
+    }
+
+    /// 离中心最远的顶点距离
+    fn max_radius(&self) -> f64 {
+        self.faces
+            .iter()
+            .flat_map(|f| f.vertices.iter())
+            .map(|v| norm(*v))
+            .fold(0.0, f64::max)
+    }
+}
+
+/// 周期性径向（功率）Voronoi 剖分：原子 i 与 j 之间的分界面满足 |x-a_i|² - r_i² = |x-a_j|² - r_j²
+/// 半径全部相等时退化为普通 Voronoi 剖分
+/// 近邻搜索半径自适应增大，直到更远的原子不可能再切割任何胞
+pub fn radical_voronoi(lattice: &Mat3, frac: &[Vec3], radii: &[f64]) -> Vec<VoronoiCell> {
+    let n = frac.len();
+    if n == 0 || cell_volume(lattice) < 1e-12 {
+        return Vec::new();
+    }
+    let r_max = radii.iter().copied().fold(0.0, f64::max);
+    let mut cutoff = 2.5 * (cell_volume(lattice) / n as f64).cbrt() + 2.0 * r_max;
+    loop {
+        let neighbors = neighbor_list(lattice, frac, cutoff);
+        let cells: Vec<VoronoiCell> = (0..n)
+            .map(|i| {
+                let mut cell = VoronoiCell::cube(cutoff);
+                for neighbor in &neighbors[i] {
+                    let d = neighbor.distance;
+                    if d < 1e-8 {
+                        continue;
+                    }
+                    let normal = scale(neighbor.vector, 1.0 / d);
+                    let offset = (d * d + radii[i] * radii[i] - radii[neighbor.index].powi(2)) / (2.0 * d);
+                    cell.clip(normal, offset, (neighbor.index, neighbor.image));
+                }
+                cell
+            })
+            .collect();
+
+        // 距离 d 以外的原子所生成的分界面离中心至少 (d² - r_max²) / 2d
+        let complete = cells.iter().all(|cell| {
+            let reach = cell.max_radius();
+            (cutoff * cutoff - r_max * r_max) / (2.0 * cutoff) > reach
+                && cell.faces.iter().all(|face| face.neighbor.is_some())
+        });
+        if complete {
+            return cells;
+        }
+        cutoff *= 1.5;
+    }
+}
+
+/// Voronoi 网络的节点：Voronoi 胞的顶点
+#[derive(Serialize, Clone, Debug)]
+pub struct NetworkNode {
+    pub frac: Vec3,  // 折回晶胞内的分数坐标
+    pub radius: f64, // 到最近原子表面的距离（Å），即该处可容纳的最大球半径
+}
+
+/// Voronoi 网络的边：Voronoi 胞的棱
+#[derive(Serialize, Clone, Debug)]
+pub struct NetworkEdge {
+    pub from: usize,
+    pub to: usize,
+    pub image: [i32; 3], // 终点节点所在的晶胞平移
+    pub bottleneck: f64, // 沿棱到原子表面的最小距离（Å），即能通过该棱的最大球半径
+}
+
+/// 周期性 Voronoi 网络：空隙空间的骨架
+#[derive(Serialize, Clone, Debug)]
+pub struct VoronoiNetwork {
+    pub nodes: Vec<NetworkNode>,
+    pub edges: Vec<NetworkEdge>,
+}
+
+/// 由 Voronoi 胞的顶点和棱构建周期性网络
+pub fn voronoi_network(lattice: &Mat3, frac: &[Vec3], radii: &[f64], cells: &[VoronoiCell]) -> VoronoiNetwork {
+    const NODE_TOLERANCE: f64 = 1e-4; // Å
+    let mut nodes: Vec<NetworkNode> = Vec::new();
+    let mut edges: Vec<NetworkEdge> = Vec::new();
+    let surface = SurfaceGrid::new(lattice, frac, radii);
+
+    // 节点按折回后的分数坐标分桶：桶宽保证相距小于容差的两点落在相邻的桶中，
+    // 查找时只比较周围 27 个桶内的节点
+    let steps = perpendicular_widths(lattice).map(|w| NODE_TOLERANCE / w);
+    let bucket_counts = steps.map(|step| ((1.0 / step).ceil() as i64).max(1));
+    let bucket = |f: Vec3| [0, 1, 2].map(|k| ((f[k] / steps[k]).floor() as i64).min(bucket_counts[k] - 1));
+    let mut buckets: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+
+    // 返回节点序号与顶点所在的晶胞平移
+    let mut locate = |nodes: &mut Vec<NetworkNode>, point: Vec3| -> (usize, [i32; 3]) {
+        let unwrapped = cart_to_frac(lattice, point);
+        let wrapped = wrap_frac(unwrapped);
+        let key = bucket(wrapped);
+        for da in -1..=1 {
+            for db in -1..=1 {
+                for dc in -1..=1 {
+                    let around = [0, 1, 2].map(|k| (key[k] + [da, db, dc][k]).rem_euclid(bucket_counts[k]));
+                    let Some(candidates) = buckets.get(&around) else {
+                        continue;
+                    };
+                    if let Some(&index) = candidates
+                        .iter()
+                        .find(|&&index| periodic_distance(lattice, nodes[index].frac, wrapped) < NODE_TOLERANCE)
+                    {
+                        // 已有节点可能位于相邻晶胞的边界上，按实际坐标修正平移
+                        let existing = nodes[index].frac;
+                        let image = [0, 1, 2].map(|k| (unwrapped[k] - existing[k]).round() as i32);
+                        return (index, image);
+                    }
+                }
+            }
+        }
+        let image = [0, 1, 2].map(|k| (unwrapped[k] - wrapped[k]).round() as i32);
+        nodes.push(NetworkNode { frac: wrapped, radius: surface.point_distance(wrapped) });
+        buckets.entry(key).or_default().push(nodes.len() - 1);
+        (nodes.len() - 1, image)
+    };
+
+    // 棱按 (较小节点, 较大节点, 平移) 去重，两个方向记为同一条棱
+    let mut seen: HashSet<(usize, usize, [i32; 3])> = HashSet::new();
+    for (i, cell) in cells.iter().enumerate() {
+        let center = frac_to_cart(lattice, frac[i]);
+        for face in &cell.faces {
+            let located: Vec<(usize, [i32; 3])> =
+                face.vertices.iter().map(|v| locate(&mut nodes, add(center, *v))).collect();
+            for k in 0..located.len() {
+                let (a, image_a) = located[k];
+                let (b, image_b) = located[(k + 1) % located.len()];
+                if a == b && image_a == image_b {
+                    continue;
+                }
+                let image = [0, 1, 2].map(|x| image_b[x] - image_a[x]);
+                let reverse = image.map(|x| -x);
+                let key = if (a, image) <= (b, reverse) { (a, b, image) } else { (b, a, reverse) };
+                if seen.insert(key) {
+                    let start = nodes[a].frac;
+                    let end = [0, 1, 2].map(|x| nodes[b].frac[x] + image[x] as f64);
+                    edges.push(NetworkEdge {
+                        from: a,
+                        to: b,
+                        image,
+                        bottleneck: surface.segment_distance(start, end),
+                    });
+                }
+            }
+        }
+    }
+    VoronoiNetwork { nodes, edges }
+}
+
+/// 按分数坐标分桶的原子，用于求点、线段到最近原子表面的距离（周期性边界）
+/// 从所在的桶向外逐层搜索，已找到的距离不大于未搜索桶的距离下界时停止
+struct SurfaceGrid<'a> {
+    lattice: &'a Mat3,
+    frac: Vec<Vec3>, // 折回晶胞内的原子坐标
+    radii: &'a [f64],
+    counts: [i64; 3],
+    bins: Vec<Vec<usize>>,
+    bin_width: f64, // 桶在三个方向上面间距的最小值（Å）
+    r_max: f64,
+}
+
+impl<'a> SurfaceGrid<'a> {
+    fn new(lattice: &'a Mat3, frac: &[Vec3], radii: &'a [f64]) -> Self {
+        // 平均每个桶约一个原子
+        let spacing = (cell_volume(lattice) / frac.len().max(1) as f64).cbrt().max(0.5);
+        let widths = perpendicular_widths(lattice);
+        let counts = widths.map(|w| ((w / spacing).floor() as i64).max(1));
+        let frac: Vec<Vec3> = frac.iter().map(|f| wrap_frac(*f)).collect();
+        let mut bins = vec![Vec::new(); (counts[0] * counts[1] * counts[2]) as usize];
+        for (i, f) in frac.iter().enumerate() {
+            let key = [0, 1, 2].map(|k| ((f[k] * counts[k] as f64).floor() as i64).min(counts[k] - 1));
+            bins[((key[0] * counts[1] + key[1]) * counts[2] + key[2]) as usize].push(i);
+        }
+        SurfaceGrid {
+            lattice,
+            frac,
+            radii,
+            counts,
+            bins,
+            bin_width: (0..3).map(|k| widths[k] / counts[k] as f64).fold(f64::MAX, f64::min),
+            r_max: radii.iter().copied().fold(0.0, f64::max),
+        }
+    }
+
+    /// 以分数坐标 center 为中心逐层搜索原子镜像，distance(镜像的笛卡尔坐标, 半径) 为候选值；
+    /// reach 为被测对象离 center 的最远距离，用于估计未搜索原子的下界
+    fn search(&self, center: Vec3, reach: f64, distance: impl Fn(Vec3, f64) -> f64) -> f64 {
+        if self.frac.is_empty() {
+            return f64::MAX;
+        }
+        let key = [0, 1, 2].map(|k| (center[k] * self.counts[k] as f64).floor() as i64);
+        let mut best = f64::MAX;
+        for ring in 0i64.. {
+            for da in -ring..=ring {
+                for db in -ring..=ring {
+                    for dc in -ring..=ring {
+                        if da.abs().max(db.abs()).max(dc.abs()) != ring {
+                            continue;
+                        }
+                        let bin = [key[0] + da, key[1] + db, key[2] + dc];
+                        let wrapped = [0, 1, 2].map(|k| bin[k].rem_euclid(self.counts[k]));
+                        let image = [0, 1, 2].map(|k| bin[k].div_euclid(self.counts[k]) as f64);
+                        let index = (wrapped[0] * self.counts[1] + wrapped[1]) * self.counts[2] + wrapped[2];
+                        for &i in &self.bins[index as usize] {
+                            let f = [0, 1, 2].map(|k| self.frac[i][k] + image[k]);
+                            best = best.min(distance(frac_to_cart(self.lattice, f), self.radii[i]));
+                        }
+                    }
+                }
+            }
+            // 第 ring 层之外的原子距 center 至少 ring 个桶宽
+            if best <= ring as f64 * self.bin_width - reach - self.r_max {
+                break;
+            }
+        }
+        best
+    }
+
+    /// 点到最近原子表面的距离，点为分数坐标
+    fn point_distance(&self, point: Vec3) -> f64 {
+        let p = frac_to_cart(self.lattice, point);
+        self.search(point, 0.0, |atom, r| norm(sub(atom, p)) - r)
+    }
+
+    /// 线段到最近原子表面的最小距离，线段端点为分数坐标
+    fn segment_distance(&self, start: Vec3, end: Vec3) -> f64 {
+        let a = frac_to_cart(self.lattice, start);
+        let b = frac_to_cart(self.lattice, end);
+        let ab = sub(b, a);
+        let length2 = dot(ab, ab).max(1e-16);
+        let middle = [0, 1, 2].map(|k| (start[k] + end[k]) / 2.0);
+        self.search(middle, norm(ab) / 2.0, |atom, r| {
+            let t = (dot(sub(atom, a), ab) / length2).clamp(0.0, 1.0);
+            norm(sub(atom, add(a, scale(ab, t)))) - r
+        })
+    }
+}
@@ -0,0 +1,238 @@
+use serde::Deserialize;
+use crate::analysis::symmetry::{equivalent_sites, find_symmetry_operations, periodic_distance};
+use crate::builders::supercell::make_supercell;
+use crate::parsers::atom_config::{CrystalStructure, Site};
+use crate::utils::geometry::wrap_frac;
+use crate::utils::linalg::{norm, Mat3, Vec3};
+
+/// 替位掺杂：将 from 元素替换为 to 元素
+#[derive(Deserialize, Clone, Debug)]
+pub struct Substitution {
+    pub from: String,
+    pub to: String,
+}
+
+/// 点缺陷生成参数
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct DefectSettings {
+    pub supercell: [usize; 3],
+    pub vacancies: bool,
+    pub substitutions: Vec<Substitution>,
+    pub interstitials: Vec<String>, // 插入间隙位的元素
+    pub min_interstitial_distance: f64, // 间隙位到最近原子的最小距离（Å）
+    pub grid_spacing: f64,              // 搜索空隙中心的网格间距（Å）
+    pub symprec: f64,                   // 对称性判断的位置容差（Å）
+}
+
+impl Default for DefectSettings {
+    fn default() -> Self {
+        DefectSettings {
+            supercell: [2, 2, 2],
+            vacancies: true,
+            substitutions: Vec::new(),
+            interstitials: Vec::new(),
+            min_interstitial_distance: 1.5,
+            grid_spacing: 0.2,
+            symprec: 0.01,
+        }
+    }
+}
+
+/// 生成的缺陷结构
+#[derive(Clone, Debug)]
+pub struct Defect {
+    pub kind: &'static str, // vacancy / substitution / interstitial
+    pub name: String,       // 如 V_O1、Al_Si1、Li_i1
+    pub multiplicity: usize, // 原胞中等价位置的数目
+    pub position: Vec3,     // 缺陷在超胞中的分数坐标
+    pub lattice: Mat3,
+    pub sites: Vec<Site>,
+}
+
+/// 在超胞中生成对称性不等价的空位、替位与间隙缺陷
+pub fn generate_defects(structure: &CrystalStructure, settings: &DefectSettings) -> Vec<Defect> {
+    let lattice = &structure.lattice;
+    let sites = &structure.sites;
+    let orbits = equivalent_sites(lattice, sites, settings.symprec);
+    let (super_lattice, super_sites) = make_supercell(lattice, sites, settings.supercell);
+    let repeats = settings.supercell.map(|n| n.max(1) as f64);
+    let to_super = |frac: Vec3| [frac[0] / repeats[0], frac[1] / repeats[1], frac[2] / repeats[2]];
+
+    // 每个轨道取序号最小的位点作为代表；超胞中前 N 个位点即原胞位点
+    let representatives: Vec<usize> = (0..sites.len()).filter(|&i| orbits[i] == i).collect();
+    let multiplicity = |i: usize| orbits.iter().filter(|&&o| o == i).count();
+
+    let mut defects = Vec::new();
+    if settings.vacancies {
+        for &i in &representatives {
+            let mut defect_sites = super_sites.clone();
+            defect_sites.remove(i);
+            defects.push(Defect {
+                kind: "vacancy",
+                name: format!("V_{}", sites[i].label),
+                multiplicity: multiplicity(i),
+                position: to_super(sites[i].frac),
+                lattice: super_lattice,
+                sites: defect_sites,
+            });
+        }
+    }
+
+    for substitution in &settings.substitutions {
+        for &i in representatives.iter().filter(|&&i| sites[i].element == substitution.from) {
+            let mut defect_sites = super_sites.clone();
+            defect_sites[i].element = substitution.to.clone();
+            defect_sites[i].label = format!("{}_{}", substitution.to, sites[i].label);
+            defects.push(Defect {
+                kind: "substitution",
+                name: format!("{}_{}", substitution.to, sites[i].label),
+                multiplicity: multiplicity(i),
+                position: to_super(sites[i].frac),
+                lattice: super_lattice,
+                sites: defect_sites,
+            });
+        }
+    }
+
+    if !settings.interstitials.is_empty() {
+        let candidates = interstitial_sites(structure, settings);
+        for element in &settings.interstitials {
+            for (n, (frac, count)) in candidates.iter().enumerate() {
+                let mut defect_sites = super_sites.clone();
+                let label = format!("{}_i{}", element, n + 1);
+                defect_sites.push(Site::new(label.clone(), element.clone(), to_super(*frac)));
+                defects.push(Defect {
+                    kind: "interstitial",
+                    name: label,
+                    multiplicity: *count,
+                    position: to_super(*frac),
+                    lattice: super_lattice,
+                    sites: defect_sites,
+                });
+            }
+        }
+    }
+
+    defects
+}
+
+/// 搜索对称性不等价的间隙位（原胞分数坐标及其多重度）
+/// 间隙位取到最近原子距离的局部极大值，即 Voronoi 顶点中的空隙中心
+pub fn interstitial_sites(structure: &CrystalStructure, settings: &DefectSettings) -> Vec<(Vec3, usize)> {
+    let lattice = &structure.lattice;
+    let sites = &structure.sites;
+    if sites.is_empty() {
+        return Vec::new();
+    }
+    let nearest = |frac: Vec3| -> f64 {
+        sites
+            .iter()
+            .map(|s| periodic_distance(lattice, frac, s.frac))
+            .fold(f64::MAX, f64::min)
+    };
+
+    // 在网格上计算到最近原子的距离
+    let counts = lattice.map(|v| ((norm(v) / settings.grid_spacing.max(0.05)).ceil() as usize).max(2));
+    let [na, nb, nc] = counts;
+    let mut field = vec![0.0; na * nb * nc];
+    let index = |i: usize, j: usize, k: usize| (i * nb + j) * nc + k;
+    for i in 0..na {
+        for j in 0..nb {
+            for k in 0..nc {
+                let frac = [i as f64 / na as f64, j as f64 / nb as f64, k as f64 / nc as f64];
+                field[index(i, j, k)] = nearest(frac);
+            }
+        }
+    }
+
+    // 取周期性网格上的局部极大值，并在附近细化
+    let mut candidates: Vec<(Vec3, f64)> = Vec::new();
+    for i in 0..na {
+        for j in 0..nb {
+            for k in 0..nc {
+                let value = field[index(i, j, k)];
+                if value < settings.min_interstitial_distance {
+                    continue;
+                }
+                let mut is_maximum = true;
+                'search: for di in [na - 1, 0, 1] {
+                    for dj in [nb - 1, 0, 1] {
+                        for dk in [nc - 1, 0, 1] {
+                            let other = field[index((i + di) % na, (j + dj) % nb, (k + dk) % nc)];
+                            if other > value {
+                                is_maximum = false;
+                                break 'search;
+                            }
+                        }
+                    }
+                }
+                if !is_maximum {
+                    continue;
+                }
+                let start = [i as f64 / na as f64, j as f64 / nb as f64, k as f64 / nc as f64];
+                let refined = refine_void_centre(start, counts, &nearest);
+                let distance = nearest(refined);
+                if !candidates
+                    .iter()
+                    .any(|(frac, _)| periodic_distance(lattice, *frac, refined) < settings.grid_spacing)
+                {
+                    candidates.push((refined, distance));
+                }
+            }
+        }
+    }
+    // 空隙大的优先
+    candidates.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+
+    // 按对称操作归并等价的间隙位
+    let operations = find_symmetry_operations(lattice, sites, settings.symprec);
+    let tolerance = settings.grid_spacing.max(settings.symprec);
+    let mut unique: Vec<(Vec3, usize)> = Vec::new();
+    let mut seen: Vec<Vec3> = Vec::new();
+    for (frac, _) in candidates {
+        if seen.iter().any(|s| periodic_distance(lattice, *s, frac) < tolerance) {
+            continue;
+        }
+        let mut orbit: Vec<Vec3> = Vec::new();
+        for operation in &operations {
+            let image = wrap_frac(operation.apply(frac));
+            if !orbit.iter().any(|o| periodic_distance(lattice, *o, image) < tolerance) {
+                orbit.push(image);
+            }
+        }
+        if orbit.is_empty() {
+            orbit.push(frac);
+        }
+        unique.push((wrap_frac(frac), orbit.len()));
+        seen.extend(orbit);
+    }
+    unique
+}
+
+/// 以逐步减半的步长做坐标搜索，使到最近原子的距离最大
+fn refine_void_centre(start: Vec3, counts: [usize; 3], nearest: &impl Fn(Vec3) -> f64) -> Vec3 {
+    let mut best = start;
+    let mut best_value = nearest(start);
+    let mut steps = counts.map(|n| 0.5 / n as f64);
+    for _ in 0..8 {
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for axis in 0..3 {
+                for sign in [-1.0, 1.0] {
+                    let mut trial = best;
+                    trial[axis] += sign * steps[axis];
+                    let value = nearest(trial);
+                    if value > best_value + 1e-9 {
+                        best = trial;
+                        best_value = value;
+                        improved = true;
+                    }
+                }
+            }
+        }
+        steps = steps.map(|s| s / 2.0);
+    }
+    wrap_frac(best)
+}
@@ -0,0 +1,5 @@
+pub mod slab;
+pub mod supercell;
+pub mod defects;
+pub mod neb;
+pub mod strain;
@@ -0,0 +1,221 @@
+use serde::Deserialize;
+use crate::analysis::measure::minimum_image;
+use crate::parsers::atom_config::{CrystalStructure, Site};
+use crate::utils::geometry::{cart_to_frac, frac_to_cart, wrap_frac};
+use crate::utils::linalg::{add, dot, inverse, norm, scale, sub, vec_mat, Mat3, Vec3};
+
+/// NEB 图像链的插值参数
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct NebSettings {
+    pub images: usize,      // 中间图像数，不含首末结构
+    pub idpp: bool,         // 线性插值后再用 IDPP 方法优化
+    pub idpp_steps: usize,  // IDPP 优化的最大步数
+    pub idpp_fmax: f64,     // IDPP 优化的收敛力判据
+    pub spring: f64,        // NEB 弹簧常数
+}
+
+impl Default for NebSettings {
+    fn default() -> Self {
+        NebSettings {
+            images: 5,
+            idpp: false,
+            idpp_steps: 100,
+            idpp_fmax: 0.1,
+            spring: 0.1,
+        }
+    }
+}
+
+/// 一个图像：晶格与位点
+pub type Image = (Mat3, Vec<Site>);
+
+/// 在首末结构之间插值生成 NEB 图像链，返回包含首末结构在内的 images + 2 个图像
+/// 原子按序号一一对应，位移取最小镜像；晶格随图像线性变化
+pub fn interpolate_images(
+    initial: &CrystalStructure,
+    last: &CrystalStructure,
+    settings: &NebSettings,
+) -> Result<Vec<Image>, String> {
+    if initial.sites.len() != last.sites.len() {
+        return Err(format!(
+            "Initial and final structures have different numbers of atoms ({} and {})",
+            initial.sites.len(),
+            last.sites.len()
+        ));
+    }
+    if let Some(index) = (0..initial.sites.len()).find(|&i| initial.sites[i].element != last.sites[i].element) {
+        return Err(format!(
+            "Atom {} is {} in the initial structure but {} in the final structure",
+            index + 1,
+            initial.sites[index].element,
+            last.sites[index].element
+        ));
+    }
+
+    // 最小镜像位移（分数坐标）
+    let displacements: Vec<Vec3> = initial
+        .sites
+        .iter()
+        .zip(&last.sites)
+        .map(|(a, b)| [0, 1, 2].map(|i| {
+            let d = b.frac[i] - a.frac[i];
+            d - d.round()
+        }))
+        .collect();
+
+    let count = settings.images + 1;
+    let mut images: Vec<Image> = (0..=count)
+        .map(|k| {
+            let t = k as f64 / count as f64;
+            let lattice = [0, 1, 2].map(|i| add(initial.lattice[i], scale(sub(last.lattice[i], initial.lattice[i]), t)));
+            let sites = initial
+                .sites
+                .iter()
+                .zip(&displacements)
+                .map(|(site, d)| Site { frac: add(site.frac, scale(*d, t)), ..site.clone() })
+                .collect();
+            (lattice, sites)
+        })
+        .collect();
+
+    if settings.idpp && settings.images > 0 {
+        idpp_optimize(&mut images, settings);
+    }
+    for (_, sites) in images.iter_mut() {
+        sites.iter_mut().for_each(|site| site.frac = wrap_frac(site.frac));
+    }
+    Ok(images)
+}
+
+/// 最小镜像下由 a 指向 b 的笛卡尔矢量（a、b 为笛卡尔坐标），斜交晶胞中同样取最近的镜像
+fn pair_vector(lattice: &Mat3, inverse_lattice: &Mat3, a: Vec3, b: Vec3) -> Vec3 {
+    let (fa, fb) = (vec_mat(a, inverse_lattice), vec_mat(b, inverse_lattice));
+    let image = minimum_image(lattice, fa, fb);
+    vec_mat([0, 1, 2].map(|k| fb[k] - fa[k] + image[k] as f64), lattice)
+}
+
+/// 图像内全部原子对的最小镜像距离
+fn pair_distances(lattice: &Mat3, positions: &[Vec3]) -> Vec<f64> {
+    let inverse_lattice = inverse(lattice).expect("Singular lattice matrix");
+    let n = positions.len();
+    let mut distances = Vec::with_capacity(n * n.saturating_sub(1) / 2);
+    for i in 0..n {
+        for j in i + 1..n {
+            distances.push(norm(pair_vector(lattice, &inverse_lattice, positions[i], positions[j])));
+        }
+    }
+    distances
+}
+
+/// IDPP 目标函数 S = Σ w(d)·(d_target - d)²，w = 1/d⁴，返回各原子受力 -∂S/∂x
+fn idpp_forces(lattice: &Mat3, positions: &[Vec3], target: &[f64]) -> Vec<Vec3> {
+    let inverse_lattice = inverse(lattice).expect("Singular lattice matrix");
+    let n = positions.len();
+    let mut forces = vec![[0.0; 3]; n];
+    let mut pair = 0;
+    for i in 0..n {
+        for j in i + 1..n {
+            let r = pair_vector(lattice, &inverse_lattice, positions[i], positions[j]);
+            let d = norm(r).max(1e-6);
+            let diff = target[pair] - d;
+            // dS/dd = w'·diff² - 2w·diff
+            let d_s = -4.0 / d.powi(5) * diff * diff - 2.0 / d.powi(4) * diff;
+            let f = scale(r, d_s / d);
+            forces[i] = add(forces[i], f);
+            forces[j] = sub(forces[j], f);
+            pair += 1;
+        }
+    }
+    forces
+}
+
+/// 用 NEB 方法优化中间图像，使原子间距接近首末结构间距的线性插值（Smidstrup 等，2014）
+/// IDPP 力只保留垂直于路径切线的分量，沿切线方向由弹簧力保持图像间距，采用 FIRE 算法
+fn idpp_optimize(images: &mut [Image], settings: &NebSettings) {
+    let count = images.len() - 1;
+    let n = images[0].1.len();
+    let first = pair_distances(&images[0].0, &cartesian(&images[0]));
+    let last = pair_distances(&images[count].0, &cartesian(&images[count]));
+    let targets: Vec<Vec<f64>> = (0..=count)
+        .map(|k| {
+            let t = k as f64 / count as f64;
+            first.iter().zip(&last).map(|(a, b)| a + (b - a) * t).collect()
+        })
+        .collect();
+
+    let mut positions: Vec<Vec<Vec3>> = images.iter().map(cartesian).collect();
+    // 中间图像的位置展开为一维数组
+    let size = (count - 1) * n;
+    let mut velocity = vec![[0.0; 3]; size];
+    let (mut dt, mut alpha, mut steps_since_negative): (f64, f64, usize) = (0.1, 0.1, 0);
+    for _ in 0..settings.idpp_steps {
+        let mut forces = Vec::with_capacity(size);
+        for k in 1..count {
+            let force = idpp_forces(&images[k].0, &positions[k], &targets[k]);
+            let forward: Vec<Vec3> = (0..n).map(|i| sub(positions[k + 1][i], positions[k][i])).collect();
+            let backward: Vec<Vec3> = (0..n).map(|i| sub(positions[k][i], positions[k - 1][i])).collect();
+            let tangent_norm = (0..n).map(|i| {
+                let t = add(forward[i], backward[i]);
+                dot(t, t)
+            }).sum::<f64>().sqrt().max(1e-12);
+            let tangent: Vec<Vec3> = (0..n).map(|i| scale(add(forward[i], backward[i]), 1.0 / tangent_norm)).collect();
+
+            let parallel: f64 = (0..n).map(|i| dot(force[i], tangent[i])).sum();
+            let length = |v: &[Vec3]| v.iter().map(|x| dot(*x, *x)).sum::<f64>().sqrt();
+            let spring = settings.spring * (length(&forward) - length(&backward));
+            for i in 0..n {
+                forces.push(add(sub(force[i], scale(tangent[i], parallel)), scale(tangent[i], spring)));
+            }
+        }
+
+        let fmax = forces.iter().map(|f| norm(*f)).fold(0.0, f64::max);
+        if fmax < settings.idpp_fmax {
+            break;
+        }
+
+        // FIRE
+        let power: f64 = forces.iter().zip(&velocity).map(|(f, v)| dot(*f, *v)).sum();
+        if power > 0.0 {
+            let v_norm = velocity.iter().map(|v| dot(*v, *v)).sum::<f64>().sqrt();
+            let f_norm = forces.iter().map(|f| dot(*f, *f)).sum::<f64>().sqrt();
+            for (v, f) in velocity.iter_mut().zip(&forces) {
+                *v = add(scale(*v, 1.0 - alpha), scale(*f, alpha * v_norm / f_norm));
+            }
+            steps_since_negative += 1;
+            if steps_since_negative > 5 {
+                dt = (dt * 1.1).min(1.0);
+                alpha *= 0.99;
+            }
+        } else {
+            velocity.iter_mut().for_each(|v| *v = [0.0; 3]);
+            dt *= 0.5;
+            alpha = 0.1;
+            steps_since_negative = 0;
+        }
+        for (v, f) in velocity.iter_mut().zip(&forces) {
+            *v = add(*v, scale(*f, dt));
+        }
+        // 单步位移不超过 0.2 Å
+        let step_max = velocity.iter().map(|v| norm(*v) * dt).fold(0.0, f64::max);
+        let limit = if step_max > 0.2 { 0.2 / step_max } else { 1.0 };
+        for k in 1..count {
+            for i in 0..n {
+                let v = velocity[(k - 1) * n + i];
+                positions[k][i] = add(positions[k][i], scale(v, dt * limit));
+            }
+        }
+    }
+
+    for (image, cart) in images.iter_mut().zip(&positions).take(count).skip(1) {
+        let lattice = image.0;
+        for (site, x) in image.1.iter_mut().zip(cart) {
+            site.frac = cart_to_frac(&lattice, *x);
+        }
+    }
+}
+
+/// 图像中各原子的笛卡尔坐标
+fn cartesian(image: &Image) -> Vec<Vec3> {
+    image.1.iter().map(|site| frac_to_cart(&image.0, site.frac)).collect()
+}
@@ -0,0 +1,386 @@
+use serde::{Deserialize, Serialize};
+use crate::parsers::atom_config::{CrystalStructure, Site};
+use crate::utils::geometry::{frac_to_cart, wrap_frac};
+use crate::utils::linalg::{cross, det, dot, inverse, norm, scale, sub, vec_mat, Mat3, Vec3};
+
+/// 表面模型的构建参数
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct SlabSettings {
+    pub miller: [i32; 3],     // 表面的 Miller 指数 (hkl)
+    pub layers: usize,        // 沿法向堆叠的 (hkl) 重复单元数
+    pub vacuum: f64,          // 真空层厚度（Å）
+    pub termination: usize,   // 顶部终止面序号，见 enumerate_terminations
+    pub layer_tolerance: f64, // 判断原子处于同一原子层的高度容差（Å）
+}
+
+impl Default for SlabSettings {
+    fn default() -> Self {
+        SlabSettings {
+            miller: [0, 0, 1],
+            layers: 3,
+            vacuum: 15.0,
+            termination: 0,
+            layer_tolerance: 0.1,
+        }
+    }
+}
+
+/// 表面终止面
+#[derive(Serialize, Clone, Debug)]
+pub struct SlabTermination {
+    pub index: usize,        // 作为 SlabSettings::termination 使用的序号
+    pub composition: String, // 顶层原子层的组成，如 TiO2
+    pub height: f64,         // 该原子层在一个重复单元内的高度（Å）
+}
+
+/// 由 (hkl) 取向的重复单元：整数变换矩阵、变换后的位点与面间距
+struct OrientedCell {
+    lattice: Mat3,
+    sites: Vec<Site>,
+    spacing: f64,
+}
+
+/// 构建表面模型，返回晶格与位点
+/// 晶格 a、b 位于表面内，c 沿表面法向，长度为 layers·d + vacuum，原子层居中
+pub fn build_slab(bulk: &CrystalStructure, settings: &SlabSettings) -> Result<(Mat3, Vec<Site>), String> {
+    if settings.layers == 0 {
+        return Err("Slab needs at least one layer".to_string());
+    }
+    if settings.vacuum < 0.0 {
+        return Err("Vacuum thickness must not be negative".to_string());
+    }
+    let cell = oriented_cell(bulk, settings.miller)?;
+    let planes = atomic_planes(&cell, settings.layer_tolerance);
+    let Some(top) = planes.get(settings.termination) else {
+        return Err(format!(
+            "Termination {} out of range ({} atomic planes)",
+            settings.termination,
+            planes.len()
+        ));
+    };
+
+    // 平移使所选原子层位于重复单元的顶部
+    let tol = settings.layer_tolerance / cell.spacing;
+    let top_z = top.0;
+    let layers = settings.layers as f64;
+    let mut cartesian: Vec<(Site, Vec3)> = Vec::new();
+    for m in 0..settings.layers {
+        for site in &cell.sites {
+            let z = (site.frac[2] - top_z - tol).rem_euclid(1.0) + tol;
+            let frac = [site.frac[0], site.frac[1], (z + m as f64) / layers];
+            let stacked = [cell.lattice[0], cell.lattice[1], scale(cell.lattice[2], layers)];
+            cartesian.push((site.clone(), frac_to_cart(&stacked, frac)));
+        }
+    }
+
+    // 将 c 改为沿法向，并旋转到 a 沿 x、法向沿 z 的标准取向
+    let a1 = cell.lattice[0];
+    let a2 = cell.lattice[1];
+    let normal = scale(cross(a1, a2), 1.0 / norm(cross(a1, a2)));
+    let heights: Vec<f64> = cartesian.iter().map(|(_, p)| dot(*p, normal)).collect();
+    let bottom = heights.iter().cloned().fold(f64::MAX, f64::min);
+    let c_length = layers * cell.spacing + settings.vacuum;
+    let e1 = scale(a1, 1.0 / norm(a1));
+    let e2 = cross(normal, e1);
+    let lattice = [
+        [norm(a1), 0.0, 0.0],
+        [dot(a2, e1), dot(a2, e2), 0.0],
+        [0.0, 0.0, c_length],
+    ];
+    let in_plane = [a1, a2, scale(normal, c_length)];
+    let in_plane_inverse = inverse(&in_plane).ok_or("Degenerate slab cell")?;
+
+    // 原子层整体居中：底部留出 (c - 厚度)/2 的真空
+    let thickness = heights.iter().cloned().fold(f64::MIN, f64::max) - bottom;
+    let offset = (c_length - thickness) / 2.0 - bottom;
+    let sites = cartesian
+        .into_iter()
+        .map(|(mut site, position)| {
+            let frac = vec_mat(position, &in_plane_inverse);
+            let z = (dot(position, normal) + offset) / c_length;
+            let wrapped = wrap_frac(frac);
+            site.frac = [wrapped[0], wrapped[1], z];
+            site.frac_esd = [0.0; 3];
+            site
+        })
+        .collect();
+
+    Ok((lattice, sites))
+}
+
+/// 列出 (hkl) 方向上不等价的表面终止面
+/// 以自顶向下各原子层的组成序列判断等价性
+pub fn enumerate_terminations(
+    bulk: &CrystalStructure,
+    miller: [i32; 3],
+    layer_tolerance: f64,
+) -> Result<Vec<SlabTermination>, String> {
+    let cell = oriented_cell(bulk, miller)?;
+    let planes = atomic_planes(&cell, layer_tolerance);
+    let mut order: Vec<&str> = Vec::new();
+    for site in &cell.sites {
+        if !order.contains(&site.element.as_str()) {
+            order.push(&site.element);
+        }
+    }
+    let compositions: Vec<String> = planes
+        .iter()
+        .map(|(_, members)| {
+            let elements: Vec<&str> = members.iter().map(|&k| cell.sites[k].element.as_str()).collect();
+            composition(&elements, &order)
+        })
+        .collect();
+
+    let mut sequences: Vec<Vec<String>> = Vec::new();
+    let mut terminations = Vec::new();
+    for (index, (z, _)) in planes.iter().enumerate() {
+        // 从该层开始向下的组成序列
+        let sequence: Vec<String> = (0..planes.len())
+            .map(|k| compositions[(index + planes.len() - k) % planes.len()].clone())
+            .collect();
+        if sequences.contains(&sequence) {
+            continue;
+        }
+        sequences.push(sequence);
+        terminations.push(SlabTermination {
+            index,
+            composition: compositions[index].clone(),
+            height: z * cell.spacing,
+        });
+    }
+    Ok(terminations)
+}
+
+/// 构造以 (hkl) 为底面的重复单元
+/// 算法与 ASE ase.build.surface 相同：c1、c2 张成 (hkl) 面，c3 为面外的最短整数平移，变换矩阵幺模
+fn oriented_cell(bulk: &CrystalStructure, miller: [i32; 3]) -> Result<OrientedCell, String> {
+    if miller == [0, 0, 0] {
+        return Err("Miller indices must not all be zero".to_string());
+    }
+    if bulk.sites.is_empty() {
+        return Err("Structure has no atoms".to_string());
+    }
+    let divisor = gcd(gcd(miller[0] as i64, miller[1] as i64), miller[2] as i64);
+    let [h, k, l] = miller.map(|v| v as i64 / divisor);
+    let lattice = &bulk.lattice;
+    let [a1, a2, a3] = *lattice;
+
+    let (c1, c2, c3): ([i64; 3], [i64; 3], [i64; 3]) = match (h == 0, k == 0, l == 0) {
+        (false, true, true) => ([0, 1, 0], [0, 0, 1], [1, 0, 0]),
+        (true, false, true) => ([0, 0, 1], [1, 0, 0], [0, 1, 0]),
+        (true, true, false) => ([1, 0, 0], [0, 1, 0], [0, 0, 1]),
+        _ => {
+            let (mut p, mut q) = ext_gcd(k, l);
+            // 选取使 c1、c2 最接近正交的解
+            let ka = |x: i64, v: Vec3| scale(v, x as f64);
+            let k_a1_h_a2 = sub(ka(k, a1), ka(h, a2));
+            let l_a1_h_a3 = sub(ka(l, a1), ka(h, a3));
+            let l_a2_k_a3 = sub(ka(l, a2), ka(k, a3));
+            let k1 = dot(
+                [0, 1, 2].map(|n| p as f64 * k_a1_h_a2[n] + q as f64 * l_a1_h_a3[n]),
+                l_a2_k_a3,
+            );
+            let k2 = dot(
+                [0, 1, 2].map(|n| l as f64 * k_a1_h_a2[n] - k as f64 * l_a1_h_a3[n]),
+                l_a2_k_a3,
+            );
+            if k2.abs() > 1e-10 {
+                let i = -(k1 / k2).round() as i64;
+                p += i * l;
+                q -= i * k;
+            }
+            let (a, b) = ext_gcd(p * k + q * l, h);
+            let g = gcd(l, k).abs();
+            (
+                [p * k + q * l, -p * h, -q * h],
+                [0, l / g, -k / g],
+                [b, a * p, a * q],
+            )
+        }
+    };
+
+    let mut transform: Mat3 = [c1, c2, c3].map(|row| row.map(|v| v as f64));
+    if det(&transform) < 0.0 {
+        transform[0] = scale(transform[0], -1.0);
+    }
+    let inverse_transform = inverse(&transform).ok_or("Failed to construct surface cell")?;
+    let new_lattice = [
+        vec_mat(transform[0], lattice),
+        vec_mat(transform[1], lattice),
+        vec_mat(transform[2], lattice),
+    ];
+
+    let normal = cross(new_lattice[0], new_lattice[1]);
+    let spacing = dot(new_lattice[2], normal).abs() / norm(normal);
+    let sites = bulk
+        .sites
+        .iter()
+        .map(|site| {
+            let mut site = site.clone();
+            site.frac = wrap_frac(vec_mat(site.frac, &inverse_transform));
+            site
+        })
+        .collect();
+
+    Ok(OrientedCell {
+        lattice: new_lattice,
+        sites,
+        spacing,
+    })
+}
+
+/// 将原子按沿法向的高度分组为原子层，按高度升序返回 (分数高度, 位点序号)
+fn atomic_planes(cell: &OrientedCell, layer_tolerance: f64) -> Vec<(f64, Vec<usize>)> {
+    let tol = layer_tolerance / cell.spacing;
+    let mut order: Vec<usize> = (0..cell.sites.len()).collect();
+    order.sort_by(|&a, &b| cell.sites[a].frac[2].partial_cmp(&cell.sites[b].frac[2]).unwrap());
+
+    let mut planes: Vec<(f64, Vec<usize>)> = Vec::new();
+    for index in order {
+        let z = cell.sites[index].frac[2];
+        match planes.last_mut() {
+            Some((z0, members)) if z - *z0 < tol => members.push(index),
+            _ => planes.push((z, vec![index])),
+        }
+    }
+    // 首尾两层跨越周期边界时合并
+    if planes.len() > 1 {
+        let first = planes[0].0;
+        let last = planes.last().unwrap().0;
+        if first + 1.0 - last < tol {
+            let (_, members) = planes.pop().unwrap();
+            planes[0].1.extend(members);
+        }
+    }
+    planes
+}
+
+/// 由元素列表生成化学式，元素按 order 中的顺序排列，如 ["O", "Ti", "O"] -> TiO2
+fn composition(elements: &[&str], order: &[&str]) -> String {
+    order
+        .iter()
+        .filter_map(|element| {
+            match elements.iter().filter(|e| *e == element).count() {
+                0 => None,
+                1 => Some(element.to_string()),
+                count => Some(format!("{}{}", element, count)),
+            }
+        })
+        .collect()
+}
+
+/// 最大公约数
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// 扩展欧几里得算法：返回 (x, y) 使 a·x + b·y = gcd(a, b)
+/// 取模与整除采用向下取整，与 ASE 的 Python 实现保持一致
+fn ext_gcd(a: i64, b: i64) -> (i64, i64) {
+    if b == 0 {
+        (1, 0)
+    } else if a % b == 0 {
+        (0, 1)
+    } else {
+        let (x, y) = ext_gcd(b, a - b * floor_div(a, b));
+        (y, x - y * floor_div(a, b))
+    }
+}
+
+/// 向下取整的整数除法
+fn floor_div(a: i64, b: i64) -> i64 {
+    let q = a / b;
+    if (a % b != 0) && ((a < 0) != (b < 0)) {
+        q - 1
+    } else {
+        q
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use super::*;
+    use crate::parsers::parser_utils::build_crystal_structure;
+    use crate::utils::geometry::lattice_from_parameters;
+
+    const A: f64 = 5.64;
+
+    /// NaCl 惯用晶胞
+    fn rock_salt() -> CrystalStructure {
+        let mut sites = Vec::new();
+        for [x, y, z] in [[0.0, 0.0, 0.0], [0.0, 0.5, 0.5], [0.5, 0.0, 0.5], [0.5, 0.5, 0.0]] {
+            sites.push(Site::new(format!("Na{}", sites.len() + 1), "Na".to_string(), [x, y, z]));
+            sites.push(Site::new(format!("Cl{}", sites.len() + 1), "Cl".to_string(), [x + 0.5, y, z]));
+        }
+        build_crystal_structure(lattice_from_parameters(A, A, A, 90.0, 90.0, 90.0), sites, &HashMap::new())
+    }
+
+    #[test]
+    fn rock_salt_001() {
+        let bulk = rock_salt();
+        let terminations = enumerate_terminations(&bulk, [0, 0, 1], 0.1).unwrap();
+        assert_eq!(terminations.len(), 1);
+        assert_eq!(terminations[0].composition, "Na2Cl2");
+
+        let settings = SlabSettings { layers: 2, vacuum: 10.0, ..SlabSettings::default() };
+        let (lattice, sites) = build_slab(&bulk, &settings).unwrap();
+        assert_eq!(sites.len(), 16);
+        assert!((lattice[0][0] - A).abs() < 1e-9);
+        assert!((lattice[2][2] - (2.0 * A + 10.0)).abs() < 1e-9);
+        // 四个原子层，间距 a/2，整体居中
+        let mut heights: Vec<f64> = sites.iter().map(|site| site.frac[2] * lattice[2][2]).collect();
+        heights.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let thickness = 1.5 * A;
+        assert!((heights[0] - (lattice[2][2] - thickness) / 2.0).abs() < 1e-9);
+        assert!((heights[15] - heights[0] - thickness).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rock_salt_111_terminations() {
+        let bulk = rock_salt();
+        let terminations = enumerate_terminations(&bulk, [1, 1, 1], 0.1).unwrap();
+        let mut compositions: Vec<&str> = terminations.iter().map(|t| t.composition.as_str()).collect();
+        compositions.sort_unstable();
+        // 惯用晶胞切出的 (111) 重复单元每层含 4 个原子
+        assert_eq!(compositions, ["Cl4", "Na4"]);
+
+        // 顶层为所选终止面的元素
+        for termination in &terminations {
+            let settings = SlabSettings { miller: [1, 1, 1], termination: termination.index, ..SlabSettings::default() };
+            let (lattice, sites) = build_slab(&bulk, &settings).unwrap();
+            let top = sites.iter().max_by(|a, b| a.frac[2].partial_cmp(&b.frac[2]).unwrap()).unwrap();
+            assert!(termination.composition.starts_with(&top.element));
+            // 表面内为边长 √2·a、夹角 60° 或 120° 的六角网格
+            let side = A * 2f64.sqrt();
+            assert!((norm(lattice[0]) - side).abs() < 1e-9);
+            assert!((norm(lattice[1]) - side).abs() < 1e-9);
+            assert!((dot(lattice[0], lattice[1]).abs() - side * side / 2.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn invalid_settings() {
+        let bulk = rock_salt();
+        assert!(build_slab(&bulk, &SlabSettings { layers: 0, ..SlabSettings::default() }).is_err());
+        assert!(build_slab(&bulk, &SlabSettings { miller: [0, 0, 0], ..SlabSettings::default() }).is_err());
+        assert!(build_slab(&bulk, &SlabSettings { termination: 5, ..SlabSettings::default() }).is_err());
+    }
+
+    #[test]
+    fn integer_helpers() {
+        assert_eq!(composition(&["O", "Ti", "O"], &["Ti", "O"]), "TiO2");
+        for (a, b) in [(3, 5), (-4, 6), (7, -3), (0, 5)] {
+            let (x, y) = ext_gcd(a, b);
+            // 与 ASE 相同，b 为负时结果可能差一个符号
+            assert_eq!((a * x + b * y).abs(), gcd(a, b), "ext_gcd({}, {})", a, b);
+        }
+        assert_eq!(floor_div(-7, 2), -4);
+        assert_eq!(floor_div(7, 2), 3);
+    }
+}
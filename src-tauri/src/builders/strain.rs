@@ -0,0 +1,87 @@
+use serde::Deserialize;
+use crate::utils::linalg::{add, identity, mat_mul, Mat3};
+
+/// 应变参数，应变张量定义在笛卡尔坐标系中
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct StrainSettings {
+    pub mode: String,     // uniaxial / biaxial / shear / hydrostatic / tensor
+    pub magnitude: f64,   // 应变量，0.01 表示 1%；剪切为工程剪应变 γ
+    pub direction: usize, // uniaxial：拉伸方向；biaxial：平面法向；shear：0 = yz, 1 = xz, 2 = xy
+    pub tensor: Mat3,     // mode 为 tensor 时使用的应变张量，按对称部分计算
+}
+
+impl Default for StrainSettings {
+    fn default() -> Self {
+        StrainSettings {
+            mode: "uniaxial".to_string(),
+            magnitude: 0.01,
+            direction: 2,
+            tensor: [[0.0; 3]; 3],
+        }
+    }
+}
+
+/// 由应变参数构造对称的应变张量 ε
+pub fn strain_tensor(settings: &StrainSettings) -> Result<Mat3, String> {
+    let d = settings.direction;
+    if d > 2 {
+        return Err(format!("Invalid strain direction {}", d));
+    }
+    let e = settings.magnitude;
+    let mut strain = [[0.0; 3]; 3];
+    match settings.mode.as_str() {
+        "uniaxial" => strain[d][d] = e,
+        "biaxial" => {
+            for i in (0..3).filter(|&i| i != d) {
+                strain[i][i] = e;
+            }
+        }
+        "shear" => {
+            // 剪切平面由另外两个方向组成，张量分量为工程剪应变的一半
+            let (i, j) = [(1, 2), (0, 2), (0, 1)][d];
+            strain[i][j] = e / 2.0;
+            strain[j][i] = e / 2.0;
+        }
+        "hydrostatic" => {
+            for (i, row) in strain.iter_mut().enumerate() {
+                row[i] = e;
+            }
+        }
+        "tensor" => {
+            let t = &settings.tensor;
+            strain = [0, 1, 2].map(|i| [0, 1, 2].map(|j| (t[i][j] + t[j][i]) / 2.0));
+        }
+        mode => return Err(format!("Unknown strain mode {}", mode)),
+    }
+    Ok(strain)
+}
+
+/// 对晶格施加应变：形变梯度 F = I + ε，新晶格的基矢量 a' = F·a
+/// 原子保持分数坐标不变
+pub fn apply_strain(lattice: &Mat3, strain: &Mat3) -> Mat3 {
+    let identity = identity();
+    let deformation = [0, 1, 2].map(|i| add(identity[i], strain[i]));
+    // 晶格按行存放基矢量，a' = F·a 即 L' = L·Fᵀ，F 对称
+    mat_mul(lattice, &deformation)
+}
+
+/// 拟合弹性常数所需的应变组：六个 Voigt 分量分别取各个应变量
+/// 返回 (名称, 应变张量)，名称形如 e4_+0.50%；剪切分量按工程剪应变计
+pub fn elastic_strain_set(magnitudes: &[f64]) -> Vec<(String, Mat3)> {
+    const VOIGT: [(usize, usize); 6] = [(0, 0), (1, 1), (2, 2), (1, 2), (0, 2), (0, 1)];
+    let mut strains = Vec::new();
+    for (component, &(i, j)) in VOIGT.iter().enumerate() {
+        for &magnitude in magnitudes {
+            let mut strain = [[0.0; 3]; 3];
+            if i == j {
+                strain[i][i] = magnitude;
+            } else {
+                strain[i][j] = magnitude / 2.0;
+                strain[j][i] = magnitude / 2.0;
+            }
+            strains.push((format!("e{}_{:+.2}%", component + 1, magnitude * 100.0), strain));
+        }
+    }
+    strains
+}
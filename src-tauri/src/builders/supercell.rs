@@ -0,0 +1,32 @@
+use crate::parsers::atom_config::Site;
+use crate::utils::linalg::{scale, Mat3};
+
+/// 沿 a、b、c 分别扩展 na、nb、nc 倍构建超胞
+/// 位点保持原有标签，按晶胞平移的顺序依次排列
+pub fn make_supercell(lattice: &Mat3, sites: &[Site], repeats: [usize; 3]) -> (Mat3, Vec<Site>) {
+    let [na, nb, nc] = repeats.map(|n| n.max(1));
+    let supercell = [
+        scale(lattice[0], na as f64),
+        scale(lattice[1], nb as f64),
+        scale(lattice[2], nc as f64),
+    ];
+
+    let mut new_sites = Vec::with_capacity(sites.len() * na * nb * nc);
+    for i in 0..na {
+        for j in 0..nb {
+            for k in 0..nc {
+                for site in sites {
+                    let mut site = site.clone();
+                    site.frac = [
+                        (site.frac[0] + i as f64) / na as f64,
+                        (site.frac[1] + j as f64) / nb as f64,
+                        (site.frac[2] + k as f64) / nc as f64,
+                    ];
+                    site.frac_esd = [0.0; 3];
+                    new_sites.push(site);
+                }
+            }
+        }
+    }
+    (supercell, new_sites)
+}
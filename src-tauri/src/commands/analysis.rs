@@ -0,0 +1,137 @@
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_dialog::{DialogExt, FilePath};
+use crate::analysis::bond_valence::{self, BvsResult, BvsSettings};
+use crate::analysis::diffusion::{self, DiffusionResult, DiffusionSettings};
+use crate::analysis::measure::{self, BondEntry, Measurement};
+use crate::analysis::polyhedra::{self, PolyhedraSettings, Polyhedron};
+use crate::analysis::pores::{self, PoreResult, PoreSettings};
+use crate::analysis::rdf::{self, RdfResult, RdfSettings};
+use crate::analysis::structure_info::{self, StructureInfo};
+use crate::analysis::structure_matcher::{self, MatchResult, MatchSettings};
+use crate::file_dialog::read_structure_file;
+use crate::state::AppState;
+use crate::utils::elements::element;
+
+/// 计算当前结构或轨迹（各帧平均）的径向分布函数
+/// 轨迹文件的帧逐帧读取，不保存完整的结构数据
+#[tauri::command]
+pub fn compute_rdf(state: State<'_, AppState>, settings: RdfSettings) -> Result<RdfResult, String> {
+    let source = state.frame_source()?;
+    rdf::compute_rdf(source.len(), |index| source.read_frame(index), &settings)
+}
+
+/// 计算当前轨迹各元素的均方位移、扩散系数与速度自相关函数
+/// 轨迹文件的帧逐帧读取，不保存完整的结构数据
+#[tauri::command]
+pub fn compute_diffusion(state: State<'_, AppState>, settings: DiffusionSettings) -> Result<DiffusionResult, String> {
+    let source = state.frame_source()?;
+    diffusion::diffusion_analysis(source.len(), |index| source.read_frame(index), &settings)
+}
+
+/// 测量两原子间的最小镜像距离
+#[tauri::command]
+pub fn measure_distance(state: State<'_, AppState>, i: usize, j: usize) -> Result<Measurement, String> {
+    measure::measure_distance(&state.current_structure()?, i, j)
+}
+
+/// 测量键角 i-j-k（j 为顶点）
+#[tauri::command]
+pub fn measure_angle(
+    state: State<'_, AppState>,
+    i: usize,
+    j: usize,
+    k: usize,
+) -> Result<Measurement, String> {
+    measure::measure_angle(&state.current_structure()?, i, j, k)
+}
+
+/// 测量二面角 i-j-k-l
+#[tauri::command]
+pub fn measure_dihedral(
+    state: State<'_, AppState>,
+    i: usize,
+    j: usize,
+    k: usize,
+    l: usize,
+) -> Result<Measurement, String> {
+    measure::measure_dihedral(&state.current_structure()?, i, j, k, l)
+}
+
+/// 列出原子 i 周围 r_max 以内的全部距离
+#[tauri::command]
+pub fn list_distances(state: State<'_, AppState>, i: usize, r_max: f64) -> Result<Vec<BondEntry>, String> {
+    measure::distance_table(&state.current_structure()?, i, r_max)
+}
+
+/// 计算当前结构中各阳离子的配位多面体
+#[tauri::command]
+pub fn compute_polyhedra(
+    state: State<'_, AppState>,
+    settings: PolyhedraSettings,
+) -> Result<Vec<Polyhedron>, String> {
+    Ok(polyhedra::compute_polyhedra(&state.current_structure()?, &settings))
+}
+
+/// 计算当前结构的键价和与全局不稳定指数
+#[tauri::command]
+pub fn compute_bond_valence(
+    state: State<'_, AppState>,
+    settings: BvsSettings,
+) -> Result<BvsResult, String> {
+    Ok(bond_valence::compute_bond_valence_sums(&state.current_structure()?, &settings))
+}
+
+/// 将当前结构与另一个文件中的结构比对，未给出文件时弹出选择对话框
+/// 轨迹文件取最后一帧
+#[tauri::command]
+pub async fn compare_with_file(
+    app_handle: AppHandle,
+    file_path: Option<FilePath>,
+    settings: MatchSettings,
+) -> Result<Option<MatchResult>, String> {
+    let current = app_handle.state::<AppState>().current_structure()?;
+    let file_path = match file_path {
+        Some(file_path) => file_path,
+        None => match app_handle.dialog().file().blocking_pick_file() {
+            Some(file_path) => file_path,
+            None => return Ok(None),
+        },
+    };
+    let FilePath::Path(path) = file_path else {
+        return Err("Invalid file path".to_string());
+    };
+
+    let other = read_structure_file(&app_handle, &path)?;
+    Ok(Some(structure_matcher::match_structures(&current, &other, &settings)))
+}
+
+/// 当前结构的 Voronoi 剖分与孔隙分析，未给出半径的元素使用范德华半径
+#[tauri::command]
+pub fn compute_pores(state: State<'_, AppState>, mut settings: PoreSettings) -> Result<PoreResult, String> {
+    let structure = state.current_structure()?;
+    for site in &structure.sites {
+        if !settings.radii.contains_key(&site.element) {
+            let radius = element(&site.element)
+                .and_then(|e| e.vdw_radius.or(e.covalent_radius))
+                .ok_or_else(|| format!("No radius available for {}", site.element))?;
+            settings.radii.insert(site.element.clone(), radius);
+        }
+    }
+    pores::analyze_pores(&structure, &settings)
+}
+
+/// 当前结构的化学式、密度与堆积分数
+/// 堆积分数使用元素数据库中的原子半径（缺少时用共价半径），与显示半径和用户样式无关
+#[tauri::command]
+pub fn get_structure_info(state: State<'_, AppState>) -> Result<StructureInfo, String> {
+    let structure = state.current_structure()?;
+    let radii = structure
+        .sites
+        .iter()
+        .filter_map(|site| {
+            let radius = element(&site.element).and_then(|e| e.atomic_radius.or(e.covalent_radius))?;
+            Some((site.element.clone(), radius))
+        })
+        .collect();
+    structure_info::structure_info(&structure, &radii)
+}
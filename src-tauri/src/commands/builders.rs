@@ -0,0 +1,134 @@
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_dialog::{DialogExt, FilePath};
+use crate::builders::defects::{self, DefectSettings};
+use crate::builders::neb::{self, NebSettings};
+use crate::builders::slab::{self, SlabSettings, SlabTermination};
+use crate::builders::strain::{self, StrainSettings};
+use crate::parsers::atom_config::CrystalStructure;
+use crate::parsers::parser_utils::{build_crystal_structure, load_atom_config};
+use crate::commands::display::send_structure;
+use crate::file_dialog::read_structure_file;
+use crate::state::AppState;
+
+/// 生成的结构及其说明，供前端列出、显示与导出
+#[derive(Serialize, Clone, Debug)]
+pub struct GeneratedStructure {
+    pub name: String,
+    pub kind: String,
+    pub multiplicity: usize,
+    pub position: Option<[f64; 3]>, // 缺陷在超胞中的分数坐标，其他生成结构为 None
+    pub structure: CrystalStructure,
+}
+
+/// 由当前结构构建 (hkl) 表面模型
+#[tauri::command]
+pub fn build_slab(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    settings: SlabSettings,
+) -> Result<CrystalStructure, String> {
+    let (lattice, sites) = slab::build_slab(&state.current_structure()?, &settings)?;
+    Ok(build_crystal_structure(lattice, sites, &load_atom_config(&app_handle)))
+}
+
+/// 列出当前结构在 (hkl) 方向上不等价的表面终止面
+#[tauri::command]
+pub fn list_slab_terminations(
+    state: State<'_, AppState>,
+    miller: [i32; 3],
+    layer_tolerance: f64,
+) -> Result<Vec<SlabTermination>, String> {
+    slab::enumerate_terminations(&state.current_structure()?, miller, layer_tolerance)
+}
+
+/// 在当前结构的超胞中生成不等价的空位、替位与间隙缺陷
+#[tauri::command]
+pub fn generate_defects(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    settings: DefectSettings,
+) -> Result<Vec<GeneratedStructure>, String> {
+    let structure = state.current_structure()?;
+    let atom_config = load_atom_config(&app_handle);
+    Ok(defects::generate_defects(&structure, &settings)
+        .into_iter()
+        .map(|defect| GeneratedStructure {
+            name: defect.name,
+            kind: defect.kind.to_string(),
+            multiplicity: defect.multiplicity,
+            position: Some(defect.position),
+            structure: build_crystal_structure(defect.lattice, defect.sites, &atom_config),
+        })
+        .collect())
+}
+
+/// 对当前结构施加应变，原子分数坐标保持不变
+#[tauri::command]
+pub fn apply_strain(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    settings: StrainSettings,
+) -> Result<CrystalStructure, String> {
+    let structure = state.current_structure()?;
+    let lattice = strain::apply_strain(&structure.lattice, &strain::strain_tensor(&settings)?);
+    Ok(build_crystal_structure(lattice, structure.sites, &load_atom_config(&app_handle)))
+}
+
+/// 生成拟合弹性常数用的应变结构组，magnitudes 为各分量的应变量
+#[tauri::command]
+pub fn generate_elastic_strains(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    magnitudes: Vec<f64>,
+) -> Result<Vec<GeneratedStructure>, String> {
+    let structure = state.current_structure()?;
+    let atom_config = load_atom_config(&app_handle);
+    Ok(strain::elastic_strain_set(&magnitudes)
+        .into_iter()
+        .map(|(name, tensor)| GeneratedStructure {
+            name,
+            kind: "strain".to_string(),
+            multiplicity: 1,
+            position: None,
+            structure: build_crystal_structure(
+                strain::apply_strain(&structure.lattice, &tensor),
+                structure.sites.clone(),
+                &atom_config,
+            ),
+        })
+        .collect())
+}
+
+/// 以当前结构为初态、另一个文件中的结构为末态生成 NEB 图像链，未给出文件时弹出选择对话框
+/// 图像链作为轨迹载入视图，返回包含首末结构在内的全部图像
+#[tauri::command]
+pub async fn interpolate_images(
+    app_handle: AppHandle,
+    file_path: Option<FilePath>,
+    settings: NebSettings,
+) -> Result<Option<Vec<CrystalStructure>>, String> {
+    let state = app_handle.state::<AppState>();
+    let initial = state.current_structure()?;
+    let file_path = match file_path {
+        Some(file_path) => file_path,
+        None => match app_handle.dialog().file().blocking_pick_file() {
+            Some(file_path) => file_path,
+            None => return Ok(None),
+        },
+    };
+    let FilePath::Path(path) = file_path else {
+        return Err("Invalid file path".to_string());
+    };
+
+    let last = read_structure_file(&app_handle, &path)?;
+    let atom_config = load_atom_config(&app_handle);
+    let images: Vec<CrystalStructure> = neb::interpolate_images(&initial, &last, &settings)?
+        .into_iter()
+        .map(|(lattice, sites)| build_crystal_structure(lattice, sites, &atom_config))
+        .collect();
+
+    state.set_trajectory(images.clone());
+    send_structure(&app_handle, &state.current_structure()?)?;
+    Ok(Some(images))
+}
@@ -0,0 +1,14 @@
+use tauri::State;
+use crate::analysis::neutron::{neutron_powder_pattern, NeutronSettings};
+use crate::analysis::powder::PowderPattern;
+use crate::state::AppState;
+
+/// 计算当前结构的中子粉末衍射谱
+#[tauri::command]
+pub fn compute_neutron_pattern(
+    state: State<'_, AppState>,
+    settings: NeutronSettings,
+) -> Result<PowderPattern, String> {
+    let structure = state.current_structure()?;
+    neutron_powder_pattern(&structure, &settings)
+}
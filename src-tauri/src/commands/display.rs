@@ -0,0 +1,298 @@
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+use crate::analysis::bond_valence::{compute_bond_valence_sums, BvsSettings};
+use crate::analysis::neighbors::coordination_numbers;
+use crate::parsers::atom_config::CrystalStructure;
+use crate::parsers::parser_utils::{load_atom_config, restyle_structure, save_user_styles};
+use crate::state::{AppState, DisplaySettings, PropertyColoring};
+use crate::utils::colors::{colormap, palette, parse_hex, COLORMAPS};
+use crate::utils::elements::{element, Element, ELEMENTS, RADIUS_TYPES};
+
+/// 按元素着色的配色方案
+const ELEMENT_SCHEMES: [&str; 3] = ["jmol", "cpk", "vesta"];
+
+/// 晶胞边界的画法
+const BOUNDARY_MODES: [&str; 3] = ["cell", "molecules", "grow"];
+
+/// 元素当前生效的绘制样式
+#[derive(Serialize, Clone, Debug)]
+pub struct AtomStyle {
+    pub element: String,
+    pub radius: f64,
+    pub color: String,
+    pub visible: bool,
+    pub customized: bool, // 是否含有用户修改
+}
+
+/// 获取当前显示设置
+#[tauri::command]
+pub fn get_display_settings(state: State<'_, AppState>) -> DisplaySettings {
+    state.display_settings()
+}
+
+/// 切换原子球使用的半径类型，并按新半径重新绘制当前结构
+#[tauri::command]
+pub fn set_radius_type(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    radius_type: String,
+) -> Result<(), String> {
+    if !RADIUS_TYPES.contains(&radius_type.as_str()) {
+        return Err(format!("Unknown radius type {}", radius_type));
+    }
+    state.display.lock().unwrap().radius_type = radius_type;
+    refresh_styles(&app_handle, &state)
+}
+
+/// 切换原子配色：jmol / cpk / vesta 按元素着色，site 按位点标签着色，wyckoff 按对称等价位点着色
+#[tauri::command]
+pub fn set_color_scheme(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    color_scheme: String,
+) -> Result<(), String> {
+    if !ELEMENT_SCHEMES.contains(&color_scheme.as_str()) && !["site", "wyckoff"].contains(&color_scheme.as_str()) {
+        return Err(format!("Unknown color scheme {}", color_scheme));
+    }
+    state.display.lock().unwrap().color_scheme = color_scheme;
+    refresh_styles(&app_handle, &state)
+}
+
+/// 切换晶胞边界的画法：cell 只画晶胞内的原子并复制边界原子，
+/// molecules 画出几何中心在晶胞内的完整分子，grow 把晶胞内原子所属的分子补全
+/// 骨架与无机晶体等周期性连通的部分始终按 cell 画法
+#[tauri::command]
+pub fn set_boundary_mode(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    boundary_mode: String,
+) -> Result<(), String> {
+    if !BOUNDARY_MODES.contains(&boundary_mode.as_str()) {
+        return Err(format!("Unknown boundary mode {}", boundary_mode));
+    }
+    state.display.lock().unwrap().boundary_mode = boundary_mode;
+    match state.structure.lock().unwrap().clone() {
+        Some(structure) => send_structure(&app_handle, &structure),
+        None => Ok(()),
+    }
+}
+
+/// 按逐原子标量经色图着色
+/// coordination（配位数）与 bond_valence（键价和）可由当前结构直接计算；
+/// 其他属性（电荷、磁矩、受力大小等）需给出与位点一一对应的 values
+/// range 缺省时取数据的最小值与最大值，返回实际使用的着色数据供前端绘制色标
+#[tauri::command]
+pub fn color_by_property(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    property: String,
+    values: Option<Vec<f64>>,
+    colormap: Option<String>,
+    range: Option<[f64; 2]>,
+) -> Result<PropertyColoring, String> {
+    let structure = state.current_structure()?;
+    let values = match (values, property.as_str()) {
+        (Some(values), _) => values,
+        (None, "coordination") => coordination_numbers(&structure.lattice, &structure.sites, 1.2)
+            .into_iter()
+            .map(|n| n as f64)
+            .collect(),
+        (None, "bond_valence") => compute_bond_valence_sums(&structure, &BvsSettings::default())
+            .sites
+            .iter()
+            .map(|site| site.bond_valence_sum)
+            .collect(),
+        (None, _) => return Err(format!("No values given for {}", property)),
+    };
+    if values.len() != structure.sites.len() {
+        return Err(format!(
+            "Expected {} values for {}, got {}",
+            structure.sites.len(),
+            property,
+            values.len()
+        ));
+    }
+    let colormap = colormap.unwrap_or_else(|| "viridis".to_string());
+    if !COLORMAPS.contains(&colormap.as_str()) {
+        return Err(format!("Unknown colormap {}", colormap));
+    }
+    let range = range.unwrap_or_else(|| {
+        let finite = values.iter().copied().filter(|v| v.is_finite());
+        [finite.clone().fold(f64::MAX, f64::min), finite.fold(f64::MIN, f64::max)]
+    });
+
+    let coloring = PropertyColoring { name: property, values, colormap, range };
+    let mut display = state.display.lock().unwrap();
+    display.color_scheme = "property".to_string();
+    display.property = Some(coloring.clone());
+    drop(display);
+    refresh_styles(&app_handle, &state)?;
+    Ok(coloring)
+}
+
+/// 获取全部元素当前生效的半径、颜色与可见性
+#[tauri::command]
+pub fn get_atom_styles(app_handle: AppHandle, state: State<'_, AppState>) -> Vec<AtomStyle> {
+    let atom_config = load_atom_config(&app_handle);
+    let user_styles = state.user_styles();
+    ELEMENTS
+        .iter()
+        .filter_map(|element| {
+            let config = atom_config.get(element.symbol)?;
+            Some(AtomStyle {
+                element: element.symbol.to_string(),
+                radius: config.radius,
+                color: config.color.clone(),
+                visible: config.visible,
+                customized: user_styles.elements.contains_key(element.symbol),
+            })
+        })
+        .collect()
+}
+
+/// 修改一个元素的半径、颜色或可见性，未给出的项保持不变
+/// 修改写入用户配置文件，重启后仍然生效
+#[tauri::command]
+pub fn set_atom_style(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    symbol: String,
+    radius: Option<f64>,
+    color: Option<String>,
+    visible: Option<bool>,
+) -> Result<(), String> {
+    let symbol = element(&symbol)
+        .ok_or_else(|| format!("Unknown element {}", symbol))?
+        .symbol;
+    if radius.is_some_and(|r| !(r > 0.0 && r.is_finite())) {
+        return Err(format!("Invalid radius for {}", symbol));
+    }
+    if let Some(color) = &color {
+        if parse_hex(color).is_none() {
+            return Err(format!("Invalid color {}, expected #RRGGBB", color));
+        }
+    }
+
+    let user_styles = {
+        let mut user_styles = state.user_styles.lock().unwrap();
+        let style = user_styles.elements.entry(symbol.to_string()).or_default();
+        style.radius = radius.or(style.radius);
+        style.color = color.or(style.color.take());
+        style.visible = visible.or(style.visible);
+        user_styles.clone()
+    };
+    save_user_styles(&app_handle, &user_styles)?;
+    refresh_styles(&app_handle, &state)
+}
+
+/// 清除一个元素的用户修改，未给出元素时清除全部修改
+#[tauri::command]
+pub fn reset_atom_styles(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    symbol: Option<String>,
+) -> Result<(), String> {
+    let user_styles = {
+        let mut user_styles = state.user_styles.lock().unwrap();
+        match symbol {
+            Some(symbol) => {
+                user_styles.elements.retain(|key, _| !key.eq_ignore_ascii_case(&symbol));
+            }
+            None => user_styles.elements.clear(),
+        }
+        user_styles.clone()
+    };
+    save_user_styles(&app_handle, &user_styles)?;
+    refresh_styles(&app_handle, &state)
+}
+
+/// 查询元素数据
+#[tauri::command]
+pub fn get_element_data(symbol: String) -> Result<Element, String> {
+    element(&symbol)
+        .cloned()
+        .ok_or_else(|| format!("Unknown element {}", symbol))
+}
+
+/// 按位点解析原子颜色；按元素着色或数据与结构不匹配时返回 None
+/// wyckoff 配色使用状态中缓存的对称等价分组
+pub fn site_colors(state: &AppState, structure: &CrystalStructure, settings: &DisplaySettings) -> Option<Vec<String>> {
+    let sites = &structure.sites;
+    match settings.color_scheme.as_str() {
+        "site" => {
+            let mut labels: Vec<&str> = Vec::new();
+            Some(
+                sites
+                    .iter()
+                    .map(|site| {
+                        let index = labels.iter().position(|l| *l == site.label).unwrap_or_else(|| {
+                            labels.push(&site.label);
+                            labels.len() - 1
+                        });
+                        palette(index).to_string()
+                    })
+                    .collect(),
+            )
+        }
+        "wyckoff" => {
+            let orbits = state.site_orbits(structure);
+            let mut representatives: Vec<usize> = orbits.clone();
+            representatives.sort_unstable();
+            representatives.dedup();
+            Some(
+                orbits
+                    .iter()
+                    .map(|orbit| palette(representatives.binary_search(orbit).unwrap()).to_string())
+                    .collect(),
+            )
+        }
+        "property" => {
+            let coloring = settings.property.as_ref()?;
+            if coloring.values.len() != sites.len() {
+                return None;
+            }
+            let [low, high] = coloring.range;
+            let span = if (high - low).abs() > 1e-12 { high - low } else { 1.0 };
+            coloring
+                .values
+                .iter()
+                .map(|v| colormap(&coloring.colormap, (v - low) / span))
+                .collect()
+        }
+        _ => None,
+    }
+}
+
+/// 按当前配色解析逐位点颜色后将结构发送给视图
+pub fn send_structure(app_handle: &AppHandle, structure: &CrystalStructure) -> Result<(), String> {
+    let state = app_handle.state::<AppState>();
+    let settings = state.display_settings();
+    let colors = site_colors(&state, structure, &settings);
+    if colors.is_none() && settings.boundary_mode == "cell" {
+        return app_handle.emit("cif-data", structure).map_err(|e| e.to_string());
+    }
+    let styled = restyle_structure(structure, &load_atom_config(app_handle), colors.as_deref(), &settings.boundary_mode);
+    app_handle.emit("cif-data", styled).map_err(|e| e.to_string())
+}
+
+/// 显示设置改变后重新生成当前结构与轨迹各帧的绘制数据，并发送给视图
+fn refresh_styles(app_handle: &AppHandle, state: &AppState) -> Result<(), String> {
+    let atom_config = load_atom_config(app_handle);
+    let mut frames = state.frames.lock().unwrap();
+    for frame in frames.iter_mut() {
+        *frame = restyle_structure(frame, &atom_config, None, "cell");
+    }
+    drop(frames);
+
+    let current = {
+        let mut structure = state.structure.lock().unwrap();
+        match structure.as_mut() {
+            Some(current) => {
+                *current = restyle_structure(current, &atom_config, None, "cell");
+                current.clone()
+            }
+            None => return Ok(()),
+        }
+    };
+    send_structure(app_handle, &current)
+}
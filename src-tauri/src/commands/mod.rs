@@ -0,0 +1,5 @@
+pub mod diffraction;
+pub mod analysis;
+pub mod builders;
+pub mod display;
+pub mod volumetric;
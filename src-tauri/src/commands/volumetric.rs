@@ -0,0 +1,175 @@
+use tauri::ipc::Response;
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_dialog::{DialogExt, FilePath};
+use crate::analysis::bader::{self, BaderResult, BaderSettings};
+use crate::analysis::isosurface::{encode_meshes, extract_isosurface, IsosurfaceSettings};
+use crate::analysis::slices::{self, Profile, SliceResult, SliceSettings};
+use crate::analysis::volumetric::{self, VolumetricOperation};
+use crate::commands::display::send_structure;
+use crate::file_dialog::read_structure_file;
+use crate::parsers::atom_config::VolumetricData;
+use crate::state::AppState;
+
+/// 提取当前结构格点数据的等值面，以二进制返回（格式见 encode_meshes）
+/// both_signs 为真时在 level 的网格之后追加 -level 的网格（level 为 0 时两者相同，不追加）
+#[tauri::command]
+pub fn compute_isosurface(state: State<'_, AppState>, settings: IsosurfaceSettings) -> Result<Response, String> {
+    let data = volumetric_data(&state, settings.data_index)?;
+    let mut meshes = vec![extract_isosurface(&data, settings.level, settings.step)];
+    if settings.both_signs && settings.level != 0.0 {
+        meshes.push(extract_isosurface(&data, -settings.level, settings.step));
+    }
+    Ok(Response::new(encode_meshes(&meshes)))
+}
+
+/// 沿晶面或三原子平面切取二维标量图
+#[tauri::command]
+pub fn compute_slice(state: State<'_, AppState>, settings: SliceSettings) -> Result<SliceResult, String> {
+    let structure = state.current_structure()?;
+    let data = volumetric_data(&state, settings.data_index)?;
+    slices::slice_plane(&structure, &data, &settings)
+}
+
+/// 两点（分数坐标）之间的线剖面
+#[tauri::command]
+pub fn compute_line_profile(
+    state: State<'_, AppState>,
+    data_index: usize,
+    start: [f64; 3],
+    end: [f64; 3],
+    points: usize,
+) -> Result<Profile, String> {
+    let data = volumetric_data(&state, data_index)?;
+    slices::line_profile(&data, start, end, points)
+}
+
+/// 沿晶轴 axis（0 = a, 1 = b, 2 = c）的平面平均
+#[tauri::command]
+pub fn compute_planar_average(state: State<'_, AppState>, data_index: usize, axis: usize) -> Result<Profile, String> {
+    let data = volumetric_data(&state, data_index)?;
+    slices::planar_average(&data, axis)
+}
+
+/// 与另一个文件中的格点数据相加或相减，如差分电荷密度 Δρ = ρ(AB) − ρ(A) − ρ(B)
+/// 未给出文件时弹出选择对话框；格点数不同时按当前数据的格点重采样
+/// 结果追加到当前结构的格点数据末尾，返回全部格点数据的信息
+#[tauri::command]
+pub async fn combine_volumetric(
+    app_handle: AppHandle,
+    file_path: Option<FilePath>,
+    settings: VolumetricOperation,
+) -> Result<Option<Vec<VolumetricData>>, String> {
+    let state = app_handle.state::<AppState>();
+    let data = volumetric_data(&state, settings.data_index)?;
+    let file_path = match file_path {
+        Some(file_path) => file_path,
+        None => match app_handle.dialog().file().blocking_pick_file() {
+            Some(file_path) => file_path,
+            None => return Ok(None),
+        },
+    };
+    let FilePath::Path(path) = file_path else {
+        return Err("Invalid file path".to_string());
+    };
+
+    let other = read_structure_file(&app_handle, &path)?
+        .volumetric
+        .get(settings.other_index)
+        .cloned()
+        .ok_or_else(|| format!("No volumetric data in {}", path.display()))?;
+    let (factor, symbol) = match settings.mode.as_str() {
+        "add" => (settings.factor, '+'),
+        "subtract" => (-settings.factor, '-'),
+        mode => return Err(format!("Unknown operation {}", mode)),
+    };
+    let file_name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    let name = format!("{} {} {}", data.name, symbol, file_name);
+    let result = volumetric::combine(&data, &other, factor, name)?;
+    append_volumetric(&app_handle, &state, result).map(Some)
+}
+
+/// 将格点数据乘以 factor，结果追加到当前结构的格点数据末尾
+#[tauri::command]
+pub fn scale_volumetric(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    data_index: usize,
+    factor: f64,
+) -> Result<Vec<VolumetricData>, String> {
+    let data = volumetric_data(&state, data_index)?;
+    let name = format!("{} × {}", data.name, factor);
+    append_volumetric(&app_handle, &state, volumetric::scale_data(&data, factor, name))
+}
+
+/// 将格点数据重采样到新的格点数，结果追加到当前结构的格点数据末尾
+#[tauri::command]
+pub fn resample_volumetric(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    data_index: usize,
+    dims: [usize; 3],
+) -> Result<Vec<VolumetricData>, String> {
+    if dims.contains(&0) {
+        return Err("Grid dimensions must be positive".to_string());
+    }
+    let data = volumetric_data(&state, data_index)?;
+    let mut result = volumetric::resample(&data, dims);
+    result.name = format!("{} ({}×{}×{})", data.name, dims[0], dims[1], dims[2]);
+    append_volumetric(&app_handle, &state, result)
+}
+
+/// 把新的格点数据追加到当前结构并刷新视图，返回全部格点数据的信息
+fn append_volumetric(
+    app_handle: &AppHandle,
+    state: &AppState,
+    data: VolumetricData,
+) -> Result<Vec<VolumetricData>, String> {
+    let structure = {
+        let mut structure = state.structure.lock().unwrap();
+        let current = structure.as_mut().ok_or("No structure loaded")?;
+        current.volumetric.push(data);
+        current.clone()
+    };
+    send_structure(app_handle, &structure)?;
+    Ok(structure.volumetric)
+}
+
+/// 对电荷密度做 Bader 划分，原子盆数据追加到当前结构的格点数据末尾
+#[tauri::command]
+pub fn compute_bader(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    settings: BaderSettings,
+) -> Result<BaderResult, String> {
+    let structure = state.current_structure()?;
+    let data = volumetric_data(&state, settings.data_index)?;
+    let (mut result, basins) = bader::bader_analysis(&structure, &data, &settings)?;
+    let volumetric = append_volumetric(&app_handle, &state, basins)?;
+    result.basin_data_index = Some(volumetric.len() - 1);
+    Ok(result)
+}
+
+/// 提取若干原子的 Bader 盆表面，data_index 为 compute_bader 生成的原子盆数据，返回格式同等值面
+#[tauri::command]
+pub fn compute_basin_surface(
+    state: State<'_, AppState>,
+    data_index: usize,
+    atoms: Vec<usize>,
+) -> Result<Response, String> {
+    let basins = volumetric_data(&state, data_index)?;
+    if basins.kind != "basins" {
+        return Err("Selected volumetric data is not a Bader basin map".to_string());
+    }
+    let indicator = bader::basin_indicator(&basins, &atoms);
+    Ok(Response::new(encode_meshes(&[extract_isosurface(&indicator, 0.5, 1)])))
+}
+
+/// 当前结构的第 index 个格点数据
+fn volumetric_data(state: &AppState, index: usize) -> Result<VolumetricData, String> {
+    state
+        .current_structure()?
+        .volumetric
+        .get(index)
+        .cloned()
+        .ok_or_else(|| "No volumetric data loaded".to_string())
+}
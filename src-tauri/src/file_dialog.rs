@@ -1,20 +1,204 @@
-use tauri::Emitter;
-use tauri::AppHandle;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
 use tauri_plugin_dialog::{DialogExt, FilePath};
+use crate::commands::display::send_structure;
+use crate::parsers::atom_config::{AtomConfig, CrystalStructure};
+use crate::parsers::espresso_parser::{self, is_qe_input, QeInput};
+use crate::parsers::lammps_parser::{self, is_lammps_data};
+use crate::parsers::parser_utils::load_atom_config;
+use crate::parsers::trajectory::{trajectory_format, Trajectory};
+use crate::parsers::{cif_parser, cube_parser, vasp_parser};
+use crate::state::{AppState, FrameSource};
+use crate::writers::{cif_writer::write_cif, lammps_writer::write_lammps_data, vasp_writer::write_poscar};
 
 /// 打开文件对话框并解析文件的异步函数
 #[tauri::command]
-pub async fn open_file_dialog(app_handle: AppHandle) {
-    let file_path = app_handle.dialog().file().blocking_pick_file();
-
-    if let Some(file_path) = file_path {
-        parse_and_send_data(app_handle, file_path).await;
+pub async fn open_file_dialog(app_handle: AppHandle) -> Result<(), String> {
+    match app_handle.dialog().file().blocking_pick_file() {
+        Some(file_path) => parse_and_send_data(app_handle, file_path).await,
+        None => Ok(()),
     }
 }
 
 /// 解析文件并发送数据的异步函数
+/// 轨迹文件只建立帧索引并保存在状态中，视图显示最后一帧
+#[tauri::command]
+pub async fn parse_and_send_data(app_handle: AppHandle, file_path: FilePath) -> Result<(), String> {
+    let path = match &file_path {
+        FilePath::Path(path) => PathBuf::from(path),
+        _ => return Err("Invalid file path".to_string()),
+    };
+    let state = app_handle.state::<AppState>();
+    let data = match trajectory_format(&path) {
+        Some(format) => Trajectory::open(&path, format).and_then(|trajectory| state.set_file_trajectory(trajectory)),
+        None => read_structure_file(&app_handle, &path).inspect(|structure| state.set_structure(structure.clone())),
+    }
+    .map_err(|err| format!("Failed to load {}: {}", path.display(), err))?;
+    send_structure(&app_handle, &data)
+}
+
+/// 读取结构文件，轨迹文件只读取最后一帧
+/// 由文件开头的内容识别出的轨迹文件（XDATCAR、QE 输出、XYZ 与 LAMMPS dump，见 trajectory_format）按轨迹读取；
+/// 其余根据文件名选择解析器：LAMMPS data 文件（.data/.lmp/data.*）与 pw.x 输入文件（.in/.pwi）按单个结构读取，
+/// CHGCAR/LOCPOT/ELFCAR 等与 .cube 文件连同格点数据读取，其余按 CIF 读取
+pub fn read_structure_file(app_handle: &AppHandle, path: &Path) -> Result<CrystalStructure, String> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let path = path.to_path_buf();
+
+    if let Some(format) = trajectory_format(&path) {
+        let frame = Trajectory::open(&path, format)?.last_frame()?;
+        Ok(frame.to_structure(&load_atom_config(app_handle)))
+    } else if is_lammps_data(&file_name) {
+        lammps_parser::parse_lammps_data(app_handle, &path)
+    } else if is_qe_input(&file_name) {
+        espresso_parser::parse_qe_input(app_handle, &path)
+    } else if let Some(kind) = volumetric_kind(&file_name) {
+        vasp_parser::parse_chgcar(app_handle, &path, kind)
+    } else if file_name.ends_with(".cube") || file_name.ends_with(".cub") {
+        cube_parser::parse_cube(app_handle, &path)
+    } else {
+        Ok(cif_parser::parse_cif(app_handle, &FilePath::Path(path.clone())))
+    }
+}
+
+/// 由文件名判断 VASP 格点文件的数据类型
+fn volumetric_kind(file_name: &str) -> Option<&'static str> {
+    if file_name.contains("locpot") {
+        Some("potential")
+    } else if file_name.contains("elfcar") {
+        Some("elf")
+    } else if ["chgcar", "parchg", "aeccar"].iter().any(|name| file_name.contains(name)) || file_name == "chg" {
+        Some("density")
+    } else {
+        None
+    }
+}
+
+/// 读取 pw.x 输入文件中的物种、原子坐标、晶格与 k 点设置，供提交计算前检查
+#[tauri::command]
+pub fn inspect_qe_input(file_path: String) -> Result<QeInput, String> {
+    let content = fs::read_to_string(&file_path).map_err(|e| format!("Failed to read {}: {}", file_path, e))?;
+    espresso_parser::read_qe_input(&content)
+}
+
+/// 在视图中显示生成的结构（表面、缺陷等），并将其设为当前结构
+#[tauri::command]
+pub fn show_structure(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    structure: CrystalStructure,
+) -> Result<(), String> {
+    state.set_structure(structure.clone());
+    send_structure(&app_handle, &structure)
+}
+
+/// 当前轨迹的概况
+#[derive(Serialize, Clone, Debug)]
+pub struct TrajectoryInfo {
+    pub frame_count: usize,
+    pub format: Option<String>, // 轨迹文件的格式，内存中的轨迹与单个结构为 None
+    pub path: Option<String>,
+}
+
+/// 获取当前轨迹的帧数与来源
 #[tauri::command]
-pub async fn parse_and_send_data(app_handle: AppHandle, file_path: FilePath) {
-    let data = crate::parsers::cif_parser::parse_cif(&app_handle, &file_path);
-    app_handle.emit("cif-data", data).unwrap();
-}
\ No newline at end of file
+pub fn get_trajectory_info(state: State<'_, AppState>) -> TrajectoryInfo {
+    let trajectory = state.trajectory();
+    TrajectoryInfo {
+        frame_count: state.frame_count(),
+        format: trajectory.as_ref().map(|t| t.format.clone()),
+        path: trajectory.as_ref().map(|t| t.path.display().to_string()),
+    }
+}
+
+/// 在视图中显示当前轨迹的第 index 帧，并将其设为当前结构
+/// 轨迹文件的帧在此时才从文件中读取
+#[tauri::command]
+pub fn show_frame(app_handle: AppHandle, state: State<'_, AppState>, index: usize) -> Result<(), String> {
+    let frame = state.frame(index)?;
+    *state.structure.lock().unwrap() = Some(frame.clone());
+    send_structure(&app_handle, &frame)
+}
+
+/// 打开保存对话框并导出结构，未指定结构时导出当前结构
+#[tauri::command]
+pub async fn export_structure(
+    app_handle: AppHandle,
+    structure: Option<CrystalStructure>,
+) -> Result<(), String> {
+    let structure = match structure {
+        Some(structure) => structure,
+        None => app_handle.state::<AppState>().current_structure()?,
+    };
+    let file_path = app_handle
+        .dialog()
+        .file()
+        .add_filter("CIF", &["cif"])
+        .add_filter("POSCAR", &["vasp"])
+        .add_filter("LAMMPS data", &["data", "lmp"])
+        .set_file_name("structure.cif")
+        .blocking_save_file();
+
+    match file_path {
+        Some(FilePath::Path(path)) => write_structure(&structure, &path),
+        Some(_) => Err("Invalid file path".to_string()),
+        None => Ok(()),
+    }
+}
+
+/// 按文件名写出结构：.cif 写为 CIF，LAMMPS data 文件（.data/.lmp/data.*）写为 atomic 风格，其余写为 POSCAR
+pub fn write_structure(structure: &CrystalStructure, path: &Path) -> Result<(), String> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let content = if file_name.to_lowercase().ends_with(".cif") {
+        let title = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "teacat".to_string());
+        write_cif(structure, &title)
+    } else if is_lammps_data(&file_name.to_lowercase()) {
+        write_lammps_data(structure, "generated by Teacat", "atomic")?
+    } else {
+        write_poscar(structure, "generated by Teacat")
+    };
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// 选择目录并把轨迹各帧导出为 VASP NEB 的图像目录 00、01、02……，每个目录中写入 POSCAR
+/// 未指定图像时导出当前轨迹，轨迹文件的帧逐帧读取并写出
+#[tauri::command]
+pub async fn export_images(
+    app_handle: AppHandle,
+    images: Option<Vec<CrystalStructure>>,
+) -> Result<(), String> {
+    let source = match images {
+        Some(images) => FrameSource::Memory(images),
+        None => app_handle.state::<AppState>().frame_source()?,
+    };
+    match app_handle.dialog().file().blocking_pick_folder() {
+        Some(FilePath::Path(path)) => write_images(&source, &path, &load_atom_config(&app_handle)),
+        Some(_) => Err("Invalid file path".to_string()),
+        None => Ok(()),
+    }
+}
+
+/// 把图像逐帧写入 directory 下的编号子目录
+pub fn write_images(source: &FrameSource, directory: &Path, atom_config: &HashMap<String, AtomConfig>) -> Result<(), String> {
+    let width = (source.len().saturating_sub(1)).to_string().len().max(2);
+    for index in 0..source.len() {
+        let image = source.read_frame(index)?.to_structure(atom_config);
+        let image_directory = directory.join(format!("{:0width$}", index, width = width));
+        fs::create_dir_all(&image_directory).map_err(|e| e.to_string())?;
+        let comment = format!("Image {:0width$}", index, width = width);
+        fs::write(image_directory.join("POSCAR"), write_poscar(&image, &comment)).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
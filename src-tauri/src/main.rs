@@ -1,12 +1,34 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use file_dialog::{open_file_dialog,parse_and_send_data};
+use file_dialog::{open_file_dialog,parse_and_send_data,show_structure,show_frame,get_trajectory_info,inspect_qe_input,export_structure,export_images};
+use commands::analysis::{
+    compare_with_file, compute_bond_valence, compute_diffusion, compute_polyhedra, compute_pores, compute_rdf,
+    get_structure_info, list_distances, measure_angle, measure_dihedral, measure_distance,
+};
+use commands::builders::{apply_strain, build_slab, generate_defects, generate_elastic_strains, interpolate_images, list_slab_terminations};
+use commands::diffraction::compute_neutron_pattern;
+use commands::display::{
+    color_by_property, get_atom_styles, get_display_settings, get_element_data, reset_atom_styles, set_atom_style,
+    set_boundary_mode, set_color_scheme, set_radius_type,
+};
+use commands::volumetric::{
+    combine_volumetric, compute_bader, compute_basin_surface, compute_isosurface, compute_line_profile, compute_planar_average, compute_slice,
+    resample_volumetric, scale_volumetric,
+};
+use parsers::parser_utils::load_user_styles;
+use tauri::Manager;
+use state::AppState;
 
 mod menu;
 mod file_dialog;
 mod parsers;
 mod utils;
+mod analysis;
+mod builders;
+mod commands;
+mod state;
+mod writers;
 
 fn main() {
     // n卡会白屏，WebKit新版的渲染器与nvidia驱动暂时还不兼容导致
@@ -21,7 +43,11 @@ fn main() {
         .plugin(tauri_plugin_log::Builder::new().build())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
+        .manage(AppState::default())
         .setup(|app| {
+            // 读取用户的原子样式配置
+            *app.state::<AppState>().user_styles.lock().unwrap() = load_user_styles(app.handle());
+
             // 创建菜单
             menu::create_menu(app.handle());
 
@@ -38,7 +64,49 @@ fn main() {
         })
         .invoke_handler(tauri::generate_handler![
             open_file_dialog,
-            parse_and_send_data
+            parse_and_send_data,
+            show_structure,
+            show_frame,
+            get_trajectory_info,
+            inspect_qe_input,
+            export_structure,
+            export_images,
+            compute_neutron_pattern,
+            compute_rdf,
+            compute_diffusion,
+            measure_distance,
+            measure_angle,
+            measure_dihedral,
+            list_distances,
+            compute_polyhedra,
+            compute_bond_valence,
+            build_slab,
+            list_slab_terminations,
+            generate_defects,
+            compare_with_file,
+            interpolate_images,
+            apply_strain,
+            generate_elastic_strains,
+            compute_pores,
+            get_structure_info,
+            get_display_settings,
+            set_radius_type,
+            get_element_data,
+            set_color_scheme,
+            color_by_property,
+            get_atom_styles,
+            set_atom_style,
+            reset_atom_styles,
+            set_boundary_mode,
+            compute_isosurface,
+            compute_slice,
+            compute_line_profile,
+            compute_planar_average,
+            combine_volumetric,
+            scale_volumetric,
+            resample_volumetric,
+            compute_bader,
+            compute_basin_surface
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
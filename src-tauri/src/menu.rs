@@ -1,16 +1,42 @@
 use tauri::Emitter;
 use tauri::menu::{MenuBuilder, MenuItemBuilder, SubmenuBuilder};
-use tauri::{AppHandle, WebviewWindowBuilder};
+use tauri::{AppHandle, Manager, WebviewWindowBuilder};
+use crate::state::AppState;
 
 /// 创建菜单
 pub fn create_menu(app: &AppHandle) {
     let open = MenuItemBuilder::with_id("open", "Open").build(app).expect("open menu item");
+    let export = MenuItemBuilder::with_id("export", "Export").build(app).expect("export menu item");
     let quit = MenuItemBuilder::with_id("quit", "Quit").build(app).expect("quit menu item");
 
     let toggle_atom_labels = MenuItemBuilder::with_id("toggle_atom_labels", "Toggle Atom Labels")
         .build(app)
         .expect("toggle atom labels menu item");
 
+    let radius_menu = SubmenuBuilder::new(app, "Atom Radius")
+        .text("radius_atomic", "Atomic")
+        .text("radius_covalent", "Covalent")
+        .text("radius_ionic", "Ionic")
+        .text("radius_vdw", "Van der Waals")
+        .build()
+        .expect("atom radius submenu");
+
+    let color_menu = SubmenuBuilder::new(app, "Atom Colors")
+        .text("color_jmol", "Jmol")
+        .text("color_cpk", "CPK")
+        .text("color_vesta", "VESTA")
+        .text("color_site", "By Site Label")
+        .text("color_wyckoff", "By Wyckoff Site")
+        .build()
+        .expect("atom colors submenu");
+
+    let boundary_menu = SubmenuBuilder::new(app, "Cell Boundary")
+        .text("boundary_cell", "Unit Cell")
+        .text("boundary_molecules", "Complete Molecules")
+        .text("boundary_grow", "Grow Fragments")
+        .build()
+        .expect("cell boundary submenu");
+
     let set_repeats = MenuItemBuilder::with_id("set_repeats", "Set Repeats (x/y/z)")
         .build(app)
         .expect("set repeats menu item");
@@ -20,12 +46,12 @@ pub fn create_menu(app: &AppHandle) {
         .expect("about menu item");
 
     let file_menu = SubmenuBuilder::new(app, "File")
-        .items(&[&open, &quit])
+        .items(&[&open, &export, &quit])
         .build()
         .expect("file submenu");
 
     let view_menu = SubmenuBuilder::new(app, "View")
-        .items(&[&toggle_atom_labels])
+        .items(&[&toggle_atom_labels, &radius_menu, &color_menu, &boundary_menu])
         .build()
         .expect("view submenu");
 
@@ -55,7 +81,14 @@ pub async fn handle_menu_event(app_handle: AppHandle, menu_id: &str) {
     match menu_id {
         "open" => {
             println!("Open menu clicked");
-            crate::file_dialog::open_file_dialog(app_handle).await;
+            if let Err(err) = crate::file_dialog::open_file_dialog(app_handle).await {
+                eprintln!("Open failed: {}", err);
+            }
+        }
+        "export" => {
+            if let Err(err) = crate::file_dialog::export_structure(app_handle, None).await {
+                eprintln!("Export failed: {}", err);
+            }
         }
         "quit" => {
             println!("Quit menu clicked");
@@ -64,6 +97,27 @@ pub async fn handle_menu_event(app_handle: AppHandle, menu_id: &str) {
         "toggle_atom_labels" => {
             app_handle.emit("toggle-atom-labels", {}).unwrap();
         }
+        id if id.starts_with("radius_") => {
+            let radius_type = id.trim_start_matches("radius_").to_string();
+            let state = app_handle.state::<AppState>();
+            if let Err(err) = crate::commands::display::set_radius_type(app_handle.clone(), state, radius_type) {
+                eprintln!("Failed to change atom radius: {}", err);
+            }
+        }
+        id if id.starts_with("color_") => {
+            let color_scheme = id.trim_start_matches("color_").to_string();
+            let state = app_handle.state::<AppState>();
+            if let Err(err) = crate::commands::display::set_color_scheme(app_handle.clone(), state, color_scheme) {
+                eprintln!("Failed to change atom colors: {}", err);
+            }
+        }
+        id if id.starts_with("boundary_") => {
+            let boundary_mode = id.trim_start_matches("boundary_").to_string();
+            let state = app_handle.state::<AppState>();
+            if let Err(err) = crate::commands::display::set_boundary_mode(app_handle.clone(), state, boundary_mode) {
+                eprintln!("Failed to change cell boundary: {}", err);
+            }
+        }
         "set_repeats" => {
             app_handle.emit("set-repeats", {}).unwrap();
         }
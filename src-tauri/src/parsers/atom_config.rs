@@ -1,13 +1,88 @@
+use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 
 // 定义晶体结构的返回类型
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct CrystalStructure {
     pub lattice_vertices: Vec<(f64, f64, f64)>, // 返回晶格顶点
     pub atoms: Vec<Atom>,
+    pub lattice: [[f64; 3]; 3], // 晶格基矢量（行向量，单位 Å）
+    pub sites: Vec<Site>,       // 晶胞内的原子位点（分数坐标，不含边界复制的原子）
+    pub cell_esd: [f64; 6],     // 晶格参数 a, b, c, α, β, γ 的标准差，文件未给出时为 0
+    #[serde(default)]
+    pub volumetric: Vec<VolumetricData>, // 随结构读入的格点数据（电荷密度、势等）
 }
 
-#[derive(Serialize, Clone, Debug)]
+/// 晶胞上的三维格点数据
+/// 格点数据不随结构发送给前端，前端只获得格点尺寸与统计信息
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct VolumetricData {
+    pub name: String,     // total / magnetization / spin_down，cube 文件为注释行
+    pub kind: String,     // density / potential / elf / cube
+    pub dims: [usize; 3], // 三个方向的格点数
+    pub lattice: [[f64; 3]; 3],
+    pub origin: [f64; 3], // 格点 (0,0,0) 的笛卡尔坐标（Å）
+    #[serde(skip)]
+    pub values: Arc<Vec<f64>>, // 按 a 最快、c 最慢的顺序存放
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+}
+
+impl VolumetricData {
+    /// 创建格点数据并计算统计量
+    pub fn new(
+        name: String,
+        kind: String,
+        dims: [usize; 3],
+        lattice: [[f64; 3]; 3],
+        origin: [f64; 3],
+        values: Vec<f64>,
+    ) -> Self {
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let mean = values.iter().sum::<f64>() / values.len().max(1) as f64;
+        VolumetricData {
+            name,
+            kind,
+            dims,
+            lattice,
+            origin,
+            values: Arc::new(values),
+            min,
+            max,
+            mean,
+        }
+    }
+
+    /// 格点 (i, j, k) 处的值，下标按周期性边界折回
+    pub fn value(&self, i: i64, j: i64, k: i64) -> f64 {
+        let [na, nb, nc] = self.dims.map(|n| n as i64);
+        let index = i.rem_euclid(na) + na * (j.rem_euclid(nb) + nb * k.rem_euclid(nc));
+        self.values[index as usize]
+    }
+
+    /// 分数坐标处的三线性插值（周期性）
+    pub fn interpolate(&self, frac: [f64; 3]) -> f64 {
+        let position: [f64; 3] = [0, 1, 2].map(|k| frac[k] * self.dims[k] as f64);
+        let base = position.map(|p| p.floor());
+        let t = [0, 1, 2].map(|k| position[k] - base[k]);
+        let [i, j, k] = base.map(|b| b as i64);
+        let mut result = 0.0;
+        for corner in 0..8 {
+            let (di, dj, dk) = (corner & 1, (corner >> 1) & 1, (corner >> 2) & 1);
+            let weight = (if di == 1 { t[0] } else { 1.0 - t[0] })
+                * (if dj == 1 { t[1] } else { 1.0 - t[1] })
+                * (if dk == 1 { t[2] } else { 1.0 - t[2] });
+            if weight > 0.0 {
+                result += weight * self.value(i + di, j + dj, k + dk);
+            }
+        }
+        result
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Atom {
     pub name: String,
     pub x: f64,
@@ -17,9 +92,33 @@ pub struct Atom {
     pub color: String,
 }
 
+// 晶胞中的原子位点，分析计算均基于位点而非绘制用的原子
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Site {
+    pub label: String,   // 位点标签，如 Fe1
+    pub element: String, // 元素符号，如 Fe
+    pub frac: [f64; 3],  // 分数坐标
+    pub occupancy: f64,  // 占位率
+    pub frac_esd: [f64; 3], // 分数坐标的标准差，文件未给出时为 0
+}
+
+impl Site {
+    /// 创建占位率为 1、不带标准差的位点
+    pub fn new(label: String, element: String, frac: [f64; 3]) -> Self {
+        Site {
+            label,
+            element,
+            frac,
+            occupancy: 1.0,
+            frac_esd: [0.0; 3],
+        }
+    }
+}
+
 // 定义 AtomConfig 结构体
 #[derive(Deserialize, Clone)]
 pub struct AtomConfig {
     pub radius: f64,
     pub color: String,
-}
\ No newline at end of file
+    pub visible: bool, // 隐藏的元素不生成绘制用的原子，位点仍参与分析
+}
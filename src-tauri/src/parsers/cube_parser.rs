@@ -0,0 +1,104 @@
+use std::fs;
+use std::path::PathBuf;
+use tauri::AppHandle;
+use crate::parsers::atom_config::{CrystalStructure, Site, VolumetricData};
+use crate::parsers::parser_utils::{build_crystal_structure, load_atom_config};
+use crate::utils::elements::ELEMENTS;
+use crate::utils::geometry::cart_to_frac;
+use crate::utils::linalg::sub;
+
+/// 玻尔半径（Å）
+const BOHR: f64 = 0.529177210903;
+
+/// 解析 Gaussian cube 文件
+/// 晶胞取三个格点步长向量乘以格点数，格点数为负时坐标单位为 Å，否则为 Bohr
+/// 数据按 cube 的顺序（z 最快）读入后重排为 a 最快；数值保持文件中的单位
+pub fn parse_cube(app_handle: &AppHandle, file_path: &PathBuf) -> Result<CrystalStructure, String> {
+    let file_content = fs::read_to_string(file_path).map_err(|e| e.to_string())?;
+    let lines: Vec<&str> = file_content.lines().collect();
+    if lines.len() < 6 {
+        return Err("Incomplete cube header".to_string());
+    }
+    let numbers = |line: &str| -> Result<Vec<f64>, String> {
+        line.split_whitespace()
+            .map(|v| v.parse::<f64>().map_err(|_| format!("Invalid number {} in cube header", v)))
+            .collect()
+    };
+
+    // 第三行：原子数与原点，原子数为负表示原子之后还有一行分子轨道编号
+    let header = numbers(lines[2])?;
+    if header.len() < 4 {
+        return Err("Invalid cube atom count line".to_string());
+    }
+    let atom_count = header[0] as i64;
+
+    let mut dims = [0usize; 3];
+    let mut lattice = [[0.0; 3]; 3];
+    let mut unit = BOHR;
+    for axis in 0..3 {
+        let row = numbers(lines[3 + axis])?;
+        if row.len() < 4 {
+            return Err("Invalid cube grid line".to_string());
+        }
+        if row[0] < 0.0 {
+            unit = 1.0;
+        }
+        dims[axis] = row[0].abs() as usize;
+        lattice[axis] = [row[1], row[2], row[3]].map(|v| v * dims[axis] as f64);
+    }
+    if dims.contains(&0) {
+        return Err("Cube grid has no points".to_string());
+    }
+    lattice = lattice.map(|row| row.map(|v| v * unit));
+    let origin = [header[1], header[2], header[3]].map(|v| v * unit);
+
+    let mut sites = Vec::new();
+    for n in 0..atom_count.unsigned_abs() as usize {
+        let row = numbers(lines.get(6 + n).ok_or("Missing atoms in cube file")?)?;
+        if row.len() < 5 {
+            return Err("Invalid cube atom line".to_string());
+        }
+        let element = ELEMENTS
+            .get((row[0] as usize).wrapping_sub(1))
+            .map(|e| e.symbol.to_string())
+            .unwrap_or_else(|| "X".to_string());
+        let cart = [row[2], row[3], row[4]].map(|v| v * unit);
+        let frac = cart_to_frac(&lattice, sub(cart, origin));
+        sites.push(Site::new(format!("{}{}", element, n + 1), element, frac));
+    }
+    let mut start = 6 + atom_count.unsigned_abs() as usize;
+    // 分子轨道文件的每个格点依次存放各轨道的值，只取第一个轨道
+    let mut stride = 1;
+    if atom_count < 0 {
+        let orbitals = numbers(lines.get(start).ok_or("Missing orbital line in cube file")?)?;
+        stride = orbitals.first().map(|&m| m as usize).unwrap_or(1).max(1);
+        start += 1;
+    }
+
+    let count = dims[0] * dims[1] * dims[2];
+    let mut raw = Vec::with_capacity(count * stride);
+    for line in lines.iter().skip(start) {
+        for token in line.split_whitespace() {
+            raw.push(token.parse::<f64>().map_err(|_| format!("Invalid grid value {}", token))?);
+        }
+        if raw.len() >= count * stride {
+            break;
+        }
+    }
+    if raw.len() < count * stride {
+        return Err(format!("Expected {} grid values, found {}", count * stride, raw.len()));
+    }
+    let mut values = vec![0.0; count];
+    for (index, value) in raw.into_iter().step_by(stride).take(count).enumerate() {
+        let k = index % dims[2];
+        let j = (index / dims[2]) % dims[1];
+        let i = index / (dims[1] * dims[2]);
+        values[i + dims[0] * (j + dims[1] * k)] = value;
+    }
+
+    let name = lines[0].trim().to_string();
+    let volumetric = VolumetricData::new(name, "cube".to_string(), dims, lattice, origin, values);
+    let mut structure = build_crystal_structure(lattice, sites, &load_atom_config(app_handle));
+    structure.volumetric = vec![volumetric];
+    Ok(structure)
+}
@@ -1,142 +1,623 @@
-use regex::Regex;
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
-use crate::parsers::atom_config::{CrystalStructure, Atom, AtomConfig};
-use crate::parsers::parser_utils::{load_atom_config, replicate_boundary_atoms};
-
-/// 解析 Quantum ESPRESSO 输出文件并返回多个晶体结构
-pub fn parse_qe_output(file_path: &PathBuf) -> Vec<CrystalStructure> {
-    // 正则表达式
-    let cell_pattern = Regex::new(r"CELL_PARAMETERS\s+\(alat\s*=\s*([\d\.]+)\)").unwrap();
-    let pos_pattern = Regex::new(r"ATOMIC_POSITIONS\s+\(([a-zA-Z]+)\)").unwrap();
-
-    // 存储解析结果的数组
-    let mut structures = Vec::new();
-
-    // 打开文件并逐行读取
-    let file_content = fs::read_to_string(file_path).expect("Unable to read Quantum ESPRESSO output file");
-
-    let mut current_cell: Option<[[f64; 3]; 3]> = None;
-    let mut current_atoms: Vec<Atom> = Vec::new();
-    let mut alat = 1.0; // 初始化 alat 为 1，之后从文件读取
-
-    for line in file_content.lines() {
-        // 匹配 CELL_PARAMETERS 并读取晶格参数
-        if let Some(captures) = cell_pattern.captures(line) {
-            alat = captures[1]
-                .parse::<f64>()
-                .expect("Failed to parse alat from CELL_PARAMETERS");
-            current_cell = Some(parse_cell_parameters(&file_content, line));
-        }
-
-        // 匹配 ATOMIC_POSITIONS 并读取原子数据
-        if let Some(_) = pos_pattern.captures(line) {
-            current_atoms = parse_atomic_positions(&file_content, line);
-
-            // 如果当前有 cell 和 atoms，生成 CrystalStructure 并存储
-            if let Some(cell) = current_cell.take() {
-                let lattice_vertices = calculate_lattice_vertices(cell);
-                let atoms = convert_to_cartesian(current_atoms.clone(), cell, alat); // 转换为笛卡尔坐标
-                structures.push(CrystalStructure {
-                    lattice_vertices,
-                    atoms,
-                });
+use serde::Serialize;
+use tauri::AppHandle;
+use crate::parsers::atom_config::{CrystalStructure, Site};
+use crate::parsers::parser_utils::{build_crystal_structure, element_symbol, load_atom_config};
+use crate::parsers::trajectory::{parse_triple, Frame, FrameOffset, LineReader, TrajectoryHeader};
+use crate::utils::geometry::cart_to_frac;
+use crate::utils::linalg::Mat3;
+
+/// 玻尔半径（Å）
+pub const BOHR_TO_ANGSTROM: f64 = 0.529177210903;
+
+/// 扫描 Quantum ESPRESSO 输出文件，记录每个 ATOMIC_POSITIONS 块的位置
+/// 每帧使用它之前最近的 CELL_PARAMETERS 块（vc-relax、vc-md），没有时使用文件开头的初始晶格
+pub fn index_qe_output(reader: &mut LineReader) -> Result<(Vec<FrameOffset>, TrajectoryHeader), String> {
+    let mut frames = Vec::new();
+    let mut header = TrajectoryHeader::default();
+    let mut cell: Option<u64> = None;
+
+    loop {
+        let offset = reader.position();
+        let Some(line) = reader.next_line()? else { break };
+        let line = line.trim();
+        if line.starts_with("ATOMIC_POSITIONS") {
+            frames.push(FrameOffset { start: offset, header: cell });
+        } else if line.starts_with("CELL_PARAMETERS") {
+            cell = Some(offset);
+        } else if line.starts_with("lattice parameter (alat)") && header.alat.is_none() {
+            header.alat = number_after(line, '=');
+        } else if line.starts_with("crystal axes:") && header.lattice.is_none() {
+            // a(1) = (  -0.500000   0.000000   0.500000 )，单位为 alat
+            let alat = header.alat.ok_or("Crystal axes found before lattice parameter")?;
+            let mut axes = [[0.0; 3]; 3];
+            for axis in axes.iter_mut() {
+                let line = reader.expect_line()?;
+                let values = line.split_once("= (").map(|(_, v)| v.replace(')', " ")).unwrap_or_default();
+                *axis = parse_triple(&values)?.map(|v| v * alat * BOHR_TO_ANGSTROM);
             }
+            header.lattice = Some(axes);
         }
     }
 
-    structures
+    Ok((frames, header))
 }
 
-/// 解析 CELL_PARAMETERS 的三行数据
-fn parse_cell_parameters(file_content: &str, line: &str) -> [[f64; 3]; 3] {
-    let mut cell = [[0.0; 3]; 3];
-    let mut lines = file_content.lines().skip_while(|l| *l != line).skip(1);
+/// 读取一帧：晶格取自该帧对应的 CELL_PARAMETERS 块或初始晶格，
+/// 原子坐标支持 crystal、angstrom、bohr、alat 四种单位
+pub fn read_qe_frame(reader: &mut LineReader, offset: FrameOffset, header: &TrajectoryHeader) -> Result<Frame, String> {
+    let lattice = match offset.header {
+        Some(cell) => {
+            reader.seek(cell)?;
+            let line = reader.expect_line()?.to_lowercase();
+            let factor = unit_factor(&line, header)?;
+            let mut lattice = [[0.0; 3]; 3];
+            for row in lattice.iter_mut() {
+                *row = parse_triple(reader.expect_line()?)?.map(|v| v * factor);
+            }
+            lattice
+        }
+        None => header.lattice.ok_or("No lattice found before atomic positions")?,
+    };
 
-    for i in 0..3 {
-        if let Some(l) = lines.next() {
-            let values: Vec<f64> = l
-                .split_whitespace()
-                .map(|v| v.parse::<f64>().expect("Failed to parse cell parameter"))
-                .collect();
-            cell[i] = [values[0], values[1], values[2]];
+    reader.seek(offset.start)?;
+    let line = reader.expect_line()?.to_lowercase();
+    let crystal = line.contains("crystal");
+    let factor = if crystal { 1.0 } else { unit_factor(&line, header)? };
+
+    let mut sites = Vec::new();
+    while let Some(line) = reader.next_line()? {
+        // 坐标块在空行、End final coordinates 或其他非坐标行处结束；
+        // 坐标后可能跟随固定原子的 0/1 标记
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 4 {
+            break;
         }
+        let Ok(position) = parse_triple(&parts[1..4].join(" ")) else { break };
+        let frac = if crystal {
+            position
+        } else {
+            cart_to_frac(&lattice, position.map(|v| v * factor))
+        };
+        sites.push(Site::new(parts[0].to_string(), element_symbol(parts[0]), frac));
+    }
+    if sites.is_empty() {
+        return Err("Empty ATOMIC_POSITIONS block".to_string());
+    }
+
+    Ok(Frame { lattice, sites, velocities: None })
+}
+
+/// CELL_PARAMETERS 与 ATOMIC_POSITIONS 单位到 Å 的换算系数
+/// alat 单位优先使用块标题中给出的数值，如 CELL_PARAMETERS (alat= 10.2000)
+fn unit_factor(line: &str, header: &TrajectoryHeader) -> Result<f64, String> {
+    if line.contains("angstrom") {
+        Ok(1.0)
+    } else if line.contains("bohr") {
+        Ok(BOHR_TO_ANGSTROM)
+    } else {
+        let alat = number_after(line, '=')
+            .or(header.alat)
+            .ok_or("Missing lattice parameter for alat units")?;
+        Ok(alat * BOHR_TO_ANGSTROM)
     }
+}
+
+/// 分隔符之后的第一个数
+fn number_after(line: &str, separator: char) -> Option<f64> {
+    let (_, rest) = line.split_once(separator)?;
+    let number: String = rest
+        .trim_start()
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E'))
+        .collect();
+    number.parse().ok()
+}
+
+/// pw.x 输入文件中 ATOMIC_SPECIES 的一行
+#[derive(Serialize, Clone, Debug)]
+pub struct AtomicSpecies {
+    pub label: String,
+    pub mass: f64,
+    pub pseudopotential: String,
+}
+
+/// pw.x 输入文件的 K_POINTS 卡片
+#[derive(Serialize, Clone, Debug)]
+pub struct KPoints {
+    pub mode: String,                // automatic / gamma / tpiba / crystal / tpiba_b / crystal_b
+    pub grid: Option<[usize; 3]>,    // automatic 的网格
+    pub shift: Option<[usize; 3]>,   // automatic 的偏移（0 或 1）
+    pub points: Vec<[f64; 4]>,       // 显式列出的 k 点与权重
+}
 
-    cell
+/// pw.x 输入文件的结构部分
+#[derive(Serialize, Clone, Debug)]
+pub struct QeInput {
+    pub ibrav: i32,
+    pub lattice: Mat3, // Å
+    pub sites: Vec<Site>,
+    pub species: Vec<AtomicSpecies>,
+    pub k_points: Option<KPoints>,
 }
 
-/// 解析 ATOMIC_POSITIONS 的多行数据
-fn parse_atomic_positions(file_content: &str, line: &str) -> Vec<Atom> {
-    let mut atoms = Vec::new();
-    let mut lines = file_content.lines().skip_while(|l| *l != line).skip(1);
+/// 解析 pw.x 输入文件并生成晶体结构
+pub fn parse_qe_input(app_handle: &AppHandle, file_path: &PathBuf) -> Result<CrystalStructure, String> {
+    let content = fs::read_to_string(file_path).map_err(|e| format!("Failed to read {}: {}", file_path.display(), e))?;
+    let input = read_qe_input(&content)?;
+    Ok(build_crystal_structure(input.lattice, input.sites, &load_atom_config(app_handle)))
+}
+
+/// 解析 pw.x 输入文本：&SYSTEM 中的 ibrav、celldm 或 A/B/C/cosAB/cosAC/cosBC，
+/// 以及 ATOMIC_SPECIES、ATOMIC_POSITIONS、CELL_PARAMETERS、K_POINTS 卡片
+pub fn read_qe_input(content: &str) -> Result<QeInput, String> {
+    let lines: Vec<&str> = content
+        .lines()
+        .map(|line| line.split(['!', '#']).next().unwrap_or_default().trim())
+        .collect();
+    let system = namelist(&lines, "system").ok_or("Missing &SYSTEM namelist")?;
+    let integer = |key: &str| -> Result<Option<i64>, String> {
+        system
+            .get(key)
+            .map(|v| fortran_number(v).map(|n| n as i64).ok_or_else(|| format!("Invalid {} = {}", key, v)))
+            .transpose()
+    };
+    let ibrav = integer("ibrav")?.ok_or("Missing ibrav")? as i32;
+    let nat = integer("nat")?.ok_or("Missing nat")? as usize;
+    let ntyp = integer("ntyp")?.ok_or("Missing ntyp")? as usize;
+
+    // celldm（Bohr 与比值）；给出 A 时由 A、B、C 与夹角余弦换算
+    let mut celldm = [0.0; 6];
+    for (i, value) in celldm.iter_mut().enumerate() {
+        if let Some(text) = system.get(&format!("celldm({})", i + 1)) {
+            *value = fortran_number(text).ok_or_else(|| format!("Invalid celldm({}) = {}", i + 1, text))?;
+        }
+    }
+    let real = |key: &str| system.get(key).and_then(|v| fortran_number(v));
+    if let Some(a) = real("a") {
+        if celldm[0] != 0.0 {
+            return Err("celldm and A cannot both be given".to_string());
+        }
+        celldm[0] = a / BOHR_TO_ANGSTROM;
+        celldm[1] = real("b").unwrap_or(0.0) / a;
+        celldm[2] = real("c").unwrap_or(0.0) / a;
+        let (cos_ab, cos_ac, cos_bc) = (real("cosab").unwrap_or(0.0), real("cosac").unwrap_or(0.0), real("cosbc").unwrap_or(0.0));
+        match ibrav {
+            14 => celldm[3..6].copy_from_slice(&[cos_bc, cos_ac, cos_ab]),
+            5 | -5 => celldm[3] = if cos_bc != 0.0 { cos_bc } else { cos_ab },
+            -12 | -13 => celldm[4] = cos_ac,
+            _ => celldm[3] = cos_ab,
+        }
+    }
+    let mut alat = celldm[0] * BOHR_TO_ANGSTROM;
+
+    let lattice = if ibrav == 0 {
+        let (unit, rows) = card(&lines, "CELL_PARAMETERS").ok_or("ibrav = 0 requires CELL_PARAMETERS")?;
+        // 未注明单位时：给出 celldm(1) 或 A 则为 alat，否则为 Bohr
+        let factor = match unit.as_str() {
+            "angstrom" => 1.0,
+            "bohr" => BOHR_TO_ANGSTROM,
+            "alat" => alat,
+            _ if alat > 0.0 => alat,
+            _ => BOHR_TO_ANGSTROM,
+        };
+        if rows.len() < 3 {
+            return Err("CELL_PARAMETERS needs three rows".to_string());
+        }
+        let mut lattice = [[0.0; 3]; 3];
+        for (row, line) in lattice.iter_mut().zip(&rows) {
+            *row = parse_triple(&line.replace(['d', 'D'], "e"))?.map(|v| v * factor);
+        }
+        // 未给出 celldm(1) 时 pw.x 取第一个基矢量的长度为 alat
+        if alat == 0.0 {
+            alat = lattice[0].iter().map(|v| v * v).sum::<f64>().sqrt();
+        }
+        lattice
+    } else {
+        ibrav_lattice(ibrav, celldm)?
+    };
+
+    let species = match card(&lines, "ATOMIC_SPECIES") {
+        Some((_, rows)) => rows
+            .iter()
+            .take(ntyp)
+            .map(|row| {
+                let parts: Vec<&str> = row.split_whitespace().collect();
+                if parts.len() < 3 {
+                    return Err(format!("Invalid ATOMIC_SPECIES line \"{}\"", row));
+                }
+                Ok(AtomicSpecies {
+                    label: parts[0].to_string(),
+                    mass: fortran_number(parts[1]).ok_or_else(|| format!("Invalid mass {}", parts[1]))?,
+                    pseudopotential: parts[2].to_string(),
+                })
+            })
+            .collect::<Result<_, String>>()?,
+        None => Vec::new(),
+    };
+
+    let (unit, rows) = card(&lines, "ATOMIC_POSITIONS").ok_or("Missing ATOMIC_POSITIONS")?;
+    if rows.len() < nat {
+        return Err(format!("Expected {} atomic positions, found {}", nat, rows.len()));
+    }
+    let factor = match unit.as_str() {
+        "crystal" => 1.0,
+        "angstrom" => 1.0,
+        "bohr" => BOHR_TO_ANGSTROM,
+        "alat" | "" => alat,
+        other => return Err(format!("Unsupported ATOMIC_POSITIONS units {}", other)),
+    };
+    let sites = rows
+        .iter()
+        .take(nat)
+        .map(|row| {
+            let parts: Vec<&str> = row.split_whitespace().collect();
+            if parts.len() < 4 {
+                return Err(format!("Invalid ATOMIC_POSITIONS line \"{}\"", row));
+            }
+            let position = parse_triple(&parts[1..4].join(" ").replace(['d', 'D'], "e"))?;
+            let frac = if unit == "crystal" {
+                position
+            } else {
+                cart_to_frac(&lattice, position.map(|v| v * factor))
+            };
+            Ok(Site::new(parts[0].to_string(), element_symbol(parts[0]), frac))
+        })
+        .collect::<Result<_, String>>()?;
+
+    let k_points = card(&lines, "K_POINTS").map(|(mode, rows)| parse_k_points(mode, &rows)).transpose()?;
+
+    Ok(QeInput { ibrav, lattice, sites, species, k_points })
+}
 
-    for l in lines {
-        let l = l.trim();
-        if l.is_empty() || l.contains("End final coordinates") {
+/// 由 Bravais 格子编号 ibrav 与 celldm 生成晶格矩阵（Å），基矢量取向与 pw.x 一致
+/// celldm(1) 为 a（Bohr），celldm(2)、celldm(3) 为 b/a、c/a，celldm(4..6) 为夹角余弦
+pub fn ibrav_lattice(ibrav: i32, celldm: [f64; 6]) -> Result<Mat3, String> {
+    let a = celldm[0] * BOHR_TO_ANGSTROM;
+    if a <= 0.0 {
+        return Err("Missing lattice parameter celldm(1) or A".to_string());
+    }
+    let (b, c) = (celldm[1] * a, celldm[2] * a);
+    let requires = |value: f64, name: &str| if value > 0.0 { Ok(value) } else { Err(format!("ibrav = {} requires {}", ibrav, name)) };
+    let lattice = match ibrav {
+        1 => [[a, 0.0, 0.0], [0.0, a, 0.0], [0.0, 0.0, a]],
+        2 => [[-a / 2.0, 0.0, a / 2.0], [0.0, a / 2.0, a / 2.0], [-a / 2.0, a / 2.0, 0.0]],
+        3 => [[a / 2.0, a / 2.0, a / 2.0], [-a / 2.0, a / 2.0, a / 2.0], [-a / 2.0, -a / 2.0, a / 2.0]],
+        -3 => [[-a / 2.0, a / 2.0, a / 2.0], [a / 2.0, -a / 2.0, a / 2.0], [a / 2.0, a / 2.0, -a / 2.0]],
+        4 => {
+            let c = requires(c, "celldm(3)")?;
+            [[a, 0.0, 0.0], [-a / 2.0, a * 3f64.sqrt() / 2.0, 0.0], [0.0, 0.0, c]]
+        }
+        5 | -5 => {
+            let cos = celldm[3];
+            if cos <= -0.5 || cos >= 1.0 {
+                return Err("ibrav = 5 requires -0.5 < celldm(4) < 1".to_string());
+            }
+            let tx = ((1.0 - cos) / 2.0).sqrt();
+            let ty = ((1.0 - cos) / 6.0).sqrt();
+            let tz = ((1.0 + 2.0 * cos) / 3.0).sqrt();
+            if ibrav == 5 {
+                [[a * tx, -a * ty, a * tz], [0.0, 2.0 * a * ty, a * tz], [-a * tx, -a * ty, a * tz]]
+            } else {
+                let u = tz - 2.0 * 2f64.sqrt() * ty;
+                let v = tz + 2f64.sqrt() * ty;
+                let s = a / 3f64.sqrt();
+                [[s * u, s * v, s * v], [s * v, s * u, s * v], [s * v, s * v, s * u]]
+            }
+        }
+        6 => {
+            let c = requires(c, "celldm(3)")?;
+            [[a, 0.0, 0.0], [0.0, a, 0.0], [0.0, 0.0, c]]
+        }
+        7 => {
+            let c = requires(c, "celldm(3)")?;
+            [[a / 2.0, -a / 2.0, c / 2.0], [a / 2.0, a / 2.0, c / 2.0], [-a / 2.0, -a / 2.0, c / 2.0]]
+        }
+        8 | 9 | -9 | 91 | 10 | 11 => {
+            let b = requires(b, "celldm(2)")?;
+            let c = requires(c, "celldm(3)")?;
+            match ibrav {
+                8 => [[a, 0.0, 0.0], [0.0, b, 0.0], [0.0, 0.0, c]],
+                9 => [[a / 2.0, b / 2.0, 0.0], [-a / 2.0, b / 2.0, 0.0], [0.0, 0.0, c]],
+                -9 => [[a / 2.0, -b / 2.0, 0.0], [a / 2.0, b / 2.0, 0.0], [0.0, 0.0, c]],
+                91 => [[a, 0.0, 0.0], [0.0, b / 2.0, -c / 2.0], [0.0, b / 2.0, c / 2.0]],
+                10 => [[a / 2.0, 0.0, c / 2.0], [a / 2.0, b / 2.0, 0.0], [0.0, b / 2.0, c / 2.0]],
+                _ => [[a / 2.0, b / 2.0, c / 2.0], [-a / 2.0, b / 2.0, c / 2.0], [-a / 2.0, -b / 2.0, c / 2.0]],
+            }
+        }
+        12 | 13 => {
+            let b = requires(b, "celldm(2)")?;
+            let c = requires(c, "celldm(3)")?;
+            let cos = celldm[3];
+            let sin = (1.0 - cos * cos).sqrt();
+            if ibrav == 12 {
+                [[a, 0.0, 0.0], [b * cos, b * sin, 0.0], [0.0, 0.0, c]]
+            } else {
+                [[a / 2.0, 0.0, -c / 2.0], [b * cos, b * sin, 0.0], [a / 2.0, 0.0, c / 2.0]]
+            }
+        }
+        -12 | -13 => {
+            let b = requires(b, "celldm(2)")?;
+            let c = requires(c, "celldm(3)")?;
+            let cos = celldm[4];
+            let sin = (1.0 - cos * cos).sqrt();
+            if ibrav == -12 {
+                [[a, 0.0, 0.0], [0.0, b, 0.0], [c * cos, 0.0, c * sin]]
+            } else {
+                [[a / 2.0, b / 2.0, 0.0], [-a / 2.0, b / 2.0, 0.0], [c * cos, 0.0, c * sin]]
+            }
+        }
+        14 => {
+            let b = requires(b, "celldm(2)")?;
+            let c = requires(c, "celldm(3)")?;
+            let [cos_alpha, cos_beta, cos_gamma] = [celldm[3], celldm[4], celldm[5]];
+            let sin_gamma = (1.0 - cos_gamma * cos_gamma).sqrt();
+            let volume_term = 1.0 + 2.0 * cos_alpha * cos_beta * cos_gamma
+                - cos_alpha * cos_alpha
+                - cos_beta * cos_beta
+                - cos_gamma * cos_gamma;
+            if volume_term <= 0.0 || sin_gamma == 0.0 {
+                return Err("Invalid triclinic angles".to_string());
+            }
+            [
+                [a, 0.0, 0.0],
+                [b * cos_gamma, b * sin_gamma, 0.0],
+                [
+                    c * cos_beta,
+                    c * (cos_alpha - cos_beta * cos_gamma) / sin_gamma,
+                    c * volume_term.sqrt() / sin_gamma,
+                ],
+            ]
+        }
+        other => return Err(format!("Unsupported ibrav = {}", other)),
+    };
+    Ok(lattice)
+}
+
+/// 读取名为 name 的 namelist（不区分大小写），返回小写键名到原始值的映射
+/// 键值对以逗号或换行分隔，可与 &NAME、结束符 / 写在同一行，字符串值去掉引号
+fn namelist(lines: &[&str], name: &str) -> Option<HashMap<String, String>> {
+    let header = format!("&{}", name);
+    let start = lines.iter().position(|line| line.to_lowercase().starts_with(&header))?;
+    let mut entries = HashMap::new();
+    for (i, line) in lines[start..].iter().enumerate() {
+        let mut line = if i == 0 { &line[header.len()..] } else { line };
+        if i > 0 && line.starts_with('&') {
+            break;
+        }
+        let end = line.ends_with('/');
+        if end {
+            line = &line[..line.len() - 1];
+        }
+        for item in line.split(',') {
+            if let Some((key, value)) = item.split_once('=') {
+                let key = key.trim().to_lowercase().replace(' ', "");
+                let value = value.trim().trim_matches(['\'', '"']).to_string();
+                entries.insert(key, value);
+            }
+        }
+        if end {
             break;
         }
+    }
+    Some(entries)
+}
 
-        let parts: Vec<&str> = l.split_whitespace().collect();
-        let name = parts[0].to_string();
-        let x = parts[1].parse::<f64>().expect("Failed to parse x coordinate");
-        let y = parts[2].parse::<f64>().expect("Failed to parse y coordinate");
-        let z = parts[3].parse::<f64>().expect("Failed to parse z coordinate");
+/// 读取卡片：返回小写的单位选项（花括号或圆括号内，可省略）与卡片之后直到下一个卡片或 namelist 的非空行
+fn card(lines: &[&str], name: &str) -> Option<(String, Vec<String>)> {
+    let start = lines.iter().position(|line| line.to_uppercase().starts_with(name))?;
+    let option = lines[start][name.len()..]
+        .trim()
+        .trim_matches(['{', '}', '(', ')'])
+        .trim()
+        .to_lowercase();
+    let rows = lines[start + 1..]
+        .iter()
+        .filter(|line| !line.is_empty())
+        .take_while(|line| !CARDS.iter().any(|card| line.to_uppercase().starts_with(card)) && !line.starts_with('&'))
+        .map(|line| line.to_string())
+        .collect();
+    Some((option, rows))
+}
 
-        let atom = Atom {
-            name,
-            x,
-            y,
-            z,
-            radius: 0.5, // 默认值，后续可以根据原子名称设置
-            color: "#FFFFFF".to_string(), // 默认颜色
-        };
+/// pw.x 输入文件的卡片名
+const CARDS: [&str; 10] = [
+    "ATOMIC_SPECIES",
+    "ATOMIC_POSITIONS",
+    "K_POINTS",
+    "CELL_PARAMETERS",
+    "OCCUPATIONS",
+    "CONSTRAINTS",
+    "ATOMIC_VELOCITIES",
+    "ATOMIC_FORCES",
+    "ADDITIONAL_K_POINTS",
+    "HUBBARD",
+];
 
-        atoms.push(atom);
+/// 解析 K_POINTS 卡片
+fn parse_k_points(mode: String, rows: &[String]) -> Result<KPoints, String> {
+    let mode = if mode.is_empty() { "tpiba".to_string() } else { mode };
+    let mut k_points = KPoints { mode, grid: None, shift: None, points: Vec::new() };
+    match k_points.mode.as_str() {
+        "gamma" => {}
+        "automatic" => {
+            let values: Vec<usize> = rows
+                .first()
+                .ok_or("Missing automatic k-point grid")?
+                .split_whitespace()
+                .map(|v| v.parse().map_err(|_| format!("Invalid k-point grid value {}", v)))
+                .collect::<Result<_, String>>()?;
+            if values.len() < 6 {
+                return Err("Automatic k-points need six integers".to_string());
+            }
+            k_points.grid = Some([values[0], values[1], values[2]]);
+            k_points.shift = Some([values[3], values[4], values[5]]);
+        }
+        _ => {
+            let count: usize = rows
+                .first()
+                .and_then(|row| row.trim().parse().ok())
+                .ok_or("Missing number of k-points")?;
+            for row in rows.iter().skip(1).take(count) {
+                let values: Vec<f64> = row
+                    .split_whitespace()
+                    .take(4)
+                    .map(|v| fortran_number(v).ok_or_else(|| format!("Invalid k-point value {}", v)))
+                    .collect::<Result<_, String>>()?;
+                if values.len() < 4 {
+                    return Err(format!("Invalid k-point line \"{}\"", row));
+                }
+                k_points.points.push([values[0], values[1], values[2], values[3]]);
+            }
+        }
     }
+    Ok(k_points)
+}
 
-    atoms
+/// 解析 Fortran 数值，支持 1.0d0 形式的指数
+fn fortran_number(text: &str) -> Option<f64> {
+    text.trim().replace(['d', 'D'], "e").parse().ok()
 }
 
-/// 将原子的分数坐标转换为笛卡尔坐标
-fn convert_to_cartesian(atoms: Vec<Atom>, cell: [[f64; 3]; 3], alat: f64) -> Vec<Atom> {
-    atoms
-        .into_iter()
-        .map(|mut atom| {
-            let frac_x = atom.x;
-            let frac_y = atom.y;
-            let frac_z = atom.z;
+/// 由文件名判断是否为 pw.x 输入文件（.in/.pwi）
+pub fn is_qe_input(file_name: &str) -> bool {
+    file_name.ends_with(".in") || file_name.ends_with(".pwi")
+}
 
-            // 笛卡尔坐标 = 分数坐标 * 基矢量 * alat
-            atom.x = frac_x * cell[0][0] * alat
-                + frac_y * cell[1][0] * alat
-                + frac_z * cell[2][0] * alat;
-            atom.y = frac_x * cell[0][1] * alat
-                + frac_y * cell[1][1] * alat
-                + frac_z * cell[2][1] * alat;
-            atom.z = frac_x * cell[0][2] * alat
-                + frac_y * cell[1][2] * alat
-                + frac_z * cell[2][2] * alat;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            atom
-        })
-        .collect()
-}
-
-/// 计算晶格顶点
-fn calculate_lattice_vertices(cell: [[f64; 3]; 3]) -> Vec<(f64, f64, f64)> {
-    let a = cell[0];
-    let b = cell[1];
-    let c = cell[2];
-
-    vec![
-        (0.0, 0.0, 0.0),
-        (a[0], a[1], a[2]),
-        (a[0] + b[0], a[1] + b[1], a[2] + b[2]),
-        (b[0], b[1], b[2]),
-        (c[0], c[1], c[2]),
-        (a[0] + c[0], a[1] + c[1], a[2] + c[2]),
-        (a[0] + b[0] + c[0], a[1] + b[1] + c[1], a[2] + b[2] + c[2]),
-        (b[0] + c[0], b[1] + c[1], b[2] + c[2]),
-    ]
-}
\ No newline at end of file
+    /// 以 alat 为单位比较晶格，期望值取自 pw.x 输出的 crystal axes
+    fn assert_axes(ibrav: i32, celldm: [f64; 6], expected: [[f64; 3]; 3]) {
+        let alat = celldm[0] * BOHR_TO_ANGSTROM;
+        let lattice = ibrav_lattice(ibrav, celldm).unwrap();
+        for (row, expected_row) in lattice.iter().zip(expected.iter()) {
+            for (value, expected_value) in row.iter().zip(expected_row.iter()) {
+                assert!(
+                    (value / alat - expected_value).abs() < 1e-6,
+                    "ibrav = {}: {:?} != {:?}",
+                    ibrav,
+                    lattice.map(|row| row.map(|v| v / alat)),
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn fcc_axes() {
+        assert_axes(2, [10.2, 0.0, 0.0, 0.0, 0.0, 0.0], [[-0.5, 0.0, 0.5], [0.0, 0.5, 0.5], [-0.5, 0.5, 0.0]]);
+    }
+
+    #[test]
+    fn trigonal_axes() {
+        let celldm = [10.0, 0.0, 0.0, 0.3, 0.0, 0.0];
+        assert_axes(
+            5,
+            celldm,
+            [
+                [0.591608, -0.341565, 0.730297],
+                [0.000000, 0.683130, 0.730297],
+                [-0.591608, -0.341565, 0.730297],
+            ],
+        );
+        assert_axes(
+            -5,
+            celldm,
+            [
+                [-0.136136, 0.700524, 0.700524],
+                [0.700524, -0.136136, 0.700524],
+                [0.700524, 0.700524, -0.136136],
+            ],
+        );
+        // 夹角 60° 的三方格子即面心立方的原胞
+        let half = 0.5f64.sqrt();
+        assert_axes(-5, [10.0, 0.0, 0.0, 0.5, 0.0, 0.0], [[0.0, half, half], [half, 0.0, half], [half, half, 0.0]]);
+    }
+
+    #[test]
+    fn base_centered_monoclinic_axes() {
+        assert_axes(
+            13,
+            [10.0, 1.2, 1.5, 0.2, 0.0, 0.0],
+            [[0.5, 0.0, -0.75], [0.24, 1.175755, 0.0], [0.5, 0.0, 0.75]],
+        );
+    }
+
+    #[test]
+    fn triclinic_axes() {
+        assert_axes(
+            14,
+            [10.0, 1.1, 1.3, 0.1, 0.2, 0.3],
+            [[1.0, 0.0, 0.0], [0.33, 1.049333, 0.0], [0.26, 0.054511, 1.272568]],
+        );
+    }
+
+    #[test]
+    fn invalid_ibrav() {
+        assert!(ibrav_lattice(5, [10.0, 0.0, 0.0, -0.6, 0.0, 0.0]).is_err());
+        assert!(ibrav_lattice(14, [10.0, 1.0, 1.0, 0.9, 0.9, -0.9]).is_err());
+        assert!(ibrav_lattice(2, [0.0; 6]).is_err());
+        assert!(ibrav_lattice(15, [10.0, 0.0, 0.0, 0.0, 0.0, 0.0]).is_err());
+    }
+
+    #[test]
+    fn silicon_input() {
+        let input = "\
+&CONTROL
+  calculation = 'scf'
+/
+&SYSTEM ibrav = 2, celldm(1) = 10.26d0, nat = 2, ntyp = 1 /
+&ELECTRONS
+/
+ATOMIC_SPECIES
+Si 28.086 Si.pbe-n-rrkjus_psl.1.0.0.UPF
+ATOMIC_POSITIONS {alat}
+Si 0.00 0.00 0.00
+Si 0.25 0.25 0.25 ! 注释
+K_POINTS automatic
+4 4 4 1 1 1
+";
+        let input = read_qe_input(input).unwrap();
+        assert_eq!(input.ibrav, 2);
+        assert_eq!(input.species.len(), 1);
+        assert_eq!(input.species[0].pseudopotential, "Si.pbe-n-rrkjus_psl.1.0.0.UPF");
+        assert_eq!(input.sites.len(), 2);
+        assert_eq!(input.sites[1].element, "Si");
+        // pw.x 的面心立方基矢量下，alat 坐标 (1/4, 1/4, 1/4) 的分数坐标
+        for (value, expected) in input.sites[1].frac.iter().zip([-0.25, 0.75, -0.25]) {
+            assert!((value - expected).abs() < 1e-9);
+        }
+        let k_points = input.k_points.unwrap();
+        assert_eq!(k_points.grid, Some([4, 4, 4]));
+        assert_eq!(k_points.shift, Some([1, 1, 1]));
+    }
+
+    #[test]
+    fn cell_parameters_input() {
+        let input = "\
+&system
+  ibrav = 0
+  nat = 2
+  ntyp = 2
+/
+CELL_PARAMETERS angstrom
+  4.0 0.0 0.0
+  0.0 4.0 0.0
+  0.0 0.0 6.0
+ATOMIC_POSITIONS angstrom
+Na 0.0 0.0 0.0
+Cl 2.0 2.0 3.0
+K_POINTS gamma
+";
+        let input = read_qe_input(input).unwrap();
+        assert_eq!(input.lattice, [[4.0, 0.0, 0.0], [0.0, 4.0, 0.0], [0.0, 0.0, 6.0]]);
+        assert_eq!(input.sites[1].element, "Cl");
+        assert_eq!(input.sites[1].frac, [0.5, 0.5, 0.5]);
+        assert_eq!(input.k_points.unwrap().mode, "gamma");
+        assert!(read_qe_input("&system\n ibrav = 0, nat = 1, ntyp = 1\n/\n").is_err());
+    }
+}
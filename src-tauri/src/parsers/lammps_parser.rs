@@ -0,0 +1,422 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use crate::parsers::atom_config::{CrystalStructure, Site};
+use crate::parsers::parser_utils::{build_crystal_structure, element_symbol, load_atom_config};
+use crate::parsers::trajectory::{parse_triple, Frame, FrameOffset, LineReader, TrajectoryHeader};
+use crate::utils::elements::{element, ELEMENTS};
+use crate::utils::geometry::{cart_to_frac, wrap_frac};
+use crate::utils::linalg::{sub, Mat3, Vec3};
+
+/// 由原子量识别元素时允许的最大偏差（g/mol）
+const MASS_TOLERANCE: f64 = 0.5;
+
+/// data 文件中各节的标题
+const DATA_SECTIONS: [&str; 16] = [
+    "Atoms",
+    "Velocities",
+    "Masses",
+    "Ellipsoids",
+    "Lines",
+    "Triangles",
+    "Bodies",
+    "Bonds",
+    "Angles",
+    "Dihedrals",
+    "Impropers",
+    "Pair Coeffs",
+    "PairIJ Coeffs",
+    "Bond Coeffs",
+    "Angle Coeffs",
+    "Dihedral Coeffs",
+];
+
+/// 扫描 LAMMPS dump 文件，记录每个 ITEM: TIMESTEP 的位置，原子行直接跳过
+/// 文件末尾不完整的帧（仍在写入的 MD 轨迹）被忽略
+pub fn index_lammps_dump(reader: &mut LineReader) -> Result<(Vec<FrameOffset>, TrajectoryHeader), String> {
+    let mut frames = Vec::new();
+    let mut atom_count = 0;
+    loop {
+        let offset = reader.position();
+        let Some(line) = reader.next_line()? else { break };
+        let line = line.trim();
+        if line.starts_with("ITEM: TIMESTEP") {
+            frames.push(FrameOffset { start: offset, header: None });
+        } else if line.starts_with("ITEM: NUMBER OF ATOMS") {
+            let count = reader.expect_line()?.trim();
+            atom_count = count.parse().map_err(|_| format!("Invalid atom count {}", count))?;
+        } else if line.starts_with("ITEM: ATOMS") && reader.skip_lines(atom_count)? < atom_count {
+            frames.pop();
+            break;
+        }
+    }
+    Ok((frames, TrajectoryHeader::default()))
+}
+
+/// 读取 LAMMPS dump 的一帧，原子按 id 排序以保证各帧顺序一致
+/// 坐标列优先使用未折回的 xu/xsu，其次为 x/xs 加镜像标记 ix/iy/iz；
+/// 元素取自 element 列，没有时按原子类型查找映射，仍未找到时以类型编号作为元素名
+pub fn read_lammps_dump_frame(reader: &mut LineReader, offset: FrameOffset, header: &TrajectoryHeader) -> Result<Frame, String> {
+    reader.seek(offset.start)?;
+    reader.expect_line()?;
+    let mut atom_count = 0;
+    let mut cell: Option<(Mat3, Vec3)> = None;
+
+    loop {
+        let line = reader.expect_line()?.trim().to_string();
+        if line.starts_with("ITEM: NUMBER OF ATOMS") {
+            let count = reader.expect_line()?.trim();
+            atom_count = count.parse().map_err(|_| format!("Invalid atom count {}", count))?;
+        } else if line.starts_with("ITEM: BOX BOUNDS") {
+            let mut bounds = [[0.0; 3]; 3];
+            for row in bounds.iter_mut() {
+                let values: Vec<f64> = reader
+                    .expect_line()?
+                    .split_whitespace()
+                    .map(|v| v.parse().map_err(|_| format!("Invalid box bound {}", v)))
+                    .collect::<Result<_, String>>()?;
+                if values.len() < 2 {
+                    return Err("Invalid box bounds".to_string());
+                }
+                *row = [values[0], values[1], values.get(2).copied().unwrap_or(0.0)];
+            }
+            let tilts = if line.contains("xy") { [bounds[0][2], bounds[1][2], bounds[2][2]] } else { [0.0; 3] };
+            cell = Some(box_lattice(bounds.map(|row| [row[0], row[1]]), tilts));
+        } else if let Some(names) = line.strip_prefix("ITEM: ATOMS") {
+            let names: Vec<&str> = names.split_whitespace().collect();
+            let (lattice, origin) = cell.ok_or("Missing box bounds")?;
+            return read_atoms(reader, &names, atom_count, lattice, origin, header);
+        }
+    }
+}
+
+/// 由 dump 的盒子边界与倾斜因子 (xy, xz, yz) 计算晶格矩阵与盒子原点
+/// 三斜盒子的边界为包围盒，需先扣除倾斜因子的贡献
+pub fn box_lattice(bounds: [[f64; 2]; 3], tilts: [f64; 3]) -> (Mat3, Vec3) {
+    let [xy, xz, yz] = tilts;
+    let xlo = bounds[0][0] - [0.0, xy, xz, xy + xz].into_iter().fold(f64::INFINITY, f64::min);
+    let xhi = bounds[0][1] - [0.0, xy, xz, xy + xz].into_iter().fold(f64::NEG_INFINITY, f64::max);
+    let ylo = bounds[1][0] - yz.min(0.0);
+    let yhi = bounds[1][1] - yz.max(0.0);
+    triclinic_lattice([[xlo, xhi], [ylo, yhi], bounds[2]], tilts)
+}
+
+/// 由盒子边界 lo、hi 与倾斜因子计算晶格矩阵与盒子原点
+/// a = (lx, 0, 0)，b = (xy, ly, 0)，c = (xz, yz, lz)
+pub fn triclinic_lattice(bounds: [[f64; 2]; 3], tilts: [f64; 3]) -> (Mat3, Vec3) {
+    let [xy, xz, yz] = tilts;
+    let [lx, ly, lz] = bounds.map(|[lo, hi]| hi - lo);
+    let lattice = [[lx, 0.0, 0.0], [xy, ly, 0.0], [xz, yz, lz]];
+    (lattice, bounds.map(|[lo, _]| lo))
+}
+
+/// 读取 ITEM: ATOMS 之后的原子行
+fn read_atoms(
+    reader: &mut LineReader,
+    names: &[&str],
+    count: usize,
+    lattice: Mat3,
+    origin: Vec3,
+    header: &TrajectoryHeader,
+) -> Result<Frame, String> {
+    let column = |name: &str| names.iter().position(|n| *n == name);
+    let triple = |a: &str, b: &str, c: &str| Some([column(a)?, column(b)?, column(c)?]);
+    // 坐标列与是否为分数坐标
+    let (position_columns, scaled) = if let Some(columns) = triple("xu", "yu", "zu") {
+        (columns, false)
+    } else if let Some(columns) = triple("xsu", "ysu", "zsu") {
+        (columns, true)
+    } else if let Some(columns) = triple("x", "y", "z") {
+        (columns, false)
+    } else if let Some(columns) = triple("xs", "ys", "zs") {
+        (columns, true)
+    } else {
+        return Err("Missing coordinate columns".to_string());
+    };
+    let unwrapped = names.contains(&"xu") || names.contains(&"xsu");
+    let image_columns = if unwrapped { None } else { triple("ix", "iy", "iz") };
+    let velocity_columns = triple("vx", "vy", "vz");
+    let (id_column, type_column, element_column) = (column("id"), column("type"), column("element"));
+
+    let mut atoms = Vec::with_capacity(count);
+    for n in 0..count {
+        let line = reader.expect_line()?;
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let number = |column: usize| -> Result<f64, String> {
+            let token = parts.get(column).ok_or("Too few columns in atom line")?;
+            token.parse().map_err(|_| format!("Invalid number {}", token))
+        };
+        let vector = |columns: [usize; 3]| -> Result<Vec3, String> {
+            Ok([number(columns[0])?, number(columns[1])?, number(columns[2])?])
+        };
+        let id = match id_column {
+            Some(column) => number(column)? as usize,
+            None => n + 1,
+        };
+        let (element, label) = match (element_column, type_column) {
+            (Some(column), _) => {
+                let element = element_symbol(parts.get(column).ok_or("Missing element")?);
+                (element.clone(), format!("{}{}", element, id))
+            }
+            (None, Some(column)) => {
+                let atom_type = parts.get(column).ok_or("Missing atom type")?.to_string();
+                match header.type_elements.get(&atom_type) {
+                    Some(element) => (element.clone(), format!("{}{}", element, id)),
+                    None => (atom_type.clone(), format!("{}_{}", atom_type, id)),
+                }
+            }
+            (None, None) => ("X".to_string(), format!("X{}", id)),
+        };
+        let position = vector(position_columns)?;
+        let mut frac = if scaled { position } else { cart_to_frac(&lattice, sub(position, origin)) };
+        if let Some(columns) = image_columns {
+            let image = vector(columns)?;
+            frac = [0, 1, 2].map(|k| frac[k] + image[k]);
+        }
+        let velocity = velocity_columns.map(vector).transpose()?;
+        atoms.push((id, element, label, frac, velocity));
+    }
+    atoms.sort_by_key(|atom| atom.0);
+
+    let velocities = velocity_columns.map(|_| atoms.iter().map(|atom| atom.4.unwrap_or_default()).collect());
+    let sites = atoms
+        .into_iter()
+        .map(|(_, element, label, frac, _)| Site::new(label, element, frac))
+        .collect();
+    Ok(Frame { lattice, sites, velocities })
+}
+
+/// 解析 LAMMPS data 文件，支持 atomic、charge、full 三种原子风格与三斜盒子
+/// 元素由 Masses 节的注释（如 1 22.990 # Na）或原子量识别，无法识别时以类型编号作为元素名
+pub fn parse_lammps_data(app_handle: &AppHandle, file_path: &PathBuf) -> Result<CrystalStructure, String> {
+    let content = fs::read_to_string(file_path).map_err(|e| format!("Failed to read {}: {}", file_path.display(), e))?;
+    let (lattice, sites) = read_lammps_data(&content)?;
+    Ok(build_crystal_structure(lattice, sites, &load_atom_config(app_handle)))
+}
+
+/// 解析 data 文件文本，返回晶格与按原子编号排序的位点
+pub fn read_lammps_data(content: &str) -> Result<(Mat3, Vec<Site>), String> {
+    let mut atom_count: Option<usize> = None;
+    let mut bounds = [[0.0, 1.0]; 3];
+    let mut tilts = [0.0; 3];
+    let sections = data_sections(content);
+
+    // 文件头：第一行为注释，之后到第一个节标题之前为 "数值 关键字" 行
+    for line in content.lines().skip(1) {
+        let text = strip_comment(line);
+        if DATA_SECTIONS.contains(&text) {
+            break;
+        }
+        let values: Vec<f64> = text.split_whitespace().map_while(|v| v.parse().ok()).collect();
+        if text.ends_with(" atoms") {
+            atom_count = values.first().map(|&n| n as usize);
+        } else if text.ends_with("xlo xhi") && values.len() >= 2 {
+            bounds[0] = [values[0], values[1]];
+        } else if text.ends_with("ylo yhi") && values.len() >= 2 {
+            bounds[1] = [values[0], values[1]];
+        } else if text.ends_with("zlo zhi") && values.len() >= 2 {
+            bounds[2] = [values[0], values[1]];
+        } else if text.ends_with("xy xz yz") && values.len() >= 3 {
+            tilts = [values[0], values[1], values[2]];
+        }
+    }
+    let (lattice, origin) = triclinic_lattice(bounds, tilts);
+
+    let type_elements = sections.get("Masses").map(|(_, rows)| masses_to_elements(rows)).unwrap_or_default();
+    let (style, rows) = sections.get("Atoms").ok_or("Missing Atoms section")?;
+    let mut atoms: Vec<(usize, Site)> = rows
+        .iter()
+        .map(|row| {
+            let parts: Vec<&str> = strip_comment(row).split_whitespace().collect();
+            // 各风格中 type 与 x 所在的列；未注明风格时按列数判断，末尾可能附带 3 个镜像标记
+            let style = match style.as_str() {
+                "" => match parts.len() {
+                    5 | 8 => "atomic",
+                    6 | 9 => "charge",
+                    _ => "full",
+                },
+                style => style,
+            };
+            let (type_column, x_column) = match style {
+                "atomic" => (1, 2),
+                "charge" => (1, 3),
+                "full" => (2, 4),
+                other => return Err(format!("Unsupported atom style {}", other)),
+            };
+            if parts.len() < x_column + 3 {
+                return Err(format!("Too few columns in atom line \"{}\"", row));
+            }
+            let id: usize = parts[0].parse().map_err(|_| format!("Invalid atom id {}", parts[0]))?;
+            let atom_type = parts[type_column].to_string();
+            let cart = parse_triple(&parts[x_column..x_column + 3].join(" "))?;
+            let frac = wrap_frac(cart_to_frac(&lattice, sub(cart, origin)));
+            let (element, label) = match type_elements.get(&atom_type) {
+                Some(element) => (element.clone(), format!("{}{}", element, id)),
+                None => (atom_type.clone(), format!("{}_{}", atom_type, id)),
+            };
+            Ok((id, Site::new(label, element, frac)))
+        })
+        .collect::<Result<_, String>>()?;
+    if let Some(count) = atom_count {
+        if count != atoms.len() {
+            return Err(format!("Expected {} atoms, found {}", count, atoms.len()));
+        }
+    }
+    atoms.sort_by_key(|(id, _)| *id);
+    Ok((lattice, atoms.into_iter().map(|(_, site)| site).collect()))
+}
+
+/// 查找与 dump 文件同目录的 data 文件（*.data、*.lmp、data.*），由其 Masses 节得到原子类型到元素的映射
+pub fn sibling_type_elements(dump_path: &Path) -> HashMap<String, String> {
+    let Some(directory) = dump_path.parent() else { return HashMap::new() };
+    let Ok(entries) = fs::read_dir(directory) else { return HashMap::new() };
+    let mut candidates: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            let name = path.file_name().map(|n| n.to_string_lossy().to_lowercase()).unwrap_or_default();
+            is_lammps_data(&name)
+        })
+        .collect();
+    candidates.sort();
+    candidates
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .filter_map(|content| data_sections(&content).get("Masses").map(|(_, rows)| masses_to_elements(rows)))
+        .find(|map| !map.is_empty())
+        .unwrap_or_default()
+}
+
+/// 由文件名判断是否为 LAMMPS data 文件
+pub fn is_lammps_data(file_name: &str) -> bool {
+    file_name.ends_with(".data") || file_name.ends_with(".lmp") || file_name.starts_with("data.")
+}
+
+/// 把 data 文件分为各节，返回 节标题 -> (标题行注释, 数据行)
+/// 数据行为去掉注释后的非空行，遇到下一个节标题时结束
+fn data_sections(content: &str) -> HashMap<String, (String, Vec<String>)> {
+    let mut sections: HashMap<String, (String, Vec<String>)> = HashMap::new();
+    let mut current: Option<String> = None;
+    for line in content.lines().skip(1) {
+        let text = strip_comment(line);
+        if DATA_SECTIONS.contains(&text) {
+            let hint = line.split_once('#').map(|(_, c)| c.trim().to_lowercase()).unwrap_or_default();
+            sections.insert(text.to_string(), (hint, Vec::new()));
+            current = Some(text.to_string());
+        } else if !text.is_empty() {
+            if let Some(section) = current.as_ref().and_then(|name| sections.get_mut(name)) {
+                // 保留行尾注释，Masses 节用它给出元素符号
+                section.1.push(line.trim().to_string());
+            }
+        }
+    }
+    sections
+}
+
+/// 由 Masses 节识别各原子类型的元素：优先使用注释中的元素符号，其次按最接近的原子量
+fn masses_to_elements(rows: &[String]) -> HashMap<String, String> {
+    rows.iter()
+        .filter_map(|row| {
+            let (text, comment) = row.split_once('#').unwrap_or((row, ""));
+            let mut parts = text.split_whitespace();
+            let atom_type = parts.next()?.to_string();
+            let mass: f64 = parts.next()?.parse().ok()?;
+            let from_comment = comment
+                .split_whitespace()
+                .next()
+                .map(element_symbol)
+                .filter(|symbol| element(symbol).is_some());
+            let from_mass = || {
+                ELEMENTS
+                    .iter()
+                    .min_by(|a, b| (a.mass - mass).abs().total_cmp(&(b.mass - mass).abs()))
+                    .filter(|e| (e.mass - mass).abs() < MASS_TOLERANCE)
+                    .map(|e| e.symbol.to_string())
+            };
+            Some((atom_type, from_comment.or_else(from_mass)?))
+        })
+        .collect()
+}
+
+/// 去掉行尾注释与首尾空白
+fn strip_comment(line: &str) -> &str {
+    line.split('#').next().unwrap_or_default().trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::measure::measure_distance;
+    use crate::utils::geometry::{cell_volume, lattice_from_parameters};
+    use crate::writers::lammps_writer::write_lammps_data;
+
+    #[test]
+    fn triclinic_bounding_box() {
+        // lx = 10、ly = 8、lz = 6，xy = 2、xz = -1、yz = 1.5 的盒子在 dump 中写出的包围盒
+        let bounds = [[-1.0, 12.0], [0.0, 9.5], [0.0, 6.0]];
+        let (lattice, origin) = box_lattice(bounds, [2.0, -1.0, 1.5]);
+        assert_eq!(lattice, [[10.0, 0.0, 0.0], [2.0, 8.0, 0.0], [-1.0, 1.5, 6.0]]);
+        assert_eq!(origin, [0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn full_style_data() {
+        let content = "\
+LAMMPS data file
+
+2 atoms
+2 atom types
+
+0.0 4.0 xlo xhi
+0.0 4.0 ylo yhi
+0.0 4.0 zlo zhi
+
+Masses
+
+1 22.990 # Na
+2 35.45
+
+Atoms # full
+
+2 1 2 -1.0 2.0 2.0 2.0 0 0 0
+1 1 1 1.0 0.0 0.0 -4.0 0 0 -1
+";
+        let (lattice, sites) = read_lammps_data(content).unwrap();
+        assert_eq!(lattice, [[4.0, 0.0, 0.0], [0.0, 4.0, 0.0], [0.0, 0.0, 4.0]]);
+        assert_eq!(sites[0].element, "Na");
+        assert_eq!(sites[0].frac, [0.0, 0.0, 0.0]);
+        // 未注明元素的类型由原子量识别
+        assert_eq!(sites[1].element, "Cl");
+        assert_eq!(sites[1].frac, [0.5, 0.5, 0.5]);
+        assert!(read_lammps_data(&content.replace("2 atoms", "3 atoms")).is_err());
+    }
+
+    #[test]
+    fn triclinic_round_trip() {
+        // 倾斜因子超出 LAMMPS 允许范围的斜交晶胞，写出时需要约化
+        let lattice = lattice_from_parameters(4.0, 5.0, 6.0, 70.0, 105.0, 130.0);
+        let sites = vec![
+            Site::new("Na1".to_string(), "Na".to_string(), [0.1, 0.2, 0.3]),
+            Site::new("Cl2".to_string(), "Cl".to_string(), [0.6, 0.9, 0.1]),
+            Site::new("O3".to_string(), "O".to_string(), [0.95, 0.05, 0.7]),
+        ];
+        let structure = build_crystal_structure(lattice, sites, &HashMap::new());
+        let data = write_lammps_data(&structure, "round trip", "atomic").unwrap();
+        let (read_lattice, read_sites) = read_lammps_data(&data).unwrap();
+
+        let [lx, ly] = [read_lattice[0][0], read_lattice[1][1]];
+        assert!(read_lattice[1][0].abs() <= lx / 2.0 + 1e-9);
+        assert!(read_lattice[2][0].abs() <= lx / 2.0 + 1e-9);
+        assert!(read_lattice[2][1].abs() <= ly / 2.0 + 1e-9);
+        assert!((cell_volume(&read_lattice) - cell_volume(&lattice)).abs() < 1e-6);
+
+        let elements: Vec<&str> = read_sites.iter().map(|site| site.element.as_str()).collect();
+        assert_eq!(elements, ["Na", "Cl", "O"]);
+        let read = build_crystal_structure(read_lattice, read_sites, &HashMap::new());
+        for (i, j) in [(0, 1), (0, 2), (1, 2)] {
+            let expected = measure_distance(&structure, i, j).unwrap().value;
+            let distance = measure_distance(&read, i, j).unwrap().value;
+            assert!((distance - expected).abs() < 1e-6, "{}-{}: {} != {}", i, j, distance, expected);
+        }
+    }
+}
@@ -2,4 +2,8 @@ pub mod parser_utils;
 pub mod atom_config;
 pub mod cif_parser;
 pub mod espresso_parser; // (预留) Quantum ESPRESSO 解析器
-pub mod vasp_parser; // (预留) VASP 解析器
\ No newline at end of file
+pub mod cube_parser;
+pub mod lammps_parser;
+pub mod trajectory;
+pub mod vasp_parser; // (预留) VASP 解析器
+pub mod xyz_parser;
\ No newline at end of file
@@ -1,23 +1,108 @@
-use std::fs;
 use std::collections::HashMap;
-use tauri::{path::BaseDirectory, AppHandle, Manager};
-use crate::parsers::atom_config::{Atom, AtomConfig};
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+use crate::analysis::fragments::fragment_positions;
+use crate::parsers::atom_config::{Atom, AtomConfig, CrystalStructure, Site};
+use crate::state::{AppState, DisplaySettings, UserStyles};
+use crate::utils::elements::ELEMENTS;
+use crate::utils::geometry::{calculate_cell_vertices, frac_to_cart, get_lattice_center};
+
+/// 用户原子样式配置文件名，位于应用配置目录
+const USER_STYLES_FILE: &str = "atom_styles.json";
 
-/// 从配置文件中加载原子的半径和颜色
+/// 按当前显示设置与用户配置生成各元素的半径和颜色
 pub fn load_atom_config(app_handle: &AppHandle) -> HashMap<String, AtomConfig> {
-    let config_path = app_handle
-        .path()
-        .resolve("resources/atom_config.json", BaseDirectory::Resource)
-        .expect("Failed to resolve atom config file path");
-
-    // 读取配置文件内容
-    // reference:
-    // Atom Radius: https://crystalmaker.com/support/tutorials/atomic-radii/index.html
-    // Color: https://jmol.sourceforge.net/jscolors/
-    let config_content = fs::read_to_string(config_path).expect("Unable to read atom config file");
-    let config: HashMap<String, AtomConfig> =
-        serde_json::from_str(&config_content).expect("Invalid JSON format in atom config");
-    config
+    app_handle.state::<AppState>().atom_config()
+}
+
+/// 由元素数据库生成各元素的显示半径和颜色，再用用户配置覆盖
+/// 所选类型的半径缺失时依次改用共价半径、原子半径；按位点或属性着色时元素颜色取 Jmol 配色
+pub fn atom_styles(settings: &DisplaySettings, user_styles: &UserStyles) -> HashMap<String, AtomConfig> {
+    ELEMENTS
+        .iter()
+        .filter_map(|element| {
+            let user = user_styles.elements.get(element.symbol).cloned().unwrap_or_default();
+            let radius = user.radius.or_else(|| {
+                element
+                    .radius(&settings.radius_type)
+                    .or(element.covalent_radius)
+                    .or(element.atomic_radius)
+            })?;
+            let default_color = element.color(&settings.color_scheme).unwrap_or(element.jmol_color);
+            let config = AtomConfig {
+                radius,
+                color: user.color.unwrap_or_else(|| default_color.to_string()),
+                visible: user.visible.unwrap_or(true),
+            };
+            Some((element.symbol.to_string(), config))
+        })
+        .collect()
+}
+
+/// 用户原子样式配置文件的路径
+fn user_styles_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    let directory = app_handle.path().app_config_dir().map_err(|e| e.to_string())?;
+    Ok(directory.join(USER_STYLES_FILE))
+}
+
+/// 读取用户原子样式配置，文件不存在或无法解析时返回空配置
+pub fn load_user_styles(app_handle: &AppHandle) -> UserStyles {
+    let Ok(path) = user_styles_path(app_handle) else {
+        return UserStyles::default();
+    };
+    match fs::read_to_string(&path) {
+        Ok(text) => serde_json::from_str(&text).unwrap_or_else(|err| {
+            eprintln!("Failed to parse {}: {}", path.display(), err);
+            UserStyles::default()
+        }),
+        Err(_) => UserStyles::default(),
+    }
+}
+
+/// 保存用户原子样式配置，必要时创建配置目录
+pub fn save_user_styles(app_handle: &AppHandle, user_styles: &UserStyles) -> Result<(), String> {
+    let path = user_styles_path(app_handle)?;
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory).map_err(|e| e.to_string())?;
+    }
+    let text = serde_json::to_string_pretty(user_styles).map_err(|e| e.to_string())?;
+    fs::write(&path, text).map_err(|e| e.to_string())
+}
+
+/// 按新的半径和颜色重新生成结构的绘制数据，位点与晶格不变
+/// site_colors 给出时按位点逐个覆盖元素颜色，长度须与位点数一致；
+/// boundary_mode 决定晶胞边界处原子与分子的画法：cell / molecules / grow
+pub fn restyle_structure(
+    structure: &CrystalStructure,
+    atom_config: &HashMap<String, AtomConfig>,
+    site_colors: Option<&[String]>,
+    boundary_mode: &str,
+) -> CrystalStructure {
+    let styles = site_styles(&structure.sites, atom_config, site_colors);
+    let mut restyled = assemble_structure(structure.lattice, structure.sites.clone(), styles, boundary_mode);
+    restyled.cell_esd = structure.cell_esd;
+    restyled.volumetric = structure.volumetric.clone();
+    restyled
+}
+
+/// 各位点的绘制半径和颜色
+fn site_styles(
+    sites: &[Site],
+    atom_config: &HashMap<String, AtomConfig>,
+    site_colors: Option<&[String]>,
+) -> Vec<AtomConfig> {
+    sites
+        .iter()
+        .enumerate()
+        .map(|(i, site)| {
+            let mut config = lookup_atom_config(atom_config, &site.element);
+            if let Some(color) = site_colors.and_then(|colors| colors.get(i)) {
+                config.color = color.clone();
+            }
+            config
+        })
+        .collect()
 }
 
 /// 判断两个浮点数是否接近
@@ -66,4 +151,141 @@ pub fn replicate_boundary_atoms(atoms: &mut Vec<Atom>) {
 
     // 将新复制的原子添加到原子列表中
     atoms.extend(new_atoms);
-}
\ No newline at end of file
+}
+
+
+/// 查找原子的绘制半径和颜色，如果没有找到，使用默认值
+pub fn lookup_atom_config(atom_config: &HashMap<String, AtomConfig>, element: &str) -> AtomConfig {
+    atom_config.get(element).cloned().unwrap_or(AtomConfig {
+        radius: 0.35,
+        color: "#505050".to_string(),
+        visible: true,
+    })
+}
+
+/// 解析带标准差的数值，如 "5.4310(2)" -> (5.431, 0.0002)
+/// 没有括号时标准差为 0
+pub fn parse_value_with_esd(text: &str) -> Option<(f64, f64)> {
+    let text = text.trim();
+    match text.find('(') {
+        Some(open) => {
+            let number = &text[..open];
+            let value = number.parse::<f64>().ok()?;
+            let digits = text[open + 1..].trim_end_matches(')').parse::<f64>().ok()?;
+            // 标准差以数值最后一位为单位
+            let decimals = number
+                .split(['e', 'E'])
+                .next()
+                .and_then(|mantissa| mantissa.split('.').nth(1))
+                .map_or(0, |fraction| fraction.len());
+            Some((value, digits / 10f64.powi(decimals as i32)))
+        }
+        None => text.parse::<f64>().ok().map(|value| (value, 0.0)),
+    }
+}
+
+/// 从原子名称中提取元素符号，如 "Fe1" -> "Fe"、"O2-" -> "O"
+pub fn element_symbol(name: &str) -> String {
+    let letters: String = name
+        .trim()
+        .chars()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect();
+    let mut chars = letters.chars();
+    match chars.next() {
+        Some(first) => {
+            let mut symbol = first.to_ascii_uppercase().to_string();
+            // 元素符号最多两个字母，第二个字母小写
+            if let Some(second) = chars.next() {
+                if second.is_ascii_lowercase() {
+                    symbol.push(second);
+                }
+            }
+            symbol
+        }
+        None => name.trim().to_string(),
+    }
+}
+
+/// 由晶格和原子位点构建用于绘制的晶体结构
+/// 复制边界原子、转换为笛卡尔坐标，并将晶格中心平移到原点
+pub fn build_crystal_structure(
+    lattice: [[f64; 3]; 3],
+    sites: Vec<Site>,
+    atom_config: &HashMap<String, AtomConfig>,
+) -> CrystalStructure {
+    let styles = site_styles(&sites, atom_config, None);
+    assemble_structure(lattice, sites, styles, "cell")
+}
+
+/// 按逐位点的绘制样式构建晶体结构
+/// boundary_mode 为 cell 时画出晶胞内的位点并复制边界原子；
+/// molecules 与 grow 按成键关系画出完整分子（见 fragments::fragment_positions）
+fn assemble_structure(
+    lattice: [[f64; 3]; 3],
+    sites: Vec<Site>,
+    styles: Vec<AtomConfig>,
+    boundary_mode: &str,
+) -> CrystalStructure {
+    let (placed, cell_sites) = match boundary_mode {
+        "molecules" => fragment_positions(&lattice, &sites, false),
+        "grow" => fragment_positions(&lattice, &sites, true),
+        _ => (Vec::new(), (0..sites.len()).collect()),
+    };
+    let atom = |i: usize, frac: [f64; 3]| Atom {
+        name: sites[i].element.clone(),
+        x: frac[0],
+        y: frac[1],
+        z: frac[2],
+        radius: styles[i].radius,
+        color: styles[i].color.clone(),
+    };
+
+    let mut atoms: Vec<Atom> = cell_sites
+        .into_iter()
+        .filter(|&i| styles[i].visible)
+        .map(|i| atom(i, sites[i].frac))
+        .collect();
+
+    // 复制位于边界的原子
+    replicate_boundary_atoms(&mut atoms);
+
+    // 完整分子的原子可以位于晶胞外，不再复制
+    atoms.extend(
+        placed
+            .into_iter()
+            .filter(|&(i, _)| styles[i].visible)
+            .map(|(i, frac)| atom(i, frac)),
+    );
+
+    // 将所有原子坐标转换为笛卡尔坐标
+    atoms.iter_mut().for_each(|atom| {
+        let cart = frac_to_cart(&lattice, [atom.x, atom.y, atom.z]);
+        (atom.x, atom.y, atom.z) = (cart[0], cart[1], cart[2]);
+    });
+
+    // 计算晶格顶点与晶格中心
+    let mut lattice_vertices = calculate_cell_vertices(&lattice);
+    let center = get_lattice_center(&lattice_vertices);
+
+    // 平移原子与晶格顶点，使晶格的中心位于 (0,0,0)
+    atoms.iter_mut().for_each(|atom| {
+        atom.x -= center.0;
+        atom.y -= center.1;
+        atom.z -= center.2;
+    });
+    lattice_vertices.iter_mut().for_each(|vertex| {
+        vertex.0 -= center.0;
+        vertex.1 -= center.1;
+        vertex.2 -= center.2;
+    });
+
+    CrystalStructure {
+        lattice_vertices,
+        atoms,
+        lattice,
+        sites,
+        cell_esd: [0.0; 6],
+        volumetric: Vec::new(),
+    }
+}
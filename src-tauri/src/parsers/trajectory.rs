@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use crate::parsers::atom_config::{AtomConfig, CrystalStructure, Site};
+use crate::parsers::parser_utils::build_crystal_structure;
+use crate::parsers::{espresso_parser, lammps_parser, vasp_parser, xyz_parser};
+use crate::utils::geometry::wrap_frac;
+use crate::utils::linalg::{Mat3, Vec3};
+
+/// 轨迹中的一帧，分析计算直接使用，显示时再生成绘制数据
+#[derive(Clone, Debug)]
+pub struct Frame {
+    pub lattice: Mat3,
+    pub sites: Vec<Site>,               // 分数坐标保持文件中的值，可能超出 [0, 1)（未折回的坐标）
+    pub velocities: Option<Vec<Vec3>>,  // 笛卡尔速度，单位沿用文件
+}
+
+impl Frame {
+    /// 由内存中的晶体结构（如 NEB 图像）生成帧
+    pub fn from_structure(structure: &CrystalStructure) -> Frame {
+        Frame {
+            lattice: structure.lattice,
+            sites: structure.sites.clone(),
+            velocities: None,
+        }
+    }
+
+    /// 坐标折回晶胞后生成用于绘制的晶体结构
+    pub fn to_structure(&self, atom_config: &HashMap<String, AtomConfig>) -> CrystalStructure {
+        let sites = self
+            .sites
+            .iter()
+            .map(|site| Site { frac: wrap_frac(site.frac), ..site.clone() })
+            .collect();
+        build_crystal_structure(self.lattice, sites, atom_config)
+    }
+}
+
+/// 一帧在文件中的位置
+#[derive(Clone, Copy, Debug)]
+pub struct FrameOffset {
+    pub start: u64,          // 帧起始行的字节偏移
+    pub header: Option<u64>, // 该帧使用的晶胞块（QE 的 CELL_PARAMETERS、变胞 XDATCAR 的文件头）的字节偏移
+}
+
+/// 建立索引时读取、各帧共用的信息
+#[derive(Clone, Debug, Default)]
+pub struct TrajectoryHeader {
+    pub lattice: Option<Mat3>,                  // 定胞轨迹的晶格（Å），无晶胞 XYZ 为各帧共用的正交晶胞
+    pub shift: Option<Vec3>,                    // 无晶胞 XYZ 各帧共用的原子平移（Å）
+    pub alat: Option<f64>,                      // QE 的晶格参数（Bohr）
+    pub type_elements: HashMap<String, String>, // LAMMPS 原子类型到元素符号的映射
+}
+
+/// 按需读取的轨迹文件：打开时只扫描一遍文件记录各帧的字节偏移，
+/// 读取某一帧时定位到偏移处解析，内存占用与帧数无关
+#[derive(Clone, Debug)]
+pub struct Trajectory {
+    pub path: PathBuf,
+    pub format: String, // qe / xdatcar / extxyz / lammps
+    pub header: TrajectoryHeader,
+    frames: Vec<FrameOffset>,
+}
+
+impl Trajectory {
+    /// 扫描文件建立帧索引
+    pub fn open(path: &Path, format: &str) -> Result<Trajectory, String> {
+        let mut reader = LineReader::open(path)?;
+        let (frames, header) = match format {
+            "qe" => espresso_parser::index_qe_output(&mut reader)?,
+            "xdatcar" => vasp_parser::index_xdatcar(&mut reader)?,
+            "extxyz" => xyz_parser::index_extxyz(&mut reader)?,
+            "lammps" => {
+                let (frames, mut header) = lammps_parser::index_lammps_dump(&mut reader)?;
+                header.type_elements = lammps_parser::sibling_type_elements(path);
+                (frames, header)
+            }
+            _ => return Err(format!("Unknown trajectory format {}", format)),
+        };
+        if frames.is_empty() {
+            return Err(format!("No frames found in {}", path.display()));
+        }
+        Ok(Trajectory {
+            path: path.to_path_buf(),
+            format: format.to_string(),
+            header,
+            frames,
+        })
+    }
+
+    /// 帧数
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// 轨迹是否为空（打开成功的轨迹至少有一帧）
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// 读取第 index 帧
+    pub fn read_frame(&self, index: usize) -> Result<Frame, String> {
+        let offset = *self
+            .frames
+            .get(index)
+            .ok_or_else(|| format!("Frame {} out of range", index))?;
+        let mut reader = LineReader::open(&self.path)?;
+        let frame = match self.format.as_str() {
+            "qe" => espresso_parser::read_qe_frame(&mut reader, offset, &self.header),
+            "xdatcar" => vasp_parser::read_xdatcar_frame(&mut reader, offset),
+            "extxyz" => xyz_parser::read_extxyz_frame(&mut reader, offset, &self.header),
+            _ => lammps_parser::read_lammps_dump_frame(&mut reader, offset, &self.header),
+        };
+        frame.map_err(|e| format!("Frame {}: {}", index, e))
+    }
+
+    /// 读取最后一帧
+    pub fn last_frame(&self) -> Result<Frame, String> {
+        if self.is_empty() {
+            return Err("Empty trajectory".to_string());
+        }
+        self.read_frame(self.len() - 1)
+    }
+}
+
+/// 判断轨迹格式时读取的文件开头行数
+const SNIFF_LINES: usize = 64;
+
+/// 由文件开头的内容判断轨迹格式，不是轨迹文件时返回 None：
+/// QE 输出含 "Program PWSCF"，LAMMPS dump 以 "ITEM: TIMESTEP" 开头，
+/// XDATCAR（文件名含 xdatcar）含 "Direct configuration" 行，XYZ（.xyz/.extxyz）首行为原子数
+pub fn trajectory_format(path: &Path) -> Option<&'static str> {
+    let file_name = path.file_name()?.to_string_lossy().to_lowercase();
+    let mut reader = LineReader::open(path).ok()?;
+    let mut lines = Vec::new();
+    while lines.len() < SNIFF_LINES {
+        match reader.next_line() {
+            Ok(Some(line)) => lines.push(line.trim().to_string()),
+            _ => break,
+        }
+    }
+    let first = lines.iter().find(|line| !line.is_empty())?;
+
+    if first.starts_with("ITEM: TIMESTEP") {
+        Some("lammps")
+    } else if lines.iter().any(|line| line.starts_with("Program PWSCF")) {
+        Some("qe")
+    } else if file_name.contains("xdatcar") && lines.iter().any(|line| line.starts_with("Direct configuration")) {
+        Some("xdatcar")
+    } else if (file_name.ends_with(".xyz") || file_name.ends_with(".extxyz")) && first.parse::<usize>().is_ok() {
+        Some("extxyz")
+    } else {
+        None
+    }
+}
+
+/// 逐行读取文本文件并跟踪字节偏移，供建立索引与定位读取帧使用
+/// 非 UTF-8 字节按替换字符处理
+pub struct LineReader {
+    reader: BufReader<File>,
+    position: u64,
+    buffer: Vec<u8>,
+    line: String,
+}
+
+impl LineReader {
+    pub fn open(path: &Path) -> Result<LineReader, String> {
+        let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        Ok(LineReader {
+            reader: BufReader::with_capacity(1 << 16, file),
+            position: 0,
+            buffer: Vec::new(),
+            line: String::new(),
+        })
+    }
+
+    /// 定位到字节偏移 offset
+    pub fn seek(&mut self, offset: u64) -> Result<(), String> {
+        self.reader.seek(SeekFrom::Start(offset)).map_err(|e| e.to_string())?;
+        self.position = offset;
+        Ok(())
+    }
+
+    /// 下一行起始处的字节偏移
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// 读取下一行（不含行尾换行符），文件结束时返回 None
+    pub fn next_line(&mut self) -> Result<Option<&str>, String> {
+        self.buffer.clear();
+        let count = self.reader.read_until(b'\n', &mut self.buffer).map_err(|e| e.to_string())?;
+        if count == 0 {
+            return Ok(None);
+        }
+        self.position += count as u64;
+        while matches!(self.buffer.last(), Some(b'\n' | b'\r')) {
+            self.buffer.pop();
+        }
+        self.line.clear();
+        self.line.push_str(&String::from_utf8_lossy(&self.buffer));
+        Ok(Some(&self.line))
+    }
+
+    /// 读取下一行，文件提前结束时返回错误
+    pub fn expect_line(&mut self) -> Result<&str, String> {
+        self.next_line()?.ok_or_else(|| "Unexpected end of file".to_string())
+    }
+
+    /// 跳过 count 行而不解码内容，返回实际跳过的行数
+    pub fn skip_lines(&mut self, count: usize) -> Result<usize, String> {
+        for skipped in 0..count {
+            self.buffer.clear();
+            let read = self.reader.read_until(b'\n', &mut self.buffer).map_err(|e| e.to_string())?;
+            if read == 0 {
+                return Ok(skipped);
+            }
+            self.position += read as u64;
+        }
+        Ok(count)
+    }
+}
+
+/// 解析一行中的前三个数
+pub fn parse_triple(line: &str) -> Result<Vec3, String> {
+    let values: Vec<f64> = line
+        .split_whitespace()
+        .take(3)
+        .map(|v| v.parse::<f64>().map_err(|_| format!("Invalid number {}", v)))
+        .collect::<Result<_, String>>()?;
+    if values.len() < 3 {
+        return Err(format!("Expected three numbers in \"{}\"", line.trim()));
+    }
+    Ok([values[0], values[1], values[2]])
+}
@@ -0,0 +1,205 @@
+use std::fs;
+use std::path::PathBuf;
+use tauri::AppHandle;
+use crate::parsers::atom_config::{CrystalStructure, Site, VolumetricData};
+use crate::parsers::parser_utils::{build_crystal_structure, load_atom_config};
+use crate::parsers::trajectory::{parse_triple, Frame, FrameOffset, LineReader, TrajectoryHeader};
+use crate::utils::geometry::{cart_to_frac, cell_volume};
+use crate::utils::linalg::norm;
+
+/// 扫描 VASP XDATCAR 文件，记录每帧 Direct configuration 行与其所用文件头的位置
+/// 同时支持定胞（只有一个文件头）和变胞（每帧前重复文件头）两种格式
+pub fn index_xdatcar(reader: &mut LineReader) -> Result<(Vec<FrameOffset>, TrajectoryHeader), String> {
+    let mut frames = Vec::new();
+    let mut header: Option<u64> = None;
+    let mut atom_count = 0;
+
+    loop {
+        let offset = reader.position();
+        let Some(line) = reader.next_line()? else { break };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with("Direct configuration") {
+            if header.is_none() {
+                return Err("Missing XDATCAR header".to_string());
+            }
+            frames.push(FrameOffset { start: offset, header });
+            reader.skip_lines(atom_count)?;
+        } else {
+            // 文件头：注释、缩放系数、晶格、元素、原子数
+            reader.seek(offset)?;
+            let (_, species) = read_poscar_header(reader)?;
+            atom_count = species.len();
+            header = Some(offset);
+        }
+    }
+
+    Ok((frames, TrajectoryHeader::default()))
+}
+
+/// 读取 XDATCAR 的一帧
+pub fn read_xdatcar_frame(reader: &mut LineReader, offset: FrameOffset) -> Result<Frame, String> {
+    reader.seek(offset.header.ok_or("Missing XDATCAR header")?)?;
+    let (lattice, species) = read_poscar_header(reader)?;
+    reader.seek(offset.start)?;
+    reader.expect_line()?;
+    let sites = species
+        .iter()
+        .enumerate()
+        .map(|(n, element)| {
+            let frac = parse_triple(reader.expect_line()?)?;
+            Ok(Site::new(format!("{}{}", element, n + 1), element.clone(), frac))
+        })
+        .collect::<Result<_, String>>()?;
+    Ok(Frame { lattice, sites, velocities: None })
+}
+
+/// 从当前位置读取 7 行 POSCAR 文件头
+fn read_poscar_header(reader: &mut LineReader) -> Result<([[f64; 3]; 3], Vec<String>), String> {
+    let mut lines = Vec::with_capacity(7);
+    for _ in 0..7 {
+        lines.push(reader.expect_line()?.to_string());
+    }
+    let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+    parse_poscar_header(&lines)
+}
+
+/// 解析 VASP 的 CHGCAR 类格点文件（CHGCAR、CHG、PARCHG、AECCAR、LOCPOT、ELFCAR）
+/// kind 为 density 时数值除以晶胞体积，得到 e/Å³；potential（eV）与 elf 保持原值
+/// 自旋极化计算的第二个数据块（CHGCAR 为磁化密度，LOCPOT/ELFCAR 为自旋向下分量）一并读入，
+/// 数据块之间的 augmentation occupancies 与逐原子磁矩行被跳过
+pub fn parse_chgcar(app_handle: &AppHandle, file_path: &PathBuf, kind: &str) -> Result<CrystalStructure, String> {
+    let file_content = fs::read_to_string(file_path).map_err(|e| e.to_string())?;
+    let lines: Vec<&str> = file_content.lines().collect();
+    if lines.len() < 8 {
+        return Err("Incomplete CHGCAR header".to_string());
+    }
+    let (lattice, species) = parse_poscar_header(&lines[..7])?;
+
+    // 可选的 Selective dynamics 行，之后为坐标类型与原子坐标
+    let mut i = 7;
+    if lines[i].trim_start().starts_with(['S', 's']) {
+        i += 1;
+    }
+    let cartesian = lines
+        .get(i)
+        .map(|line| line.trim_start().starts_with(['C', 'c', 'K', 'k']))
+        .ok_or("Missing coordinate type")?;
+    // 笛卡尔坐标与晶格使用相同的缩放系数
+    let factor = norm(lattice[0]) / norm(parse_triple(lines[2])?);
+    let sites: Vec<Site> = species
+        .iter()
+        .enumerate()
+        .map(|(n, element)| {
+            let line = lines.get(i + 1 + n).ok_or("Missing atomic positions")?;
+            let position = parse_triple(line)?;
+            let frac = if cartesian {
+                cart_to_frac(&lattice, position.map(|v| v * factor))
+            } else {
+                position
+            };
+            Ok(Site::new(format!("{}{}", element, n + 1), element.clone(), frac))
+        })
+        .collect::<Result<_, String>>()?;
+    i += 1 + species.len();
+
+    let volume = cell_volume(&lattice);
+    let scale = if kind == "density" { 1.0 / volume } else { 1.0 };
+    let mut blocks = Vec::new();
+    let mut dims: Option<[usize; 3]> = None;
+    while i < lines.len() && blocks.len() < 2 {
+        // 数据块以格点数行开头；第二块的格点数与第一块相同
+        let grid = parse_grid_line(lines[i]);
+        let is_block = match (grid, dims) {
+            (Some(grid), None) => grid.iter().all(|&n| n > 0),
+            (Some(grid), Some(dims)) => grid == dims,
+            _ => false,
+        };
+        if !is_block {
+            i += 1;
+            continue;
+        }
+        let grid = grid.unwrap();
+        dims = Some(grid);
+        let count = grid[0] * grid[1] * grid[2];
+        let mut values = Vec::with_capacity(count);
+        i += 1;
+        while values.len() < count && i < lines.len() {
+            for token in lines[i].split_whitespace() {
+                let value: f64 = token.parse().map_err(|_| format!("Invalid grid value {}", token))?;
+                values.push(value * scale);
+            }
+            i += 1;
+        }
+        if values.len() < count {
+            return Err(format!("Expected {} grid values, found {}", count, values.len()));
+        }
+        values.truncate(count);
+        let name = match (blocks.len(), kind) {
+            (0, _) => "total",
+            (_, "density") => "magnetization",
+            _ => "spin_down",
+        };
+        blocks.push(VolumetricData::new(name.to_string(), kind.to_string(), grid, lattice, [0.0; 3], values));
+    }
+    if blocks.is_empty() {
+        return Err("No volumetric data found".to_string());
+    }
+
+    let mut structure = build_crystal_structure(lattice, sites, &load_atom_config(app_handle));
+    structure.volumetric = blocks;
+    Ok(structure)
+}
+
+/// 解析只含三个正整数的格点数行
+fn parse_grid_line(line: &str) -> Option<[usize; 3]> {
+    let values: Vec<usize> = line.split_whitespace().map(|v| v.parse().ok()).collect::<Option<_>>()?;
+    if values.len() == 3 {
+        Some([values[0], values[1], values[2]])
+    } else {
+        None
+    }
+}
+
+/// 解析 POSCAR 格式的文件头（VASP 5 格式，共 7 行）
+/// 返回晶格矩阵（Å）与按原子顺序展开的元素列表；不含元素名称行的 VASP 4 格式返回错误
+pub fn parse_poscar_header(lines: &[&str]) -> Result<([[f64; 3]; 3], Vec<String>), String> {
+    if lines.len() < 7 {
+        return Err("Incomplete POSCAR header".to_string());
+    }
+
+    let scale: f64 = lines[1]
+        .split_whitespace()
+        .next()
+        .and_then(|v| v.parse().ok())
+        .ok_or_else(|| format!("Invalid scaling factor \"{}\"", lines[1].trim()))?;
+    let mut lattice = [parse_triple(lines[2])?, parse_triple(lines[3])?, parse_triple(lines[4])?];
+    // 负的缩放系数表示目标体积
+    let factor = if scale < 0.0 {
+        (-scale / cell_volume(&lattice)).cbrt()
+    } else {
+        scale
+    };
+    lattice = lattice.map(|row| row.map(|v| v * factor));
+
+    let names: Vec<&str> = lines[5].split_whitespace().collect();
+    if names.is_empty() || names.iter().all(|name| name.parse::<usize>().is_ok()) {
+        return Err("VASP 4 files without element names are not supported".to_string());
+    }
+    let counts: Vec<usize> = lines[6]
+        .split_whitespace()
+        .map(|v| v.parse().map_err(|_| format!("Invalid atom count {}", v)))
+        .collect::<Result<_, String>>()?;
+    if counts.len() != names.len() {
+        return Err(format!("{} element names but {} atom counts", names.len(), counts.len()));
+    }
+    let species = names
+        .iter()
+        .zip(counts.iter())
+        .flat_map(|(name, &count)| std::iter::repeat_n(name.to_string(), count))
+        .collect();
+
+    Ok((lattice, species))
+}
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use crate::parsers::atom_config::Site;
+use crate::parsers::parser_utils::element_symbol;
+use crate::parsers::trajectory::{Frame, FrameOffset, LineReader, TrajectoryHeader};
+use crate::utils::geometry::cart_to_frac;
+use crate::utils::linalg::Vec3;
+
+/// 没有 Lattice 的普通 XYZ 文件，原子外围留出的真空层（Å）
+const VACUUM_PADDING: f64 = 5.0;
+
+/// 原子行中各属性所在的列
+struct Columns {
+    species: usize,
+    position: usize,
+    velocity: Option<usize>,
+}
+
+/// 扫描（扩展）XYZ 文件，记录每帧原子数行的位置
+/// 没有 Lattice 的帧在此时读取坐标，由全部这类帧的原子范围加真空层确定共用的正交晶胞与平移，
+/// 保证各帧的坐标处于同一参照系中（MSD 等分析依赖这一点）
+/// 文件末尾不完整的帧（仍在写入的 MD 轨迹）被忽略
+pub fn index_extxyz(reader: &mut LineReader) -> Result<(Vec<FrameOffset>, TrajectoryHeader), String> {
+    let mut frames = Vec::new();
+    let mut bare_frames = false;
+    let mut min = [f64::INFINITY; 3];
+    let mut max = [f64::NEG_INFINITY; 3];
+    loop {
+        let offset = reader.position();
+        let Some(line) = reader.next_line()? else { break };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let count: usize = line
+            .parse()
+            .map_err(|_| format!("Invalid atom count \"{}\" in XYZ file", line))?;
+        let Some(comment) = reader.next_line()? else { break };
+        let info = parse_comment(comment);
+        if info.contains_key("lattice") {
+            if reader.skip_lines(count)? < count {
+                break;
+            }
+        } else {
+            let columns = frame_columns(&info)?;
+            let mut frame_min = min;
+            let mut frame_max = max;
+            let mut read = 0;
+            while read < count {
+                let Some(line) = reader.next_line()? else { break };
+                let position = parse_vector(line, columns.position)?;
+                for k in 0..3 {
+                    frame_min[k] = frame_min[k].min(position[k]);
+                    frame_max[k] = frame_max[k].max(position[k]);
+                }
+                read += 1;
+            }
+            if read < count {
+                break;
+            }
+            bare_frames = true;
+            min = frame_min;
+            max = frame_max;
+        }
+        frames.push(FrameOffset { start: offset, header: None });
+    }
+
+    let mut header = TrajectoryHeader::default();
+    if bare_frames {
+        if !min[0].is_finite() {
+            // 只有空帧
+            min = [0.0; 3];
+            max = [0.0; 3];
+        }
+        let size = [0, 1, 2].map(|k| max[k] - min[k] + 2.0 * VACUUM_PADDING);
+        header.lattice = Some([[size[0], 0.0, 0.0], [0.0, size[1], 0.0], [0.0, 0.0, size[2]]]);
+        header.shift = Some([0, 1, 2].map(|k| VACUUM_PADDING - min[k]));
+    }
+    Ok((frames, header))
+}
+
+/// 读取 XYZ 的一帧
+/// 扩展 XYZ 的注释行给出 Lattice="ax ay az bx by bz cx cy cz" 与 Properties=species:S:1:pos:R:3:vel:R:3 等；
+/// 没有 Lattice 时使用建立索引时确定的共用晶胞，原子按共用的平移移入晶胞
+pub fn read_extxyz_frame(reader: &mut LineReader, offset: FrameOffset, header: &TrajectoryHeader) -> Result<Frame, String> {
+    reader.seek(offset.start)?;
+    let count: usize = reader
+        .expect_line()?
+        .trim()
+        .parse()
+        .map_err(|_| "Invalid atom count".to_string())?;
+    let info = parse_comment(reader.expect_line()?);
+    let columns = frame_columns(&info)?;
+
+    let (lattice, shift) = match info.get("lattice") {
+        Some(text) => {
+            let values: Vec<f64> = text
+                .split_whitespace()
+                .map(|v| v.parse().map_err(|_| format!("Invalid lattice value {}", v)))
+                .collect::<Result<_, String>>()?;
+            if values.len() != 9 {
+                return Err("Lattice must have nine components".to_string());
+            }
+            ([[values[0], values[1], values[2]], [values[3], values[4], values[5]], [values[6], values[7], values[8]]], [0.0; 3])
+        }
+        None => (
+            header.lattice.ok_or("Missing XYZ bounding box")?,
+            header.shift.ok_or("Missing XYZ bounding box")?,
+        ),
+    };
+
+    let mut sites = Vec::with_capacity(count);
+    let mut velocities = Vec::with_capacity(if columns.velocity.is_some() { count } else { 0 });
+    for n in 0..count {
+        let line = reader.expect_line()?;
+        let element = element_symbol(line.split_whitespace().nth(columns.species).ok_or("Missing species")?);
+        let position = parse_vector(line, columns.position)?;
+        let cart = [0, 1, 2].map(|k| position[k] + shift[k]);
+        sites.push(Site::new(format!("{}{}", element, n + 1), element, cart_to_frac(&lattice, cart)));
+        if let Some(column) = columns.velocity {
+            velocities.push(parse_vector(line, column)?);
+        }
+    }
+
+    Ok(Frame {
+        lattice,
+        sites,
+        velocities: columns.velocity.map(|_| velocities),
+    })
+}
+
+/// 由注释行的 Properties（缺省为 species:S:1:pos:R:3）确定元素、坐标与速度所在的列
+fn frame_columns(info: &HashMap<String, String>) -> Result<Columns, String> {
+    let properties = info
+        .get("properties")
+        .cloned()
+        .unwrap_or_else(|| "species:S:1:pos:R:3".to_string());
+    let columns = property_columns(&properties)?;
+    let species = ["species", "element", "symbols"]
+        .iter()
+        .find_map(|name| columns.get(*name))
+        .map(|&(column, _)| column)
+        .ok_or("Missing species column")?;
+    let position = match columns.get("pos").or_else(|| columns.get("positions")) {
+        Some(&(column, 3)) => column,
+        _ => return Err("Missing pos:R:3 column".to_string()),
+    };
+    let velocity = ["vel", "velo", "velocities"]
+        .iter()
+        .find_map(|name| columns.get(*name))
+        .filter(|&&(_, width)| width == 3)
+        .map(|&(column, _)| column);
+    Ok(Columns { species, position, velocity })
+}
+
+/// 读取原子行中从 column 列开始的三个数
+fn parse_vector(line: &str, column: usize) -> Result<Vec3, String> {
+    let mut parts = line.split_whitespace().skip(column);
+    let mut vector = [0.0; 3];
+    for value in vector.iter_mut() {
+        let token = parts.next().ok_or_else(|| format!("Too few columns in \"{}\"", line.trim()))?;
+        *value = token.parse().map_err(|_| format!("Invalid number {}", token))?;
+    }
+    Ok(vector)
+}
+
+/// 解析扩展 XYZ 注释行中的 key=value 与 key="带空格的值"，键名转为小写
+fn parse_comment(line: &str) -> HashMap<String, String> {
+    let mut info = HashMap::new();
+    let mut chars = line.trim().chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let key: String = std::iter::from_fn(|| chars.next_if(|c| !c.is_whitespace() && *c != '=')).collect();
+        if key.is_empty() {
+            break;
+        }
+        if chars.next_if_eq(&'=').is_none() {
+            continue;
+        }
+        let value: String = if chars.next_if_eq(&'"').is_some() {
+            let value = std::iter::from_fn(|| chars.next_if(|c| *c != '"')).collect();
+            chars.next();
+            value
+        } else {
+            std::iter::from_fn(|| chars.next_if(|c| !c.is_whitespace())).collect()
+        };
+        info.insert(key.to_lowercase(), value);
+    }
+    info
+}
+
+/// 把 Properties 字符串解析为 属性名 -> (起始列, 列数)
+fn property_columns(properties: &str) -> Result<HashMap<String, (usize, usize)>, String> {
+    let fields: Vec<&str> = properties.split(':').collect();
+    if !fields.len().is_multiple_of(3) {
+        return Err(format!("Invalid Properties {}", properties));
+    }
+    let mut columns = HashMap::new();
+    let mut column = 0;
+    for field in fields.chunks(3) {
+        let width: usize = field[2].parse().map_err(|_| format!("Invalid Properties {}", properties))?;
+        columns.insert(field[0].to_lowercase(), (column, width));
+        column += width;
+    }
+    Ok(columns)
+}
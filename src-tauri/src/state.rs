@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use serde::{Deserialize, Serialize};
+use crate::analysis::symmetry::equivalent_sites;
+use crate::parsers::atom_config::{AtomConfig, CrystalStructure};
+use crate::parsers::parser_utils::atom_styles;
+use crate::parsers::trajectory::{Frame, Trajectory};
+
+/// 视图的显示设置
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DisplaySettings {
+    pub radius_type: String,  // 原子球使用的半径类型：atomic / covalent / ionic / vdw
+    pub color_scheme: String, // 原子配色：jmol / cpk / vesta / site / wyckoff / property
+    pub property: Option<PropertyColoring>, // color_scheme 为 property 时使用的逐原子数据
+    pub boundary_mode: String, // 晶胞边界的画法：cell / molecules / grow
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        DisplaySettings {
+            radius_type: "atomic".to_string(),
+            color_scheme: "jmol".to_string(),
+            property: None,
+            boundary_mode: "cell".to_string(),
+        }
+    }
+}
+
+/// 按逐原子标量着色的数据，values 与当前结构的位点一一对应
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PropertyColoring {
+    pub name: String,     // 如 charge、magnetic_moment、coordination、force
+    pub values: Vec<f64>,
+    pub colormap: String, // viridis / plasma / coolwarm / rainbow
+    pub range: [f64; 2],  // 映射到色图两端的数值
+}
+
+/// 用户对单个元素绘制样式的修改，未设置的项沿用默认值
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct ElementStyle {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub radius: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visible: Option<bool>,
+}
+
+/// 用户的原子样式配置，保存在应用配置目录中，覆盖元素数据库的默认值
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct UserStyles {
+    pub elements: HashMap<String, ElementStyle>, // 以元素符号为键
+}
+
+/// 应用全局状态，保存当前加载的晶体结构供各分析命令使用
+#[derive(Default)]
+pub struct AppState {
+    pub structure: Mutex<Option<CrystalStructure>>,
+    pub frames: Mutex<Vec<CrystalStructure>>, // 内存中的轨迹（如 NEB 图像），单个结构时为空
+    pub trajectory: Mutex<Option<Arc<Trajectory>>>, // 按需读取帧的轨迹文件
+    pub display: Mutex<DisplaySettings>,
+    pub user_styles: Mutex<UserStyles>,
+    pub orbits: Mutex<Option<Vec<usize>>>, // 当前结构对称等价位点的分组（wyckoff 配色），替换结构时清空
+}
+
+/// 逐帧处理轨迹时的帧来源，轨迹文件不会一次读入全部帧
+pub enum FrameSource {
+    File(Arc<Trajectory>),
+    Memory(Vec<CrystalStructure>), // 内存中的轨迹（如 NEB 图像）或单个结构
+}
+
+impl FrameSource {
+    /// 帧数
+    pub fn len(&self) -> usize {
+        match self {
+            FrameSource::File(trajectory) => trajectory.len(),
+            FrameSource::Memory(frames) => frames.len(),
+        }
+    }
+
+    /// 读取第 index 帧
+    pub fn read_frame(&self, index: usize) -> Result<Frame, String> {
+        match self {
+            FrameSource::File(trajectory) => trajectory.read_frame(index),
+            FrameSource::Memory(frames) => frames
+                .get(index)
+                .map(Frame::from_structure)
+                .ok_or_else(|| format!("Frame {} out of range", index)),
+        }
+    }
+}
+
+/// wyckoff 配色判断对称等价的位置容差（Å）
+const ORBIT_SYMPREC: f64 = 0.05;
+
+/// 搜索对称等价分组的位点数上限，超过时只按元素分组
+const MAX_ORBIT_SITES: usize = 2000;
+
+impl AppState {
+    /// 替换当前晶体结构，逐原子着色数据与对称等价分组随之失效
+    pub fn set_structure(&self, structure: CrystalStructure) {
+        *self.structure.lock().unwrap() = Some(structure);
+        self.frames.lock().unwrap().clear();
+        *self.trajectory.lock().unwrap() = None;
+        self.display.lock().unwrap().property = None;
+        *self.orbits.lock().unwrap() = None;
+    }
+
+    /// 替换当前轨迹，最后一帧作为当前晶体结构
+    pub fn set_trajectory(&self, frames: Vec<CrystalStructure>) {
+        *self.structure.lock().unwrap() = frames.last().cloned();
+        *self.frames.lock().unwrap() = frames;
+        *self.trajectory.lock().unwrap() = None;
+        self.display.lock().unwrap().property = None;
+        *self.orbits.lock().unwrap() = None;
+    }
+
+    /// 替换为按需读取的轨迹文件，读取最后一帧作为当前晶体结构并返回
+    pub fn set_file_trajectory(&self, trajectory: Trajectory) -> Result<CrystalStructure, String> {
+        let structure = trajectory.last_frame()?.to_structure(&self.atom_config());
+        *self.structure.lock().unwrap() = Some(structure.clone());
+        self.frames.lock().unwrap().clear();
+        *self.trajectory.lock().unwrap() = Some(Arc::new(trajectory));
+        self.display.lock().unwrap().property = None;
+        *self.orbits.lock().unwrap() = None;
+        Ok(structure)
+    }
+
+    /// 当前轨迹文件
+    pub fn trajectory(&self) -> Option<Arc<Trajectory>> {
+        self.trajectory.lock().unwrap().clone()
+    }
+
+    /// 轨迹的帧数；单个结构为 1，未加载文件时为 0
+    pub fn frame_count(&self) -> usize {
+        if let Some(trajectory) = self.trajectory() {
+            return trajectory.len();
+        }
+        match self.frames.lock().unwrap().len() {
+            0 => self.structure.lock().unwrap().is_some() as usize,
+            count => count,
+        }
+    }
+
+    /// 获取轨迹的第 index 帧；轨迹文件的帧按需读取并生成绘制数据
+    pub fn frame(&self, index: usize) -> Result<CrystalStructure, String> {
+        if let Some(trajectory) = self.trajectory() {
+            return Ok(trajectory.read_frame(index)?.to_structure(&self.atom_config()));
+        }
+        let frames = self.frames.lock().unwrap();
+        if frames.is_empty() && index == 0 {
+            drop(frames);
+            return self.current_structure();
+        }
+        frames.get(index).cloned().ok_or_else(|| format!("Frame {} out of range", index))
+    }
+
+    /// 获取当前晶体结构的副本，未加载文件时返回错误
+    pub fn current_structure(&self) -> Result<CrystalStructure, String> {
+        self.structure
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| "No structure loaded".to_string())
+    }
+
+    /// 分析与导出使用的帧来源：轨迹文件按需读取，内存中的轨迹与单个结构直接取用
+    pub fn frame_source(&self) -> Result<FrameSource, String> {
+        if let Some(trajectory) = self.trajectory() {
+            return Ok(FrameSource::File(trajectory));
+        }
+        let frames = self.frames.lock().unwrap();
+        if frames.is_empty() {
+            drop(frames);
+            Ok(FrameSource::Memory(vec![self.current_structure()?]))
+        } else {
+            Ok(FrameSource::Memory(frames.clone()))
+        }
+    }
+
+    /// 当前结构的对称等价位点分组，首次使用时计算并缓存
+    /// 轨迹各帧共用同一分组，播放时不再重复搜索对称操作；位点数不符时重新计算
+    /// 大结构（超胞、无定形模型）通常没有有意义的对称性，直接把同种元素归为一组
+    pub fn site_orbits(&self, structure: &CrystalStructure) -> Vec<usize> {
+        let mut orbits = self.orbits.lock().unwrap();
+        match orbits.as_ref() {
+            Some(cached) if cached.len() == structure.sites.len() => cached.clone(),
+            _ => {
+                let sites = &structure.sites;
+                let computed = if sites.len() > MAX_ORBIT_SITES {
+                    let mut first: HashMap<&str, usize> = HashMap::new();
+                    sites
+                        .iter()
+                        .enumerate()
+                        .map(|(index, site)| *first.entry(site.element.as_str()).or_insert(index))
+                        .collect()
+                } else {
+                    equivalent_sites(&structure.lattice, sites, ORBIT_SYMPREC)
+                };
+                *orbits = Some(computed.clone());
+                computed
+            }
+        }
+    }
+
+    /// 获取当前显示设置的副本
+    pub fn display_settings(&self) -> DisplaySettings {
+        self.display.lock().unwrap().clone()
+    }
+
+    /// 按当前显示设置与用户配置生成各元素的半径和颜色
+    pub fn atom_config(&self) -> HashMap<String, AtomConfig> {
+        atom_styles(&self.display_settings(), &self.user_styles())
+    }
+
+    /// 获取用户原子样式配置的副本
+    pub fn user_styles(&self) -> UserStyles {
+        self.user_styles.lock().unwrap().clone()
+    }
+}
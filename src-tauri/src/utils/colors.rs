@@ -0,0 +1,60 @@
+// 颜色工具：十六进制颜色、连续色图与离散调色板
+
+/// 可选的连续色图
+pub const COLORMAPS: [&str; 4] = ["viridis", "plasma", "coolwarm", "rainbow"];
+
+/// 色图的等距控制点
+fn colormap_stops(name: &str) -> Option<&'static [&'static str]> {
+    match name {
+        "viridis" => Some(&[
+            "#440154", "#482878", "#3E4A89", "#31688E", "#26828E", "#1F9E89", "#35B779", "#6DCD59", "#B4DE2C",
+            "#FDE725",
+        ]),
+        "plasma" => Some(&[
+            "#0D0887", "#46039F", "#7201A8", "#9C179E", "#BD3786", "#D8576B", "#ED7953", "#FB9F3A", "#FDCA26",
+            "#F0F921",
+        ]),
+        "coolwarm" => Some(&["#3B4CC0", "#6F92F3", "#AAC7FD", "#DDDDDD", "#F7B89C", "#E7745B", "#B40426"]),
+        "rainbow" => Some(&["#0000FF", "#00FFFF", "#00FF00", "#FFFF00", "#FF0000"]),
+        _ => None,
+    }
+}
+
+/// 离散调色板（Tableau 20），用于按位点着色
+const PALETTE: [&str; 20] = [
+    "#1F77B4", "#FF7F0E", "#2CA02C", "#D62728", "#9467BD", "#8C564B", "#E377C2", "#7F7F7F", "#BCBD22", "#17BECF",
+    "#AEC7E8", "#FFBB78", "#98DF8A", "#FF9896", "#C5B0D5", "#C49C94", "#F7B6D2", "#C7C7C7", "#DBDB8D", "#9EDAE5",
+];
+
+/// 解析 #RRGGBB 格式的颜色
+pub fn parse_hex(color: &str) -> Option<[u8; 3]> {
+    let hex = color.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// 格式化为 #RRGGBB
+pub fn format_hex(rgb: [u8; 3]) -> String {
+    format!("#{:02X}{:02X}{:02X}", rgb[0], rgb[1], rgb[2])
+}
+
+/// 在色图上取 t（0 ~ 1）处的颜色，相邻控制点之间线性插值
+pub fn colormap(name: &str, t: f64) -> Option<String> {
+    let stops = colormap_stops(name)?;
+    let t = if t.is_finite() { t.clamp(0.0, 1.0) } else { 0.0 };
+    let position = t * (stops.len() - 1) as f64;
+    let lower = (position.floor() as usize).min(stops.len() - 2);
+    let fraction = position - lower as f64;
+    let a = parse_hex(stops[lower])?;
+    let b = parse_hex(stops[lower + 1])?;
+    let mix = [0, 1, 2].map(|i| (a[i] as f64 + (b[i] as f64 - a[i] as f64) * fraction).round() as u8);
+    Some(format_hex(mix))
+}
+
+/// 调色板中第 index 个颜色，超出后循环使用
+pub fn palette(index: usize) -> &'static str {
+    PALETTE[index % PALETTE.len()]
+}
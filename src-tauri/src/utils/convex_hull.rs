@@ -0,0 +1,116 @@
+use crate::utils::linalg::{add, cross, dot, norm, scale, sub, Vec3};
+
+/// 凸包：多边形面与体积
+#[derive(Clone, Debug)]
+pub struct ConvexHull {
+    pub faces: Vec<Vec<usize>>, // 每个面的顶点序号，从外侧看为逆时针
+    pub volume: f64,
+}
+
+impl ConvexHull {
+    /// 将多边形面按扇形剖分为三角形
+    pub fn triangles(&self) -> Vec<[usize; 3]> {
+        self.faces
+            .iter()
+            .flat_map(|face| (1..face.len() - 1).map(move |k| [face[0], face[k], face[k + 1]]))
+            .collect()
+    }
+
+    /// 凸包的全部棱（顶点序号对，小序号在前）
+    pub fn edges(&self) -> Vec<(usize, usize)> {
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        for face in &self.faces {
+            for k in 0..face.len() {
+                let (a, b) = (face[k], face[(k + 1) % face.len()]);
+                let edge = (a.min(b), a.max(b));
+                if !edges.contains(&edge) {
+                    edges.push(edge);
+                }
+            }
+        }
+        edges
+    }
+}
+
+/// 计算少量点的三维凸包（逐个检验三点平面，适用于配位多面体这类几十个点以内的情形）
+/// 点数少于 4 或全部共面时返回 None
+pub fn convex_hull(points: &[Vec3]) -> Option<ConvexHull> {
+    let n = points.len();
+    if n < 4 {
+        return None;
+    }
+    let size = points.iter().map(|p| norm(*p)).fold(0.0, f64::max).max(1.0);
+    let eps = 1e-6 * size;
+
+    // 收集所有支撑平面 (单位外法向, 原点到平面的距离)
+    let mut planes: Vec<(Vec3, f64)> = Vec::new();
+    for i in 0..n {
+        for j in i + 1..n {
+            for k in j + 1..n {
+                let normal = cross(sub(points[j], points[i]), sub(points[k], points[i]));
+                let length = norm(normal);
+                if length < eps * eps {
+                    continue;
+                }
+                let mut normal = scale(normal, 1.0 / length);
+                let mut offset = dot(normal, points[i]);
+                let above = points.iter().filter(|p| dot(normal, **p) - offset > eps).count();
+                let below = points.iter().filter(|p| dot(normal, **p) - offset < -eps).count();
+                if above > 0 && below > 0 {
+                    continue;
+                }
+                if above == 0 && below == 0 {
+                    // 全部共面
+                    return None;
+                }
+                if above > 0 {
+                    normal = scale(normal, -1.0);
+                    offset = -offset;
+                }
+                let duplicate = planes
+                    .iter()
+                    .any(|(m, d)| dot(*m, normal) > 1.0 - 1e-9 && (d - offset).abs() < eps);
+                if !duplicate {
+                    planes.push((normal, offset));
+                }
+            }
+        }
+    }
+
+    // 每个平面上的点按外法向逆时针排序构成一个面
+    let centroid = scale(points.iter().fold([0.0; 3], |acc, p| add(acc, *p)), 1.0 / n as f64);
+    let mut faces = Vec::new();
+    let mut volume = 0.0;
+    for (normal, offset) in planes {
+        let mut face: Vec<usize> = (0..n)
+            .filter(|&m| (dot(normal, points[m]) - offset).abs() <= eps)
+            .collect();
+        let face_center = scale(
+            face.iter().fold([0.0; 3], |acc, &m| add(acc, points[m])),
+            1.0 / face.len() as f64,
+        );
+        let u = sub(points[face[0]], face_center);
+        let v = cross(normal, u);
+        face.sort_by(|&p, &q| {
+            let dp = sub(points[p], face_center);
+            let dq = sub(points[q], face_center);
+            let ap = dot(dp, v).atan2(dot(dp, u));
+            let aq = dot(dq, v).atan2(dot(dq, u));
+            ap.partial_cmp(&aq).unwrap()
+        });
+
+        // 以凸包内的质心为顶点，累加棱锥体积
+        let height = offset - dot(normal, centroid);
+        let area = (1..face.len() - 1)
+            .map(|k| {
+                let e1 = sub(points[face[k]], points[face[0]]);
+                let e2 = sub(points[face[k + 1]], points[face[0]]);
+                norm(cross(e1, e2)) / 2.0
+            })
+            .sum::<f64>();
+        volume += area * height / 3.0;
+        faces.push(face);
+    }
+
+    Some(ConvexHull { faces, volume })
+}
@@ -0,0 +1,1833 @@
+// 元素周期表数据
+// 原子量取 IUPAC 2021 标准原子量的常用值，无稳定同位素的元素取最长寿命同位素的质量数
+// 共价半径：B. Cordero et al., Dalton Trans. (2008) 2832
+// 离子半径：R. D. Shannon, Acta Cryst. A32 (1976) 751，取最常见氧化态、六配位的值
+// 范德华半径：主族元素取 Bondi / Mantina 的值，其余取 S. Alvarez, Dalton Trans. 42 (2013) 8617
+// 原子半径：J. C. Slater, J. Chem. Phys. 41 (1964) 3199，稀有气体取计算值
+// 电负性：Pauling 标度
+// 颜色：Jmol（https://jmol.sourceforge.net/jscolors/）、RasMol CPK、VESTA 默认配色
+
+use serde::Serialize;
+
+/// 元素数据，缺少数据的项为 None
+#[derive(Serialize, Clone, Debug)]
+pub struct Element {
+    pub number: u32,                         // 原子序数
+    pub symbol: &'static str,                // 元素符号
+    pub name: &'static str,                  // 英文名称
+    pub mass: f64,                           // 原子量（g/mol）
+    pub covalent_radius: Option<f64>,        // 共价半径（Å）
+    pub ionic_radius: Option<f64>,           // 离子半径（Å）
+    pub vdw_radius: Option<f64>,             // 范德华半径（Å）
+    pub atomic_radius: Option<f64>,          // 原子半径（Å）
+    pub electronegativity: Option<f64>,      // Pauling 电负性
+    pub oxidation_states: &'static [i32],    // 常见氧化态
+    pub jmol_color: &'static str,
+    pub cpk_color: &'static str,
+    pub vesta_color: &'static str,
+}
+
+/// 可选的半径类型
+pub const RADIUS_TYPES: [&str; 4] = ["atomic", "covalent", "ionic", "vdw"];
+
+impl Element {
+    /// 按类型取半径：atomic / covalent / ionic / vdw
+    pub fn radius(&self, radius_type: &str) -> Option<f64> {
+        match radius_type {
+            "atomic" => self.atomic_radius,
+            "covalent" => self.covalent_radius,
+            "ionic" => self.ionic_radius,
+            "vdw" => self.vdw_radius,
+            _ => None,
+        }
+    }
+
+    /// 按配色方案取颜色：jmol / cpk / vesta
+    pub fn color(&self, scheme: &str) -> Option<&'static str> {
+        match scheme {
+            "jmol" => Some(self.jmol_color),
+            "cpk" => Some(self.cpk_color),
+            "vesta" => Some(self.vesta_color),
+            _ => None,
+        }
+    }
+}
+
+/// 按原子序数排列的全部元素
+pub static ELEMENTS: [Element; 118] = [
+    Element {
+        number: 1,
+        symbol: "H",
+        name: "Hydrogen",
+        mass: 1.008,
+        covalent_radius: Some(0.31),
+        ionic_radius: None,
+        vdw_radius: Some(1.1),
+        atomic_radius: Some(0.25),
+        electronegativity: Some(2.2),
+        oxidation_states: &[-1, 1],
+        jmol_color: "#FFFFFF",
+        cpk_color: "#FFFFFF",
+        vesta_color: "#FFCCCC",
+    },
+    Element {
+        number: 2,
+        symbol: "He",
+        name: "Helium",
+        mass: 4.0026,
+        covalent_radius: Some(0.28),
+        ionic_radius: None,
+        vdw_radius: Some(1.4),
+        atomic_radius: Some(0.31),
+        electronegativity: None,
+        oxidation_states: &[],
+        jmol_color: "#D9FFFF",
+        cpk_color: "#FFC0CB",
+        vesta_color: "#FCE8CE",
+    },
+    Element {
+        number: 3,
+        symbol: "Li",
+        name: "Lithium",
+        mass: 6.94,
+        covalent_radius: Some(1.28),
+        ionic_radius: Some(0.76),
+        vdw_radius: Some(1.81),
+        atomic_radius: Some(1.45),
+        electronegativity: Some(0.98),
+        oxidation_states: &[1],
+        jmol_color: "#CC80FF",
+        cpk_color: "#B22222",
+        vesta_color: "#86E074",
+    },
+    Element {
+        number: 4,
+        symbol: "Be",
+        name: "Beryllium",
+        mass: 9.0122,
+        covalent_radius: Some(0.96),
+        ionic_radius: Some(0.45),
+        vdw_radius: Some(1.53),
+        atomic_radius: Some(1.05),
+        electronegativity: Some(1.57),
+        oxidation_states: &[2],
+        jmol_color: "#C2FF00",
+        cpk_color: "#FF1493",
+        vesta_color: "#5ED77B",
+    },
+    Element {
+        number: 5,
+        symbol: "B",
+        name: "Boron",
+        mass: 10.81,
+        covalent_radius: Some(0.84),
+        ionic_radius: Some(0.27),
+        vdw_radius: Some(1.92),
+        atomic_radius: Some(0.85),
+        electronegativity: Some(2.04),
+        oxidation_states: &[3],
+        jmol_color: "#FFB5B5",
+        cpk_color: "#00FF00",
+        vesta_color: "#1FA20F",
+    },
+    Element {
+        number: 6,
+        symbol: "C",
+        name: "Carbon",
+        mass: 12.011,
+        covalent_radius: Some(0.76),
+        ionic_radius: Some(0.16),
+        vdw_radius: Some(1.7),
+        atomic_radius: Some(0.7),
+        electronegativity: Some(2.55),
+        oxidation_states: &[-4, 4],
+        jmol_color: "#909090",
+        cpk_color: "#C8C8C8",
+        vesta_color: "#814929",
+    },
+    Element {
+        number: 7,
+        symbol: "N",
+        name: "Nitrogen",
+        mass: 14.007,
+        covalent_radius: Some(0.71),
+        ionic_radius: Some(1.46),
+        vdw_radius: Some(1.55),
+        atomic_radius: Some(0.65),
+        electronegativity: Some(3.04),
+        oxidation_states: &[-3, 3, 5],
+        jmol_color: "#3050F8",
+        cpk_color: "#8F8FFF",
+        vesta_color: "#B0B9E6",
+    },
+    Element {
+        number: 8,
+        symbol: "O",
+        name: "Oxygen",
+        mass: 15.999,
+        covalent_radius: Some(0.66),
+        ionic_radius: Some(1.4),
+        vdw_radius: Some(1.52),
+        atomic_radius: Some(0.6),
+        electronegativity: Some(3.44),
+        oxidation_states: &[-2],
+        jmol_color: "#FF0D0D",
+        cpk_color: "#F00000",
+        vesta_color: "#FE0300",
+    },
+    Element {
+        number: 9,
+        symbol: "F",
+        name: "Fluorine",
+        mass: 18.998,
+        covalent_radius: Some(0.57),
+        ionic_radius: Some(1.33),
+        vdw_radius: Some(1.47),
+        atomic_radius: Some(0.5),
+        electronegativity: Some(3.98),
+        oxidation_states: &[-1],
+        jmol_color: "#90E050",
+        cpk_color: "#DAA520",
+        vesta_color: "#B0B9E6",
+    },
+    Element {
+        number: 10,
+        symbol: "Ne",
+        name: "Neon",
+        mass: 20.18,
+        covalent_radius: Some(0.58),
+        ionic_radius: None,
+        vdw_radius: Some(1.54),
+        atomic_radius: Some(0.38),
+        electronegativity: None,
+        oxidation_states: &[],
+        jmol_color: "#B3E3F5",
+        cpk_color: "#FF1493",
+        vesta_color: "#FE37B5",
+    },
+    Element {
+        number: 11,
+        symbol: "Na",
+        name: "Sodium",
+        mass: 22.99,
+        covalent_radius: Some(1.66),
+        ionic_radius: Some(1.02),
+        vdw_radius: Some(2.27),
+        atomic_radius: Some(1.8),
+        electronegativity: Some(0.93),
+        oxidation_states: &[1],
+        jmol_color: "#AB5CF2",
+        cpk_color: "#0000FF",
+        vesta_color: "#F9DC3C",
+    },
+    Element {
+        number: 12,
+        symbol: "Mg",
+        name: "Magnesium",
+        mass: 24.305,
+        covalent_radius: Some(1.41),
+        ionic_radius: Some(0.72),
+        vdw_radius: Some(1.73),
+        atomic_radius: Some(1.5),
+        electronegativity: Some(1.31),
+        oxidation_states: &[2],
+        jmol_color: "#8AFF00",
+        cpk_color: "#228B22",
+        vesta_color: "#FB7B15",
+    },
+    Element {
+        number: 13,
+        symbol: "Al",
+        name: "Aluminium",
+        mass: 26.982,
+        covalent_radius: Some(1.21),
+        ionic_radius: Some(0.535),
+        vdw_radius: Some(1.84),
+        atomic_radius: Some(1.25),
+        electronegativity: Some(1.61),
+        oxidation_states: &[3],
+        jmol_color: "#BFA6A6",
+        cpk_color: "#808090",
+        vesta_color: "#81B2D6",
+    },
+    Element {
+        number: 14,
+        symbol: "Si",
+        name: "Silicon",
+        mass: 28.085,
+        covalent_radius: Some(1.11),
+        ionic_radius: Some(0.4),
+        vdw_radius: Some(2.1),
+        atomic_radius: Some(1.1),
+        electronegativity: Some(1.9),
+        oxidation_states: &[-4, 4],
+        jmol_color: "#F0C8A0",
+        cpk_color: "#DAA520",
+        vesta_color: "#1B3BFA",
+    },
+    Element {
+        number: 15,
+        symbol: "P",
+        name: "Phosphorus",
+        mass: 30.974,
+        covalent_radius: Some(1.07),
+        ionic_radius: Some(0.38),
+        vdw_radius: Some(1.8),
+        atomic_radius: Some(1.0),
+        electronegativity: Some(2.19),
+        oxidation_states: &[-3, 3, 5],
+        jmol_color: "#FF8000",
+        cpk_color: "#FFA500",
+        vesta_color: "#C09CC2",
+    },
+    Element {
+        number: 16,
+        symbol: "S",
+        name: "Sulfur",
+        mass: 32.06,
+        covalent_radius: Some(1.05),
+        ionic_radius: Some(1.84),
+        vdw_radius: Some(1.8),
+        atomic_radius: Some(1.0),
+        electronegativity: Some(2.58),
+        oxidation_states: &[-2, 2, 4, 6],
+        jmol_color: "#FFFF30",
+        cpk_color: "#FFC832",
+        vesta_color: "#FFFA00",
+    },
+    Element {
+        number: 17,
+        symbol: "Cl",
+        name: "Chlorine",
+        mass: 35.45,
+        covalent_radius: Some(1.02),
+        ionic_radius: Some(1.81),
+        vdw_radius: Some(1.75),
+        atomic_radius: Some(1.0),
+        electronegativity: Some(3.16),
+        oxidation_states: &[-1, 1, 3, 5, 7],
+        jmol_color: "#1FF01F",
+        cpk_color: "#00FF00",
+        vesta_color: "#31FC02",
+    },
+    Element {
+        number: 18,
+        symbol: "Ar",
+        name: "Argon",
+        mass: 39.948,
+        covalent_radius: Some(1.06),
+        ionic_radius: None,
+        vdw_radius: Some(1.88),
+        atomic_radius: Some(0.71),
+        electronegativity: None,
+        oxidation_states: &[],
+        jmol_color: "#80D1E3",
+        cpk_color: "#FF1493",
+        vesta_color: "#CFFEC4",
+    },
+    Element {
+        number: 19,
+        symbol: "K",
+        name: "Potassium",
+        mass: 39.098,
+        covalent_radius: Some(2.03),
+        ionic_radius: Some(1.38),
+        vdw_radius: Some(2.75),
+        atomic_radius: Some(2.2),
+        electronegativity: Some(0.82),
+        oxidation_states: &[1],
+        jmol_color: "#8F40D4",
+        cpk_color: "#FF1493",
+        vesta_color: "#A121F6",
+    },
+    Element {
+        number: 20,
+        symbol: "Ca",
+        name: "Calcium",
+        mass: 40.078,
+        covalent_radius: Some(1.76),
+        ionic_radius: Some(1.0),
+        vdw_radius: Some(2.31),
+        atomic_radius: Some(1.8),
+        electronegativity: Some(1.0),
+        oxidation_states: &[2],
+        jmol_color: "#3DFF00",
+        cpk_color: "#808090",
+        vesta_color: "#5A96BD",
+    },
+    Element {
+        number: 21,
+        symbol: "Sc",
+        name: "Scandium",
+        mass: 44.956,
+        covalent_radius: Some(1.7),
+        ionic_radius: Some(0.745),
+        vdw_radius: Some(2.58),
+        atomic_radius: Some(1.6),
+        electronegativity: Some(1.36),
+        oxidation_states: &[3],
+        jmol_color: "#E6E6E6",
+        cpk_color: "#FF1493",
+        vesta_color: "#B563AB",
+    },
+    Element {
+        number: 22,
+        symbol: "Ti",
+        name: "Titanium",
+        mass: 47.867,
+        covalent_radius: Some(1.6),
+        ionic_radius: Some(0.605),
+        vdw_radius: Some(2.46),
+        atomic_radius: Some(1.4),
+        electronegativity: Some(1.54),
+        oxidation_states: &[4],
+        jmol_color: "#BFC2C7",
+        cpk_color: "#808090",
+        vesta_color: "#78CAFF",
+    },
+    Element {
+        number: 23,
+        symbol: "V",
+        name: "Vanadium",
+        mass: 50.942,
+        covalent_radius: Some(1.53),
+        ionic_radius: Some(0.54),
+        vdw_radius: Some(2.42),
+        atomic_radius: Some(1.35),
+        electronegativity: Some(1.63),
+        oxidation_states: &[5],
+        jmol_color: "#A6A6AB",
+        cpk_color: "#FF1493",
+        vesta_color: "#E51900",
+    },
+    Element {
+        number: 24,
+        symbol: "Cr",
+        name: "Chromium",
+        mass: 51.996,
+        covalent_radius: Some(1.39),
+        ionic_radius: Some(0.615),
+        vdw_radius: Some(2.45),
+        atomic_radius: Some(1.4),
+        electronegativity: Some(1.66),
+        oxidation_states: &[3, 6],
+        jmol_color: "#8A99C7",
+        cpk_color: "#808090",
+        vesta_color: "#00009E",
+    },
+    Element {
+        number: 25,
+        symbol: "Mn",
+        name: "Manganese",
+        mass: 54.938,
+        covalent_radius: Some(1.39),
+        ionic_radius: Some(0.83),
+        vdw_radius: Some(2.45),
+        atomic_radius: Some(1.4),
+        electronegativity: Some(1.55),
+        oxidation_states: &[2, 4, 7],
+        jmol_color: "#9C7AC7",
+        cpk_color: "#808090",
+        vesta_color: "#A8089E",
+    },
+    Element {
+        number: 26,
+        symbol: "Fe",
+        name: "Iron",
+        mass: 55.845,
+        covalent_radius: Some(1.32),
+        ionic_radius: Some(0.645),
+        vdw_radius: Some(2.44),
+        atomic_radius: Some(1.4),
+        electronegativity: Some(1.83),
+        oxidation_states: &[2, 3],
+        jmol_color: "#E06633",
+        cpk_color: "#FFA500",
+        vesta_color: "#B57200",
+    },
+    Element {
+        number: 27,
+        symbol: "Co",
+        name: "Cobalt",
+        mass: 58.933,
+        covalent_radius: Some(1.26),
+        ionic_radius: Some(0.745),
+        vdw_radius: Some(2.4),
+        atomic_radius: Some(1.35),
+        electronegativity: Some(1.88),
+        oxidation_states: &[2, 3],
+        jmol_color: "#F090A0",
+        cpk_color: "#FF1493",
+        vesta_color: "#0000AF",
+    },
+    Element {
+        number: 28,
+        symbol: "Ni",
+        name: "Nickel",
+        mass: 58.693,
+        covalent_radius: Some(1.24),
+        ionic_radius: Some(0.69),
+        vdw_radius: Some(1.63),
+        atomic_radius: Some(1.35),
+        electronegativity: Some(1.91),
+        oxidation_states: &[2],
+        jmol_color: "#50D050",
+        cpk_color: "#A52A2A",
+        vesta_color: "#B7BBBD",
+    },
+    Element {
+        number: 29,
+        symbol: "Cu",
+        name: "Copper",
+        mass: 63.546,
+        covalent_radius: Some(1.32),
+        ionic_radius: Some(0.73),
+        vdw_radius: Some(1.4),
+        atomic_radius: Some(1.35),
+        electronegativity: Some(1.9),
+        oxidation_states: &[2],
+        jmol_color: "#C88033",
+        cpk_color: "#A52A2A",
+        vesta_color: "#2247DC",
+    },
+    Element {
+        number: 30,
+        symbol: "Zn",
+        name: "Zinc",
+        mass: 65.38,
+        covalent_radius: Some(1.22),
+        ionic_radius: Some(0.74),
+        vdw_radius: Some(1.39),
+        atomic_radius: Some(1.35),
+        electronegativity: Some(1.65),
+        oxidation_states: &[2],
+        jmol_color: "#7D80B0",
+        cpk_color: "#A52A2A",
+        vesta_color: "#8F9082",
+    },
+    Element {
+        number: 31,
+        symbol: "Ga",
+        name: "Gallium",
+        mass: 69.723,
+        covalent_radius: Some(1.22),
+        ionic_radius: Some(0.62),
+        vdw_radius: Some(1.87),
+        atomic_radius: Some(1.3),
+        electronegativity: Some(1.81),
+        oxidation_states: &[3],
+        jmol_color: "#C28F8F",
+        cpk_color: "#FF1493",
+        vesta_color: "#9EE373",
+    },
+    Element {
+        number: 32,
+        symbol: "Ge",
+        name: "Germanium",
+        mass: 72.63,
+        covalent_radius: Some(1.2),
+        ionic_radius: Some(0.53),
+        vdw_radius: Some(2.11),
+        atomic_radius: Some(1.25),
+        electronegativity: Some(2.01),
+        oxidation_states: &[-4, 2, 4],
+        jmol_color: "#668F8F",
+        cpk_color: "#FF1493",
+        vesta_color: "#7E6FA6",
+    },
+    Element {
+        number: 33,
+        symbol: "As",
+        name: "Arsenic",
+        mass: 74.922,
+        covalent_radius: Some(1.19),
+        ionic_radius: Some(0.46),
+        vdw_radius: Some(1.85),
+        atomic_radius: Some(1.15),
+        electronegativity: Some(2.18),
+        oxidation_states: &[-3, 3, 5],
+        jmol_color: "#BD80E3",
+        cpk_color: "#FF1493",
+        vesta_color: "#74D057",
+    },
+    Element {
+        number: 34,
+        symbol: "Se",
+        name: "Selenium",
+        mass: 78.971,
+        covalent_radius: Some(1.2),
+        ionic_radius: Some(1.98),
+        vdw_radius: Some(1.9),
+        atomic_radius: Some(1.15),
+        electronegativity: Some(2.55),
+        oxidation_states: &[-2, 2, 4, 6],
+        jmol_color: "#FFA100",
+        cpk_color: "#FF1493",
+        vesta_color: "#9AEF0F",
+    },
+    Element {
+        number: 35,
+        symbol: "Br",
+        name: "Bromine",
+        mass: 79.904,
+        covalent_radius: Some(1.2),
+        ionic_radius: Some(1.96),
+        vdw_radius: Some(1.85),
+        atomic_radius: Some(1.15),
+        electronegativity: Some(2.96),
+        oxidation_states: &[-1, 1, 3, 5],
+        jmol_color: "#A62929",
+        cpk_color: "#A52A2A",
+        vesta_color: "#7E3102",
+    },
+    Element {
+        number: 36,
+        symbol: "Kr",
+        name: "Krypton",
+        mass: 83.798,
+        covalent_radius: Some(1.16),
+        ionic_radius: None,
+        vdw_radius: Some(2.02),
+        atomic_radius: Some(0.88),
+        electronegativity: Some(3.0),
+        oxidation_states: &[2],
+        jmol_color: "#5CB8D1",
+        cpk_color: "#FF1493",
+        vesta_color: "#FAC1F3",
+    },
+    Element {
+        number: 37,
+        symbol: "Rb",
+        name: "Rubidium",
+        mass: 85.468,
+        covalent_radius: Some(2.2),
+        ionic_radius: Some(1.52),
+        vdw_radius: Some(3.03),
+        atomic_radius: Some(2.35),
+        electronegativity: Some(0.82),
+        oxidation_states: &[1],
+        jmol_color: "#702EB0",
+        cpk_color: "#FF1493",
+        vesta_color: "#FF0099",
+    },
+    Element {
+        number: 38,
+        symbol: "Sr",
+        name: "Strontium",
+        mass: 87.62,
+        covalent_radius: Some(1.95),
+        ionic_radius: Some(1.18),
+        vdw_radius: Some(2.49),
+        atomic_radius: Some(2.0),
+        electronegativity: Some(0.95),
+        oxidation_states: &[2],
+        jmol_color: "#00FF00",
+        cpk_color: "#FF1493",
+        vesta_color: "#00FF26",
+    },
+    Element {
+        number: 39,
+        symbol: "Y",
+        name: "Yttrium",
+        mass: 88.906,
+        covalent_radius: Some(1.9),
+        ionic_radius: Some(0.9),
+        vdw_radius: Some(2.75),
+        atomic_radius: Some(1.8),
+        electronegativity: Some(1.22),
+        oxidation_states: &[3],
+        jmol_color: "#94FFFF",
+        cpk_color: "#FF1493",
+        vesta_color: "#66988E",
+    },
+    Element {
+        number: 40,
+        symbol: "Zr",
+        name: "Zirconium",
+        mass: 91.224,
+        covalent_radius: Some(1.75),
+        ionic_radius: Some(0.72),
+        vdw_radius: Some(2.52),
+        atomic_radius: Some(1.55),
+        electronegativity: Some(1.33),
+        oxidation_states: &[4],
+        jmol_color: "#94E0E0",
+        cpk_color: "#FF1493",
+        vesta_color: "#00FF00",
+    },
+    Element {
+        number: 41,
+        symbol: "Nb",
+        name: "Niobium",
+        mass: 92.906,
+        covalent_radius: Some(1.64),
+        ionic_radius: Some(0.64),
+        vdw_radius: Some(2.56),
+        atomic_radius: Some(1.45),
+        electronegativity: Some(1.6),
+        oxidation_states: &[5],
+        jmol_color: "#73C2C9",
+        cpk_color: "#FF1493",
+        vesta_color: "#4CB276",
+    },
+    Element {
+        number: 42,
+        symbol: "Mo",
+        name: "Molybdenum",
+        mass: 95.95,
+        covalent_radius: Some(1.54),
+        ionic_radius: Some(0.59),
+        vdw_radius: Some(2.45),
+        atomic_radius: Some(1.45),
+        electronegativity: Some(2.16),
+        oxidation_states: &[4, 6],
+        jmol_color: "#54B5B5",
+        cpk_color: "#FF1493",
+        vesta_color: "#B486AF",
+    },
+    Element {
+        number: 43,
+        symbol: "Tc",
+        name: "Technetium",
+        mass: 98.0,
+        covalent_radius: Some(1.47),
+        ionic_radius: Some(0.645),
+        vdw_radius: Some(2.44),
+        atomic_radius: Some(1.35),
+        electronegativity: Some(1.9),
+        oxidation_states: &[4, 7],
+        jmol_color: "#3B9E9E",
+        cpk_color: "#FF1493",
+        vesta_color: "#CDAFCA",
+    },
+    Element {
+        number: 44,
+        symbol: "Ru",
+        name: "Ruthenium",
+        mass: 101.07,
+        covalent_radius: Some(1.46),
+        ionic_radius: Some(0.62),
+        vdw_radius: Some(2.46),
+        atomic_radius: Some(1.3),
+        electronegativity: Some(2.2),
+        oxidation_states: &[3, 4],
+        jmol_color: "#248F8F",
+        cpk_color: "#FF1493",
+        vesta_color: "#CFB8AD",
+    },
+    Element {
+        number: 45,
+        symbol: "Rh",
+        name: "Rhodium",
+        mass: 102.91,
+        covalent_radius: Some(1.42),
+        ionic_radius: Some(0.665),
+        vdw_radius: Some(2.44),
+        atomic_radius: Some(1.35),
+        electronegativity: Some(2.28),
+        oxidation_states: &[3],
+        jmol_color: "#0A7D8C",
+        cpk_color: "#FF1493",
+        vesta_color: "#CDD1AB",
+    },
+    Element {
+        number: 46,
+        symbol: "Pd",
+        name: "Palladium",
+        mass: 106.42,
+        covalent_radius: Some(1.39),
+        ionic_radius: Some(0.86),
+        vdw_radius: Some(1.63),
+        atomic_radius: Some(1.4),
+        electronegativity: Some(2.2),
+        oxidation_states: &[0, 2, 4],
+        jmol_color: "#006985",
+        cpk_color: "#FF1493",
+        vesta_color: "#C1C3B8",
+    },
+    Element {
+        number: 47,
+        symbol: "Ag",
+        name: "Silver",
+        mass: 107.87,
+        covalent_radius: Some(1.45),
+        ionic_radius: Some(1.15),
+        vdw_radius: Some(1.72),
+        atomic_radius: Some(1.6),
+        electronegativity: Some(1.93),
+        oxidation_states: &[1],
+        jmol_color: "#C0C0C0",
+        cpk_color: "#808090",
+        vesta_color: "#B7BBBD",
+    },
+    Element {
+        number: 48,
+        symbol: "Cd",
+        name: "Cadmium",
+        mass: 112.41,
+        covalent_radius: Some(1.44),
+        ionic_radius: Some(0.95),
+        vdw_radius: Some(1.58),
+        atomic_radius: Some(1.55),
+        electronegativity: Some(1.69),
+        oxidation_states: &[2],
+        jmol_color: "#FFD98F",
+        cpk_color: "#FF1493",
+        vesta_color: "#F21FDC",
+    },
+    Element {
+        number: 49,
+        symbol: "In",
+        name: "Indium",
+        mass: 114.82,
+        covalent_radius: Some(1.42),
+        ionic_radius: Some(0.8),
+        vdw_radius: Some(1.93),
+        atomic_radius: Some(1.55),
+        electronegativity: Some(1.78),
+        oxidation_states: &[3],
+        jmol_color: "#A67573",
+        cpk_color: "#FF1493",
+        vesta_color: "#D781BB",
+    },
+    Element {
+        number: 50,
+        symbol: "Sn",
+        name: "Tin",
+        mass: 118.71,
+        covalent_radius: Some(1.39),
+        ionic_radius: Some(0.69),
+        vdw_radius: Some(2.17),
+        atomic_radius: Some(1.45),
+        electronegativity: Some(1.96),
+        oxidation_states: &[-4, 2, 4],
+        jmol_color: "#668080",
+        cpk_color: "#FF1493",
+        vesta_color: "#9A8FBA",
+    },
+    Element {
+        number: 51,
+        symbol: "Sb",
+        name: "Antimony",
+        mass: 121.76,
+        covalent_radius: Some(1.39),
+        ionic_radius: Some(0.6),
+        vdw_radius: Some(2.06),
+        atomic_radius: Some(1.45),
+        electronegativity: Some(2.05),
+        oxidation_states: &[-3, 3, 5],
+        jmol_color: "#9E63B5",
+        cpk_color: "#FF1493",
+        vesta_color: "#D88350",
+    },
+    Element {
+        number: 52,
+        symbol: "Te",
+        name: "Tellurium",
+        mass: 127.6,
+        covalent_radius: Some(1.38),
+        ionic_radius: Some(2.21),
+        vdw_radius: Some(2.06),
+        atomic_radius: Some(1.4),
+        electronegativity: Some(2.1),
+        oxidation_states: &[-2, 2, 4, 6],
+        jmol_color: "#D47A00",
+        cpk_color: "#FF1493",
+        vesta_color: "#ADA251",
+    },
+    Element {
+        number: 53,
+        symbol: "I",
+        name: "Iodine",
+        mass: 126.9,
+        covalent_radius: Some(1.39),
+        ionic_radius: Some(2.2),
+        vdw_radius: Some(1.98),
+        atomic_radius: Some(1.4),
+        electronegativity: Some(2.66),
+        oxidation_states: &[-1, 1, 3, 5, 7],
+        jmol_color: "#940094",
+        cpk_color: "#A020F0",
+        vesta_color: "#8E1F8A",
+    },
+    Element {
+        number: 54,
+        symbol: "Xe",
+        name: "Xenon",
+        mass: 131.29,
+        covalent_radius: Some(1.4),
+        ionic_radius: None,
+        vdw_radius: Some(2.16),
+        atomic_radius: Some(1.08),
+        electronegativity: Some(2.6),
+        oxidation_states: &[2, 4, 6],
+        jmol_color: "#429EB0",
+        cpk_color: "#FF1493",
+        vesta_color: "#9AA1F8",
+    },
+    Element {
+        number: 55,
+        symbol: "Cs",
+        name: "Caesium",
+        mass: 132.91,
+        covalent_radius: Some(2.44),
+        ionic_radius: Some(1.67),
+        vdw_radius: Some(3.43),
+        atomic_radius: Some(2.6),
+        electronegativity: Some(0.79),
+        oxidation_states: &[1],
+        jmol_color: "#57178F",
+        cpk_color: "#FF1493",
+        vesta_color: "#0EFEB9",
+    },
+    Element {
+        number: 56,
+        symbol: "Ba",
+        name: "Barium",
+        mass: 137.33,
+        covalent_radius: Some(2.15),
+        ionic_radius: Some(1.35),
+        vdw_radius: Some(2.68),
+        atomic_radius: Some(2.15),
+        electronegativity: Some(0.89),
+        oxidation_states: &[2],
+        jmol_color: "#00C900",
+        cpk_color: "#FFA500",
+        vesta_color: "#1EF02D",
+    },
+    Element {
+        number: 57,
+        symbol: "La",
+        name: "Lanthanum",
+        mass: 138.91,
+        covalent_radius: Some(2.07),
+        ionic_radius: Some(1.032),
+        vdw_radius: Some(2.98),
+        atomic_radius: Some(1.95),
+        electronegativity: Some(1.1),
+        oxidation_states: &[3],
+        jmol_color: "#70D4FF",
+        cpk_color: "#FF1493",
+        vesta_color: "#5AC449",
+    },
+    Element {
+        number: 58,
+        symbol: "Ce",
+        name: "Cerium",
+        mass: 140.12,
+        covalent_radius: Some(2.04),
+        ionic_radius: Some(1.01),
+        vdw_radius: Some(2.88),
+        atomic_radius: Some(1.85),
+        electronegativity: Some(1.12),
+        oxidation_states: &[3, 4],
+        jmol_color: "#FFFFC7",
+        cpk_color: "#FF1493",
+        vesta_color: "#D1FC06",
+    },
+    Element {
+        number: 59,
+        symbol: "Pr",
+        name: "Praseodymium",
+        mass: 140.91,
+        covalent_radius: Some(2.03),
+        ionic_radius: Some(0.99),
+        vdw_radius: Some(2.92),
+        atomic_radius: Some(1.85),
+        electronegativity: Some(1.13),
+        oxidation_states: &[3],
+        jmol_color: "#D9FFC7",
+        cpk_color: "#FF1493",
+        vesta_color: "#FCE105",
+    },
+    Element {
+        number: 60,
+        symbol: "Nd",
+        name: "Neodymium",
+        mass: 144.24,
+        covalent_radius: Some(2.01),
+        ionic_radius: Some(0.983),
+        vdw_radius: Some(2.95),
+        atomic_radius: Some(1.85),
+        electronegativity: Some(1.14),
+        oxidation_states: &[3],
+        jmol_color: "#C7FFC7",
+        cpk_color: "#FF1493",
+        vesta_color: "#FB8D06",
+    },
+    Element {
+        number: 61,
+        symbol: "Pm",
+        name: "Promethium",
+        mass: 145.0,
+        covalent_radius: Some(1.99),
+        ionic_radius: Some(0.97),
+        vdw_radius: Some(2.9),
+        atomic_radius: Some(1.85),
+        electronegativity: Some(1.13),
+        oxidation_states: &[3],
+        jmol_color: "#A3FFC7",
+        cpk_color: "#FF1493",
+        vesta_color: "#0000F5",
+    },
+    Element {
+        number: 62,
+        symbol: "Sm",
+        name: "Samarium",
+        mass: 150.36,
+        covalent_radius: Some(1.98),
+        ionic_radius: Some(0.958),
+        vdw_radius: Some(2.9),
+        atomic_radius: Some(1.85),
+        electronegativity: Some(1.17),
+        oxidation_states: &[3],
+        jmol_color: "#8FFFC7",
+        cpk_color: "#FF1493",
+        vesta_color: "#FC067D",
+    },
+    Element {
+        number: 63,
+        symbol: "Eu",
+        name: "Europium",
+        mass: 151.96,
+        covalent_radius: Some(1.98),
+        ionic_radius: Some(0.947),
+        vdw_radius: Some(2.87),
+        atomic_radius: Some(1.85),
+        electronegativity: Some(1.2),
+        oxidation_states: &[2, 3],
+        jmol_color: "#61FFC7",
+        cpk_color: "#FF1493",
+        vesta_color: "#FA07D5",
+    },
+    Element {
+        number: 64,
+        symbol: "Gd",
+        name: "Gadolinium",
+        mass: 157.25,
+        covalent_radius: Some(1.96),
+        ionic_radius: Some(0.938),
+        vdw_radius: Some(2.83),
+        atomic_radius: Some(1.8),
+        electronegativity: Some(1.2),
+        oxidation_states: &[3],
+        jmol_color: "#45FFC7",
+        cpk_color: "#FF1493",
+        vesta_color: "#C003FF",
+    },
+    Element {
+        number: 65,
+        symbol: "Tb",
+        name: "Terbium",
+        mass: 158.93,
+        covalent_radius: Some(1.94),
+        ionic_radius: Some(0.923),
+        vdw_radius: Some(2.79),
+        atomic_radius: Some(1.75),
+        electronegativity: Some(1.1),
+        oxidation_states: &[3],
+        jmol_color: "#30FFC7",
+        cpk_color: "#FF1493",
+        vesta_color: "#7104FE",
+    },
+    Element {
+        number: 66,
+        symbol: "Dy",
+        name: "Dysprosium",
+        mass: 162.5,
+        covalent_radius: Some(1.92),
+        ionic_radius: Some(0.912),
+        vdw_radius: Some(2.87),
+        atomic_radius: Some(1.75),
+        electronegativity: Some(1.22),
+        oxidation_states: &[3],
+        jmol_color: "#1FFFC7",
+        cpk_color: "#FF1493",
+        vesta_color: "#3106FC",
+    },
+    Element {
+        number: 67,
+        symbol: "Ho",
+        name: "Holmium",
+        mass: 164.93,
+        covalent_radius: Some(1.92),
+        ionic_radius: Some(0.901),
+        vdw_radius: Some(2.81),
+        atomic_radius: Some(1.75),
+        electronegativity: Some(1.23),
+        oxidation_states: &[3],
+        jmol_color: "#00FF9C",
+        cpk_color: "#FF1493",
+        vesta_color: "#0741FB",
+    },
+    Element {
+        number: 68,
+        symbol: "Er",
+        name: "Erbium",
+        mass: 167.26,
+        covalent_radius: Some(1.89),
+        ionic_radius: Some(0.89),
+        vdw_radius: Some(2.83),
+        atomic_radius: Some(1.75),
+        electronegativity: Some(1.24),
+        oxidation_states: &[3],
+        jmol_color: "#00E675",
+        cpk_color: "#FF1493",
+        vesta_color: "#49723A",
+    },
+    Element {
+        number: 69,
+        symbol: "Tm",
+        name: "Thulium",
+        mass: 168.93,
+        covalent_radius: Some(1.9),
+        ionic_radius: Some(0.88),
+        vdw_radius: Some(2.79),
+        atomic_radius: Some(1.75),
+        electronegativity: Some(1.25),
+        oxidation_states: &[3],
+        jmol_color: "#00D452",
+        cpk_color: "#FF1493",
+        vesta_color: "#0000E0",
+    },
+    Element {
+        number: 70,
+        symbol: "Yb",
+        name: "Ytterbium",
+        mass: 173.05,
+        covalent_radius: Some(1.87),
+        ionic_radius: Some(0.868),
+        vdw_radius: Some(2.8),
+        atomic_radius: Some(1.75),
+        electronegativity: Some(1.1),
+        oxidation_states: &[3],
+        jmol_color: "#00BF38",
+        cpk_color: "#FF1493",
+        vesta_color: "#27FCF4",
+    },
+    Element {
+        number: 71,
+        symbol: "Lu",
+        name: "Lutetium",
+        mass: 174.97,
+        covalent_radius: Some(1.87),
+        ionic_radius: Some(0.861),
+        vdw_radius: Some(2.74),
+        atomic_radius: Some(1.75),
+        electronegativity: Some(1.27),
+        oxidation_states: &[3],
+        jmol_color: "#00AB24",
+        cpk_color: "#FF1493",
+        vesta_color: "#26FDB5",
+    },
+    Element {
+        number: 72,
+        symbol: "Hf",
+        name: "Hafnium",
+        mass: 178.49,
+        covalent_radius: Some(1.75),
+        ionic_radius: Some(0.71),
+        vdw_radius: Some(2.63),
+        atomic_radius: Some(1.55),
+        electronegativity: Some(1.3),
+        oxidation_states: &[4],
+        jmol_color: "#4DC2FF",
+        cpk_color: "#FF1493",
+        vesta_color: "#B4B459",
+    },
+    Element {
+        number: 73,
+        symbol: "Ta",
+        name: "Tantalum",
+        mass: 180.95,
+        covalent_radius: Some(1.7),
+        ionic_radius: Some(0.64),
+        vdw_radius: Some(2.53),
+        atomic_radius: Some(1.45),
+        electronegativity: Some(1.5),
+        oxidation_states: &[5],
+        jmol_color: "#4DA6FF",
+        cpk_color: "#FF1493",
+        vesta_color: "#B79A56",
+    },
+    Element {
+        number: 74,
+        symbol: "W",
+        name: "Tungsten",
+        mass: 183.84,
+        covalent_radius: Some(1.62),
+        ionic_radius: Some(0.6),
+        vdw_radius: Some(2.57),
+        atomic_radius: Some(1.35),
+        electronegativity: Some(2.36),
+        oxidation_states: &[4, 6],
+        jmol_color: "#2194D6",
+        cpk_color: "#FF1493",
+        vesta_color: "#8D8A80",
+    },
+    Element {
+        number: 75,
+        symbol: "Re",
+        name: "Rhenium",
+        mass: 186.21,
+        covalent_radius: Some(1.51),
+        ionic_radius: Some(0.63),
+        vdw_radius: Some(2.49),
+        atomic_radius: Some(1.35),
+        electronegativity: Some(1.9),
+        oxidation_states: &[4],
+        jmol_color: "#267DAB",
+        cpk_color: "#FF1493",
+        vesta_color: "#B3B08E",
+    },
+    Element {
+        number: 76,
+        symbol: "Os",
+        name: "Osmium",
+        mass: 190.23,
+        covalent_radius: Some(1.44),
+        ionic_radius: Some(0.63),
+        vdw_radius: Some(2.48),
+        atomic_radius: Some(1.3),
+        electronegativity: Some(2.2),
+        oxidation_states: &[4],
+        jmol_color: "#266696",
+        cpk_color: "#FF1493",
+        vesta_color: "#C9B179",
+    },
+    Element {
+        number: 77,
+        symbol: "Ir",
+        name: "Iridium",
+        mass: 192.22,
+        covalent_radius: Some(1.41),
+        ionic_radius: Some(0.625),
+        vdw_radius: Some(2.41),
+        atomic_radius: Some(1.35),
+        electronegativity: Some(2.2),
+        oxidation_states: &[3, 4],
+        jmol_color: "#175487",
+        cpk_color: "#FF1493",
+        vesta_color: "#C9CF73",
+    },
+    Element {
+        number: 78,
+        symbol: "Pt",
+        name: "Platinum",
+        mass: 195.08,
+        covalent_radius: Some(1.36),
+        ionic_radius: Some(0.8),
+        vdw_radius: Some(1.75),
+        atomic_radius: Some(1.35),
+        electronegativity: Some(2.28),
+        oxidation_states: &[2, 4],
+        jmol_color: "#D0D0E0",
+        cpk_color: "#FF1493",
+        vesta_color: "#CCC6BF",
+    },
+    Element {
+        number: 79,
+        symbol: "Au",
+        name: "Gold",
+        mass: 196.97,
+        covalent_radius: Some(1.36),
+        ionic_radius: Some(0.85),
+        vdw_radius: Some(1.66),
+        atomic_radius: Some(1.35),
+        electronegativity: Some(2.54),
+        oxidation_states: &[3],
+        jmol_color: "#FFD123",
+        cpk_color: "#DAA520",
+        vesta_color: "#FEB338",
+    },
+    Element {
+        number: 80,
+        symbol: "Hg",
+        name: "Mercury",
+        mass: 200.59,
+        covalent_radius: Some(1.32),
+        ionic_radius: Some(1.02),
+        vdw_radius: Some(1.55),
+        atomic_radius: Some(1.5),
+        electronegativity: Some(2.0),
+        oxidation_states: &[1, 2],
+        jmol_color: "#B8B8D0",
+        cpk_color: "#FF1493",
+        vesta_color: "#D3B8CB",
+    },
+    Element {
+        number: 81,
+        symbol: "Tl",
+        name: "Thallium",
+        mass: 204.38,
+        covalent_radius: Some(1.45),
+        ionic_radius: Some(1.5),
+        vdw_radius: Some(1.96),
+        atomic_radius: Some(1.9),
+        electronegativity: Some(1.62),
+        oxidation_states: &[1, 3],
+        jmol_color: "#A6544D",
+        cpk_color: "#FF1493",
+        vesta_color: "#96896D",
+    },
+    Element {
+        number: 82,
+        symbol: "Pb",
+        name: "Lead",
+        mass: 207.2,
+        covalent_radius: Some(1.46),
+        ionic_radius: Some(1.19),
+        vdw_radius: Some(2.02),
+        atomic_radius: Some(1.8),
+        electronegativity: Some(2.33),
+        oxidation_states: &[2, 4],
+        jmol_color: "#575961",
+        cpk_color: "#FF1493",
+        vesta_color: "#52535B",
+    },
+    Element {
+        number: 83,
+        symbol: "Bi",
+        name: "Bismuth",
+        mass: 208.98,
+        covalent_radius: Some(1.48),
+        ionic_radius: Some(1.03),
+        vdw_radius: Some(2.07),
+        atomic_radius: Some(1.6),
+        electronegativity: Some(2.02),
+        oxidation_states: &[3],
+        jmol_color: "#9E4FB5",
+        cpk_color: "#FF1493",
+        vesta_color: "#D230F8",
+    },
+    Element {
+        number: 84,
+        symbol: "Po",
+        name: "Polonium",
+        mass: 209.0,
+        covalent_radius: Some(1.4),
+        ionic_radius: Some(0.94),
+        vdw_radius: Some(1.97),
+        atomic_radius: Some(1.9),
+        electronegativity: Some(2.0),
+        oxidation_states: &[-2, 2, 4],
+        jmol_color: "#AB5C00",
+        cpk_color: "#FF1493",
+        vesta_color: "#0000FF",
+    },
+    Element {
+        number: 85,
+        symbol: "At",
+        name: "Astatine",
+        mass: 210.0,
+        covalent_radius: Some(1.5),
+        ionic_radius: Some(0.62),
+        vdw_radius: Some(2.02),
+        atomic_radius: Some(1.27),
+        electronegativity: Some(2.2),
+        oxidation_states: &[-1, 1],
+        jmol_color: "#754F45",
+        cpk_color: "#FF1493",
+        vesta_color: "#0000FF",
+    },
+    Element {
+        number: 86,
+        symbol: "Rn",
+        name: "Radon",
+        mass: 222.0,
+        covalent_radius: Some(1.5),
+        ionic_radius: None,
+        vdw_radius: Some(2.2),
+        atomic_radius: Some(1.2),
+        electronegativity: Some(2.2),
+        oxidation_states: &[2],
+        jmol_color: "#428296",
+        cpk_color: "#FF1493",
+        vesta_color: "#FFFF00",
+    },
+    Element {
+        number: 87,
+        symbol: "Fr",
+        name: "Francium",
+        mass: 223.0,
+        covalent_radius: Some(2.6),
+        ionic_radius: Some(1.8),
+        vdw_radius: Some(3.48),
+        atomic_radius: None,
+        electronegativity: Some(0.7),
+        oxidation_states: &[1],
+        jmol_color: "#420066",
+        cpk_color: "#FF1493",
+        vesta_color: "#000000",
+    },
+    Element {
+        number: 88,
+        symbol: "Ra",
+        name: "Radium",
+        mass: 226.0,
+        covalent_radius: Some(2.21),
+        ionic_radius: Some(1.48),
+        vdw_radius: Some(2.83),
+        atomic_radius: Some(2.15),
+        electronegativity: Some(0.9),
+        oxidation_states: &[2],
+        jmol_color: "#007D00",
+        cpk_color: "#FF1493",
+        vesta_color: "#6DAA58",
+    },
+    Element {
+        number: 89,
+        symbol: "Ac",
+        name: "Actinium",
+        mass: 227.0,
+        covalent_radius: Some(2.15),
+        ionic_radius: Some(1.12),
+        vdw_radius: Some(2.8),
+        atomic_radius: Some(1.95),
+        electronegativity: Some(1.1),
+        oxidation_states: &[3],
+        jmol_color: "#70ABFA",
+        cpk_color: "#FF1493",
+        vesta_color: "#649E72",
+    },
+    Element {
+        number: 90,
+        symbol: "Th",
+        name: "Thorium",
+        mass: 232.04,
+        covalent_radius: Some(2.06),
+        ionic_radius: Some(0.94),
+        vdw_radius: Some(2.93),
+        atomic_radius: Some(1.8),
+        electronegativity: Some(1.3),
+        oxidation_states: &[4],
+        jmol_color: "#00BAFF",
+        cpk_color: "#FF1493",
+        vesta_color: "#26FE78",
+    },
+    Element {
+        number: 91,
+        symbol: "Pa",
+        name: "Protactinium",
+        mass: 231.04,
+        covalent_radius: Some(2.0),
+        ionic_radius: Some(0.78),
+        vdw_radius: Some(2.88),
+        atomic_radius: Some(1.8),
+        electronegativity: Some(1.5),
+        oxidation_states: &[5],
+        jmol_color: "#00A1FF",
+        cpk_color: "#FF1493",
+        vesta_color: "#29FB35",
+    },
+    Element {
+        number: 92,
+        symbol: "U",
+        name: "Uranium",
+        mass: 238.03,
+        covalent_radius: Some(1.96),
+        ionic_radius: Some(0.89),
+        vdw_radius: Some(1.86),
+        atomic_radius: Some(1.75),
+        electronegativity: Some(1.38),
+        oxidation_states: &[6],
+        jmol_color: "#008FFF",
+        cpk_color: "#FF1493",
+        vesta_color: "#7AA2AA",
+    },
+    Element {
+        number: 93,
+        symbol: "Np",
+        name: "Neptunium",
+        mass: 237.0,
+        covalent_radius: Some(1.9),
+        ionic_radius: Some(0.87),
+        vdw_radius: Some(2.82),
+        atomic_radius: Some(1.75),
+        electronegativity: Some(1.36),
+        oxidation_states: &[5],
+        jmol_color: "#0080FF",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 94,
+        symbol: "Pu",
+        name: "Plutonium",
+        mass: 244.0,
+        covalent_radius: Some(1.87),
+        ionic_radius: Some(0.86),
+        vdw_radius: Some(2.81),
+        atomic_radius: Some(1.75),
+        electronegativity: Some(1.28),
+        oxidation_states: &[4],
+        jmol_color: "#006BFF",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 95,
+        symbol: "Am",
+        name: "Americium",
+        mass: 243.0,
+        covalent_radius: Some(1.8),
+        ionic_radius: Some(0.975),
+        vdw_radius: Some(2.83),
+        atomic_radius: Some(1.75),
+        electronegativity: Some(1.3),
+        oxidation_states: &[3],
+        jmol_color: "#545CF2",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 96,
+        symbol: "Cm",
+        name: "Curium",
+        mass: 247.0,
+        covalent_radius: Some(1.69),
+        ionic_radius: Some(0.97),
+        vdw_radius: Some(3.05),
+        atomic_radius: None,
+        electronegativity: Some(1.3),
+        oxidation_states: &[3],
+        jmol_color: "#785CE3",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 97,
+        symbol: "Bk",
+        name: "Berkelium",
+        mass: 247.0,
+        covalent_radius: None,
+        ionic_radius: Some(0.96),
+        vdw_radius: Some(3.4),
+        atomic_radius: None,
+        electronegativity: Some(1.3),
+        oxidation_states: &[3],
+        jmol_color: "#8A4FE3",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 98,
+        symbol: "Cf",
+        name: "Californium",
+        mass: 251.0,
+        covalent_radius: None,
+        ionic_radius: Some(0.95),
+        vdw_radius: Some(3.05),
+        atomic_radius: None,
+        electronegativity: Some(1.3),
+        oxidation_states: &[3],
+        jmol_color: "#A136D4",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 99,
+        symbol: "Es",
+        name: "Einsteinium",
+        mass: 252.0,
+        covalent_radius: None,
+        ionic_radius: None,
+        vdw_radius: Some(2.7),
+        atomic_radius: None,
+        electronegativity: Some(1.3),
+        oxidation_states: &[3],
+        jmol_color: "#B31FD4",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 100,
+        symbol: "Fm",
+        name: "Fermium",
+        mass: 257.0,
+        covalent_radius: None,
+        ionic_radius: None,
+        vdw_radius: None,
+        atomic_radius: None,
+        electronegativity: Some(1.3),
+        oxidation_states: &[3],
+        jmol_color: "#B31FBA",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 101,
+        symbol: "Md",
+        name: "Mendelevium",
+        mass: 258.0,
+        covalent_radius: None,
+        ionic_radius: None,
+        vdw_radius: None,
+        atomic_radius: None,
+        electronegativity: Some(1.3),
+        oxidation_states: &[3],
+        jmol_color: "#B30DA6",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 102,
+        symbol: "No",
+        name: "Nobelium",
+        mass: 259.0,
+        covalent_radius: None,
+        ionic_radius: None,
+        vdw_radius: None,
+        atomic_radius: None,
+        electronegativity: Some(1.3),
+        oxidation_states: &[2],
+        jmol_color: "#BD0D87",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 103,
+        symbol: "Lr",
+        name: "Lawrencium",
+        mass: 266.0,
+        covalent_radius: None,
+        ionic_radius: None,
+        vdw_radius: None,
+        atomic_radius: None,
+        electronegativity: None,
+        oxidation_states: &[3],
+        jmol_color: "#C70066",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 104,
+        symbol: "Rf",
+        name: "Rutherfordium",
+        mass: 267.0,
+        covalent_radius: None,
+        ionic_radius: None,
+        vdw_radius: None,
+        atomic_radius: None,
+        electronegativity: None,
+        oxidation_states: &[4],
+        jmol_color: "#CC0059",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 105,
+        symbol: "Db",
+        name: "Dubnium",
+        mass: 268.0,
+        covalent_radius: None,
+        ionic_radius: None,
+        vdw_radius: None,
+        atomic_radius: None,
+        electronegativity: None,
+        oxidation_states: &[5],
+        jmol_color: "#D1004F",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 106,
+        symbol: "Sg",
+        name: "Seaborgium",
+        mass: 269.0,
+        covalent_radius: None,
+        ionic_radius: None,
+        vdw_radius: None,
+        atomic_radius: None,
+        electronegativity: None,
+        oxidation_states: &[6],
+        jmol_color: "#D90045",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 107,
+        symbol: "Bh",
+        name: "Bohrium",
+        mass: 270.0,
+        covalent_radius: None,
+        ionic_radius: None,
+        vdw_radius: None,
+        atomic_radius: None,
+        electronegativity: None,
+        oxidation_states: &[7],
+        jmol_color: "#E00038",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 108,
+        symbol: "Hs",
+        name: "Hassium",
+        mass: 269.0,
+        covalent_radius: None,
+        ionic_radius: None,
+        vdw_radius: None,
+        atomic_radius: None,
+        electronegativity: None,
+        oxidation_states: &[8],
+        jmol_color: "#E6002E",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 109,
+        symbol: "Mt",
+        name: "Meitnerium",
+        mass: 278.0,
+        covalent_radius: None,
+        ionic_radius: None,
+        vdw_radius: None,
+        atomic_radius: None,
+        electronegativity: None,
+        oxidation_states: &[],
+        jmol_color: "#EB0026",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 110,
+        symbol: "Ds",
+        name: "Darmstadtium",
+        mass: 281.0,
+        covalent_radius: None,
+        ionic_radius: None,
+        vdw_radius: None,
+        atomic_radius: None,
+        electronegativity: None,
+        oxidation_states: &[],
+        jmol_color: "#EB0026",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 111,
+        symbol: "Rg",
+        name: "Roentgenium",
+        mass: 282.0,
+        covalent_radius: None,
+        ionic_radius: None,
+        vdw_radius: None,
+        atomic_radius: None,
+        electronegativity: None,
+        oxidation_states: &[],
+        jmol_color: "#EB0026",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 112,
+        symbol: "Cn",
+        name: "Copernicium",
+        mass: 285.0,
+        covalent_radius: None,
+        ionic_radius: None,
+        vdw_radius: None,
+        atomic_radius: None,
+        electronegativity: None,
+        oxidation_states: &[],
+        jmol_color: "#EB0026",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 113,
+        symbol: "Nh",
+        name: "Nihonium",
+        mass: 286.0,
+        covalent_radius: None,
+        ionic_radius: None,
+        vdw_radius: None,
+        atomic_radius: None,
+        electronegativity: None,
+        oxidation_states: &[],
+        jmol_color: "#EB0026",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 114,
+        symbol: "Fl",
+        name: "Flerovium",
+        mass: 289.0,
+        covalent_radius: None,
+        ionic_radius: None,
+        vdw_radius: None,
+        atomic_radius: None,
+        electronegativity: None,
+        oxidation_states: &[],
+        jmol_color: "#EB0026",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 115,
+        symbol: "Mc",
+        name: "Moscovium",
+        mass: 290.0,
+        covalent_radius: None,
+        ionic_radius: None,
+        vdw_radius: None,
+        atomic_radius: None,
+        electronegativity: None,
+        oxidation_states: &[],
+        jmol_color: "#EB0026",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 116,
+        symbol: "Lv",
+        name: "Livermorium",
+        mass: 293.0,
+        covalent_radius: None,
+        ionic_radius: None,
+        vdw_radius: None,
+        atomic_radius: None,
+        electronegativity: None,
+        oxidation_states: &[],
+        jmol_color: "#EB0026",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 117,
+        symbol: "Ts",
+        name: "Tennessine",
+        mass: 294.0,
+        covalent_radius: None,
+        ionic_radius: None,
+        vdw_radius: None,
+        atomic_radius: None,
+        electronegativity: None,
+        oxidation_states: &[],
+        jmol_color: "#EB0026",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+    Element {
+        number: 118,
+        symbol: "Og",
+        name: "Oganesson",
+        mass: 294.0,
+        covalent_radius: None,
+        ionic_radius: None,
+        vdw_radius: None,
+        atomic_radius: None,
+        electronegativity: None,
+        oxidation_states: &[],
+        jmol_color: "#EB0026",
+        cpk_color: "#FF1493",
+        vesta_color: "#4C4C4C",
+    },
+];
+
+/// 按元素符号查找元素，大小写不敏感
+pub fn element(symbol: &str) -> Option<&'static Element> {
+    ELEMENTS.iter().find(|e| e.symbol.eq_ignore_ascii_case(symbol))
+}
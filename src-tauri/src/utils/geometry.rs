@@ -1,3 +1,5 @@
+use crate::utils::linalg::{add, det, dot, inverse, norm, scale, sub, transpose, vec_mat, Mat3, Vec3};
+
 /// 计算晶格的基矢量
 fn calculate_basis_vectors(
     a: f64,
@@ -27,77 +29,137 @@ fn calculate_basis_vectors(
     [(ax, ay, az), (bx, by, bz), (cx, cy, cz)]
 }
 
-/// 解析并转换晶体结构的原子位置为笛卡尔坐标
-pub fn get_cartesian_coordinates(
+/// 计算晶格的中心点
+pub fn get_lattice_center(vertices: &Vec<(f64, f64, f64)>) -> (f64, f64, f64) {
+    let (mut x_sum, mut y_sum, mut z_sum) = (0.0, 0.0, 0.0);
+    vertices.iter().for_each(|(x, y, z)| {
+        x_sum += x;
+        y_sum += y;
+        z_sum += z;
+    });
+    let count = vertices.len() as f64;
+    (x_sum / count, y_sum / count, z_sum / count)
+}
+
+/// 由晶格参数计算晶格矩阵（行向量为基矢量）
+pub fn lattice_from_parameters(
     a: f64,
     b: f64,
     c: f64,
     alpha: f64,
     beta: f64,
     gamma: f64,
-    frac_x: f64,
-    frac_y: f64,
-    frac_z: f64,
-) -> (f64, f64, f64) {
-    // 获取基矢量
+) -> Mat3 {
     let basis_vectors = calculate_basis_vectors(a, b, c, alpha, beta, gamma);
+    basis_vectors.map(|(x, y, z)| [x, y, z])
+}
 
-    // 转换为笛卡尔坐标
-    let x = frac_x * basis_vectors[0].0 + frac_y * basis_vectors[1].0 + frac_z * basis_vectors[2].0;
-    let y = frac_x * basis_vectors[0].1 + frac_y * basis_vectors[1].1 + frac_z * basis_vectors[2].1;
-    let z = frac_x * basis_vectors[0].2 + frac_y * basis_vectors[1].2 + frac_z * basis_vectors[2].2;
-
-    (x, y, z)
+/// 由晶格矩阵计算晶格参数 [a, b, c, α, β, γ]（角度单位为度）
+pub fn lattice_parameters(lattice: &Mat3) -> [f64; 6] {
+    let [a, b, c] = lattice.map(norm);
+    let angle = |u: Vec3, v: Vec3| (dot(u, v) / (norm(u) * norm(v))).clamp(-1.0, 1.0).acos().to_degrees();
+    [
+        a,
+        b,
+        c,
+        angle(lattice[1], lattice[2]),
+        angle(lattice[0], lattice[2]),
+        angle(lattice[0], lattice[1]),
+    ]
 }
 
-/// 计算晶格顶点
-pub fn calculate_lattice_vertices(
-    a: f64,
-    b: f64,
-    c: f64,
-    alpha: f64,
-    beta: f64,
-    gamma: f64,
-) -> Vec<(f64, f64, f64)> {
-    // 获取基矢量
-    let basis_vectors = calculate_basis_vectors(a, b, c, alpha, beta, gamma);
+/// 由晶格矩阵计算晶格顶点
+pub fn calculate_cell_vertices(cell: &Mat3) -> Vec<(f64, f64, f64)> {
+    let a = cell[0];
+    let b = cell[1];
+    let c = cell[2];
 
     vec![
         (0.0, 0.0, 0.0),
-        basis_vectors[0], // (ax, ay, az)
-        (
-            basis_vectors[0].0 + basis_vectors[1].0,
-            basis_vectors[0].1 + basis_vectors[1].1,
-            basis_vectors[0].2 + basis_vectors[1].2,
-        ),
-        basis_vectors[1], // (bx, by, bz)
-        basis_vectors[2], // (cx, cy, cz)
-        (
-            basis_vectors[0].0 + basis_vectors[2].0,
-            basis_vectors[0].1 + basis_vectors[2].1,
-            basis_vectors[0].2 + basis_vectors[2].2,
-        ),
-        (
-            basis_vectors[0].0 + basis_vectors[1].0 + basis_vectors[2].0,
-            basis_vectors[0].1 + basis_vectors[1].1 + basis_vectors[2].1,
-            basis_vectors[0].2 + basis_vectors[1].2 + basis_vectors[2].2,
-        ),
-        (
-            basis_vectors[1].0 + basis_vectors[2].0,
-            basis_vectors[1].1 + basis_vectors[2].1,
-            basis_vectors[1].2 + basis_vectors[2].2,
-        ),
+        (a[0], a[1], a[2]),
+        (a[0] + b[0], a[1] + b[1], a[2] + b[2]),
+        (b[0], b[1], b[2]),
+        (c[0], c[1], c[2]),
+        (a[0] + c[0], a[1] + c[1], a[2] + c[2]),
+        (a[0] + b[0] + c[0], a[1] + b[1] + c[1], a[2] + b[2] + c[2]),
+        (b[0] + c[0], b[1] + c[1], b[2] + c[2]),
     ]
 }
 
-/// 计算晶格的中心点
-pub fn get_lattice_center(vertices: &Vec<(f64, f64, f64)>) -> (f64, f64, f64) {
-    let (mut x_sum, mut y_sum, mut z_sum) = (0.0, 0.0, 0.0);
-    vertices.iter().for_each(|(x, y, z)| {
-        x_sum += x;
-        y_sum += y;
-        z_sum += z;
-    });
-    let count = vertices.len() as f64;
-    (x_sum / count, y_sum / count, z_sum / count)
-}
\ No newline at end of file
+/// 分数坐标转换为笛卡尔坐标
+pub fn frac_to_cart(lattice: &Mat3, frac: Vec3) -> Vec3 {
+    vec_mat(frac, lattice)
+}
+
+/// 笛卡尔坐标转换为分数坐标
+pub fn cart_to_frac(lattice: &Mat3, cart: Vec3) -> Vec3 {
+    let inv = inverse(lattice).expect("Singular lattice matrix");
+    vec_mat(cart, &inv)
+}
+
+/// 晶胞体积（Å³）
+pub fn cell_volume(lattice: &Mat3) -> f64 {
+    det(lattice).abs()
+}
+
+/// 倒易晶格矩阵（行向量为倒易基矢量，不含 2π 因子）
+pub fn reciprocal_lattice(lattice: &Mat3) -> Mat3 {
+    let inv = inverse(lattice).expect("Singular lattice matrix");
+    transpose(&inv)
+}
+
+/// 将分数坐标折回 [0, 1) 区间
+pub fn wrap_frac(frac: Vec3) -> Vec3 {
+    frac.map(|v| {
+        let w = v - v.floor();
+        if w >= 1.0 - 1e-10 { 0.0 } else { w }
+    })
+}
+
+/// 分数坐标转换为绘制坐标：笛卡尔坐标减去晶格中心，与前端显示的原子位置一致
+pub fn frac_to_display(lattice: &Mat3, frac: Vec3) -> Vec3 {
+    let center = scale(add(add(lattice[0], lattice[1]), lattice[2]), 0.5);
+    sub(frac_to_cart(lattice, frac), center)
+}
+
+/// 晶格约化：反复用其余基矢量缩短每个基矢量，直到不能再缩短
+/// 返回约化后的晶格与整数变换矩阵 T（约化晶格 = T·原晶格），结果保持右手系
+pub fn reduce_lattice(lattice: &Mat3) -> (Mat3, [[i32; 3]; 3]) {
+    let mut basis = *lattice;
+    let mut transform = [[1, 0, 0], [0, 1, 0], [0, 0, 1]];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for i in 0..3 {
+            for j in 0..3 {
+                if i == j {
+                    continue;
+                }
+                let factor = (dot(basis[i], basis[j]) / dot(basis[j], basis[j])).round();
+                if factor == 0.0 {
+                    continue;
+                }
+                let reduced = sub(basis[i], scale(basis[j], factor));
+                if norm(reduced) < norm(basis[i]) - 1e-8 {
+                    basis[i] = reduced;
+                    let row = transform[j];
+                    for (value, step) in transform[i].iter_mut().zip(row) {
+                        *value -= factor as i32 * step;
+                    }
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    // 按长度排序
+    let mut order = [0, 1, 2];
+    order.sort_by(|&i, &j| norm(basis[i]).total_cmp(&norm(basis[j])));
+    let mut reduced = order.map(|i| basis[i]);
+    let mut reduced_transform = order.map(|i| transform[i]);
+    if det(&reduced) < 0.0 {
+        reduced[2] = scale(reduced[2], -1.0);
+        reduced_transform[2] = reduced_transform[2].map(|v| -v);
+    }
+    (reduced, reduced_transform)
+}
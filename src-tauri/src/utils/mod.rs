@@ -1 +1,5 @@
-pub mod geometry;
\ No newline at end of file
+pub mod geometry;
+pub mod linalg;
+pub mod convex_hull;
+pub mod elements;
+pub mod colors;
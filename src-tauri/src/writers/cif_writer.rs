@@ -0,0 +1,52 @@
+use std::fmt::Write;
+use crate::parsers::atom_config::CrystalStructure;
+use crate::utils::geometry::{cell_volume, lattice_parameters};
+
+/// 将晶体结构写为 P1 空间群的 CIF 文本
+/// 原子列的顺序与 cif_parser 读取的顺序一致，可以直接重新打开
+pub fn write_cif(structure: &CrystalStructure, title: &str) -> String {
+    let [a, b, c, alpha, beta, gamma] = lattice_parameters(&structure.lattice);
+    let mut cif = String::new();
+
+    writeln!(cif, "# generated by Teacat").unwrap();
+    writeln!(cif, "data_{}", title.replace(char::is_whitespace, "_")).unwrap();
+    writeln!(cif, "_symmetry_space_group_name_H-M   'P 1'").unwrap();
+    writeln!(cif, "_cell_length_a   {:.8}", a).unwrap();
+    writeln!(cif, "_cell_length_b   {:.8}", b).unwrap();
+    writeln!(cif, "_cell_length_c   {:.8}", c).unwrap();
+    writeln!(cif, "_cell_angle_alpha   {:.8}", alpha).unwrap();
+    writeln!(cif, "_cell_angle_beta   {:.8}", beta).unwrap();
+    writeln!(cif, "_cell_angle_gamma   {:.8}", gamma).unwrap();
+    writeln!(cif, "_symmetry_Int_Tables_number   1").unwrap();
+    writeln!(cif, "_cell_volume   {:.8}", cell_volume(&structure.lattice)).unwrap();
+    writeln!(cif, "loop_").unwrap();
+    writeln!(cif, " _symmetry_equiv_pos_site_id").unwrap();
+    writeln!(cif, " _symmetry_equiv_pos_as_xyz").unwrap();
+    writeln!(cif, "  1  'x, y, z'").unwrap();
+    writeln!(cif, "loop_").unwrap();
+    writeln!(cif, " _atom_site_type_symbol").unwrap();
+    writeln!(cif, " _atom_site_label").unwrap();
+    writeln!(cif, " _atom_site_symmetry_multiplicity").unwrap();
+    writeln!(cif, " _atom_site_fract_x").unwrap();
+    writeln!(cif, " _atom_site_fract_y").unwrap();
+    writeln!(cif, " _atom_site_fract_z").unwrap();
+    writeln!(cif, " _atom_site_occupancy").unwrap();
+
+    for (index, site) in structure.sites.iter().enumerate() {
+        // 标签须以数字结尾（如 Fe1），否则按元素和序号重新命名
+        let label = if site.label.ends_with(|c: char| c.is_ascii_digit())
+            && site.label.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            site.label.clone()
+        } else {
+            format!("{}{}", site.element, index + 1)
+        };
+        writeln!(
+            cif,
+            "  {}  {}  1  {:.8}  {:.8}  {:.8}  {}",
+            site.element, label, site.frac[0], site.frac[1], site.frac[2], site.occupancy
+        )
+        .unwrap();
+    }
+    cif
+}
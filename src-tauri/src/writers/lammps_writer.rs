@@ -0,0 +1,78 @@
+use std::fmt::Write;
+use crate::parsers::atom_config::CrystalStructure;
+use crate::utils::elements::element;
+use crate::utils::geometry::{cart_to_frac, frac_to_cart, wrap_frac};
+use crate::utils::linalg::{dot, norm};
+
+/// 将晶体结构写为 LAMMPS data 文本
+/// 晶格旋转为 LAMMPS 要求的 a = (lx, 0, 0)、b = (xy, ly, 0)、c = (xz, yz, lz)，
+/// 倾斜因子约化到 LAMMPS 允许的范围（|xy|、|xz| ≤ lx/2，|yz| ≤ ly/2）；
+/// 原子类型按元素首次出现的顺序编号，Masses 节注释中写出元素符号；
+/// atom_style 为 atomic、charge 或 full，charge 与 full 的电荷写为 0，full 的分子编号均为 1
+pub fn write_lammps_data(structure: &CrystalStructure, comment: &str, atom_style: &str) -> Result<String, String> {
+    if !["atomic", "charge", "full"].contains(&atom_style) {
+        return Err(format!("Unsupported atom style {}", atom_style));
+    }
+    let [a, b, c] = structure.lattice;
+    let lx = norm(a);
+    let xy = dot(b, a) / lx;
+    let ly = (dot(b, b) - xy * xy).sqrt();
+    let xz = dot(c, a) / lx;
+    let yz = (dot(b, c) - xy * xz) / ly;
+    let lz = (dot(c, c) - xz * xz - yz * yz).sqrt();
+    let rotated = [[lx, 0.0, 0.0], [xy, ly, 0.0], [xz, yz, lz]];
+
+    // 约化倾斜因子：c → c - n·b、c → c - n·a、b → b - n·a，晶格点阵不变
+    let mut reduced = rotated;
+    let n = (reduced[2][1] / ly).round();
+    reduced[2] = [0, 1, 2].map(|k| reduced[2][k] - n * reduced[1][k]);
+    let n = (reduced[2][0] / lx).round();
+    reduced[2][0] -= n * lx;
+    let n = (reduced[1][0] / lx).round();
+    reduced[1][0] -= n * lx;
+
+    let mut species: Vec<&str> = Vec::new();
+    for site in &structure.sites {
+        if !species.contains(&site.element.as_str()) {
+            species.push(&site.element);
+        }
+    }
+
+    let mut data = String::new();
+    writeln!(data, "{}", comment.lines().next().unwrap_or_default()).unwrap();
+    writeln!(data).unwrap();
+    writeln!(data, "{} atoms", structure.sites.len()).unwrap();
+    writeln!(data, "{} atom types", species.len()).unwrap();
+    writeln!(data).unwrap();
+    writeln!(data, "0.0 {:.10} xlo xhi", lx).unwrap();
+    writeln!(data, "0.0 {:.10} ylo yhi", ly).unwrap();
+    writeln!(data, "0.0 {:.10} zlo zhi", lz).unwrap();
+    let tilts = [reduced[1][0], reduced[2][0], reduced[2][1]];
+    if tilts.iter().any(|t| t.abs() > 1e-10) {
+        writeln!(data, "{:.10} {:.10} {:.10} xy xz yz", tilts[0], tilts[1], tilts[2]).unwrap();
+    }
+
+    writeln!(data).unwrap();
+    writeln!(data, "Masses").unwrap();
+    writeln!(data).unwrap();
+    for (index, symbol) in species.iter().enumerate() {
+        let mass = element(symbol).map(|e| e.mass).unwrap_or(1.0);
+        writeln!(data, "{} {:.6} # {}", index + 1, mass, symbol).unwrap();
+    }
+
+    writeln!(data).unwrap();
+    writeln!(data, "Atoms # {}", atom_style).unwrap();
+    writeln!(data).unwrap();
+    for (index, site) in structure.sites.iter().enumerate() {
+        let atom_type = species.iter().position(|s| *s == site.element).unwrap() + 1;
+        let cart = frac_to_cart(&rotated, site.frac);
+        let [x, y, z] = frac_to_cart(&reduced, wrap_frac(cart_to_frac(&reduced, cart)));
+        let prefix = match atom_style {
+            "atomic" => format!("{} {}", index + 1, atom_type),
+            "charge" => format!("{} {} 0.0", index + 1, atom_type),
+            _ => format!("{} 1 {} 0.0", index + 1, atom_type),
+        };
+        writeln!(data, "{} {:.10} {:.10} {:.10}", prefix, x, y, z).unwrap();
+    }
+    Ok(data)
+}
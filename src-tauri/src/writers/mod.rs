@@ -0,0 +1,3 @@
+pub mod cif_writer;
+pub mod lammps_writer;
+pub mod vasp_writer;
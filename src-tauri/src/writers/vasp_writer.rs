@@ -0,0 +1,41 @@
+use std::fmt::Write;
+use crate::parsers::atom_config::CrystalStructure;
+
+/// 将晶体结构写为 VASP 5 格式的 POSCAR 文本（分数坐标）
+/// 原子按元素首次出现的顺序分组
+pub fn write_poscar(structure: &CrystalStructure, comment: &str) -> String {
+    let mut species: Vec<&str> = Vec::new();
+    for site in &structure.sites {
+        if !species.contains(&site.element.as_str()) {
+            species.push(&site.element);
+        }
+    }
+
+    let mut poscar = String::new();
+    writeln!(poscar, "{}", comment.lines().next().unwrap_or_default()).unwrap();
+    writeln!(poscar, "1.0").unwrap();
+    for row in &structure.lattice {
+        writeln!(poscar, "  {:.10}  {:.10}  {:.10}", row[0], row[1], row[2]).unwrap();
+    }
+    writeln!(poscar, "  {}", species.join("  ")).unwrap();
+    let counts: Vec<String> = species
+        .iter()
+        .map(|element| {
+            let count = structure.sites.iter().filter(|s| s.element == *element).count();
+            count.to_string()
+        })
+        .collect();
+    writeln!(poscar, "  {}", counts.join("  ")).unwrap();
+    writeln!(poscar, "Direct").unwrap();
+    for element in &species {
+        for site in structure.sites.iter().filter(|s| s.element == *element) {
+            writeln!(
+                poscar,
+                "  {:.10}  {:.10}  {:.10}  {}",
+                site.frac[0], site.frac[1], site.frac[2], site.label
+            )
+            .unwrap();
+        }
+    }
+    poscar
+}